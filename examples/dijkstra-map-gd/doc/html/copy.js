@@ -0,0 +1,34 @@
+/*
+ * Buttons copying the content of code blocks to the clipboard.
+ *
+ * Each code block is in a `<div class="code-block">`, with a `.copy-button`
+ * before the `<pre>` containing the code.
+ */
+(function () {
+	function copy(text) {
+		if (navigator.clipboard) {
+			return navigator.clipboard.writeText(text);
+		}
+		// the clipboard API is only available in secure contexts
+		const textarea = document.createElement("textarea");
+		textarea.value = text;
+		document.body.append(textarea);
+		textarea.select();
+		const copied = document.execCommand("copy");
+		textarea.remove();
+		return copied ? Promise.resolve() : Promise.reject();
+	}
+
+	for (const button of document.querySelectorAll(".code-block > .copy-button")) {
+		button.addEventListener("click", () => {
+			const code = button.parentElement.querySelector("pre:last-child > code");
+			if (!code) {
+				return;
+			}
+			copy(code.textContent)
+				.then(() => (button.textContent = "Copied!"))
+				.catch(() => (button.textContent = "Failed"))
+				.finally(() => setTimeout(() => (button.textContent = "Copy"), 2000));
+		});
+	}
+})();
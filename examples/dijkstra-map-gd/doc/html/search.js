@@ -0,0 +1,70 @@
+/*
+ * Client-side search of the classes, methods and properties.
+ *
+ * The index is loaded from `search_index.json`, so this only works when the
+ * documentation is served over http(s).
+ */
+(function () {
+	const input = document.getElementById("search-input");
+	const results = document.getElementById("search-results");
+	if (!input || !results) {
+		return;
+	}
+	let index = null;
+
+	function load() {
+		if (index !== null) {
+			return Promise.resolve(index);
+		}
+		return fetch("./search_index.json")
+			.then((response) => response.json())
+			.then((items) => (index = items))
+			.catch(() => (index = []));
+	}
+
+	// items are found by their name or one of their aliases
+	function score(item, query) {
+		const names = [item.name, ...item.aliases].map((name) => name.toLowerCase());
+		if (names.includes(query)) {
+			return 0;
+		} else if (names.some((name) => name.startsWith(query))) {
+			return 1;
+		} else if (names.some((name) => name.includes(query))) {
+			return 2;
+		} else if (item.summary.toLowerCase().includes(query)) {
+			return 3;
+		}
+		return -1;
+	}
+
+	function render(query) {
+		results.replaceChildren();
+		if (query === "") {
+			return;
+		}
+		const matches = index
+			.map((item) => [score(item, query), item])
+			.filter(([score]) => score >= 0)
+			.sort(([score1, item1], [score2, item2]) => score1 - score2 || item1.name.localeCompare(item2.name))
+			.slice(0, 20);
+		for (const [, item] of matches) {
+			const entry = document.createElement("li");
+			const link = document.createElement("a");
+			link.href = "./" + item.url;
+			link.textContent = item.class && item.kind !== "class" ? item.class + "." + item.name : item.name;
+			const kind = document.createElement("span");
+			kind.className = "search-kind";
+			kind.textContent = item.kind;
+			const summary = document.createElement("div");
+			summary.className = "search-summary";
+			summary.textContent = item.summary;
+			entry.append(link, " ", kind, summary);
+			results.append(entry);
+		}
+	}
+
+	input.addEventListener("input", () => {
+		const query = input.value.trim().toLowerCase();
+		load().then(() => render(query));
+	});
+})();
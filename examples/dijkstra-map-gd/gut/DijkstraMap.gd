@@ -2,7 +2,7 @@ extends "res://addons/gut/test.gd"
 
 # This file was automatically generated using [gdnative-doc-rs](https://github.com/arnaudgolfouse/gdnative-doc-rs)
 # 
-# Crate: dijkstra-map-gd
+# Crate: dijkstra-map-gd 0.1.0
 # Source file: lib.rs
 
 func test_new():
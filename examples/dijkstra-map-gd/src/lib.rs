@@ -311,6 +311,8 @@ impl Interface {
     /// Given a point, returns the id of the next point along the shortest path
     /// toward the target.
     ///
+    /// @requires [recalculate](#func-recalculate) must have been called.
+    ///
     /// # Errors
     ///
     /// This function return `-1` if there is no path from the point to the target.
@@ -359,6 +361,8 @@ impl Interface {
     /// This is the central function of the library, the one that actually uses
     /// Dijkstra's algorithm.
     ///
+    /// @complexity O((V + E) log V), with V points and E connections.
+    ///
     /// # Parameters
     ///
     /// - `origin` : ID of the origin point, or array of IDs (preferably
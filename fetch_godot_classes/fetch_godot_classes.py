@@ -2,21 +2,56 @@
 # -*- coding: utf-8 -*-
 """
 @author: arnaudgolfouse
-@brief:  script that get godot classes for the 3.2, 3.3, 3.4 and 3.5 branches. Note that this is probably not portable :/
+@brief:  script that get godot classes for the 3.2, 3.3, 3.4, 3.5, 4.0, 4.1, 4.2 and 4.3 branches. Note that this is probably not portable :/
+
+Only some of the branches can be given as arguments, like `./fetch_godot_classes.py 4.0 4.1`.
+
+For godot 4, the classes can instead be read from the `extension_api.json` of a version (as dumped by
+`godot --dump-extension-api`), like `./fetch_godot_classes.py --extension-api 4.3=path/to/extension_api.json`.
 """
 
 # %%
+import json
 import subprocess
 import os
+import sys
 from pathlib import Path
 
 GODOT_REPOSITORY_URL = "https://github.com/godotengine/godot"
 GODOT_REPOSITORY_PATH = "godot"
 CLASSES_PATH = "doc/classes"
-VERSIONS = ["3.2", "3.3", "3.4", "3.5"]
+VERSIONS = ["3.2", "3.3", "3.4", "3.5", "4.0", "4.1", "4.2", "4.3"]
 
 
 script_path = Path(__file__).absolute().parent
+
+
+def write_classes_file(version: str, classes_names: list, source: str):
+    file_content = "// This file was automatically generated from " + source + "\n"
+    file_content += "[\n"
+    for class_name in sorted(classes_names):
+        file_content += f"    \"{class_name}\",\n"
+    file_content += "]"
+    file_path = script_path / f"godot_classes-{version}.txt"
+    file_path.write_text(file_content)
+
+
+if sys.argv[1:2] == ["--extension-api"]:
+    for argument in sys.argv[2:]:
+        version, extension_api_path = argument.split("=", 1)
+        with open(extension_api_path) as extension_api_file:
+            extension_api = json.load(extension_api_file)
+        # `Nil` has no documentation page, and `Variant` has one without being a type of the api
+        classes_names = ["Variant"]
+        for builtin_class in extension_api["builtin_classes"]:
+            if builtin_class["name"] != "Nil":
+                classes_names.append(builtin_class["name"])
+        for godot_class in extension_api["classes"]:
+            classes_names.append(godot_class["name"])
+        write_classes_file(version, classes_names, "the classes of the extension_api.json of " +
+                           extension_api["header"]["version_full_name"])
+    sys.exit()
+
 os.chdir(script_path)
 
 if not Path(GODOT_REPOSITORY_PATH).exists():
@@ -33,16 +68,21 @@ if not Path(GODOT_REPOSITORY_PATH).exists():
     git_remote_add_args.append(GODOT_REPOSITORY_URL)
     subprocess.call(git_remote_add_args)
 
-    subprocess.call(["git", "fetch", "--depth", "1"])
+    os.chdir(script_path)
 
-os.chdir(script_path)
+versions = sys.argv[1:] or VERSIONS
 
-for version in VERSIONS:
-    godot_classes_file: str = f"godot_classes-{version}.txt"
+# fetch the branches, even if the repository was created before they were added to VERSIONS
+os.chdir(GODOT_REPOSITORY_PATH)
+for version in versions:
+    subprocess.call(["git", "fetch", "--depth", "1", "origin",
+                    f"{version}:refs/remotes/origin/{version}"])
+os.chdir(script_path)
 
+for version in versions:
     # change branch
     os.chdir(GODOT_REPOSITORY_PATH)
-    subprocess.call(["git", "checkout", version])
+    subprocess.call(["git", "checkout", "--detach", f"origin/{version}"])
 
     # get those files
     classes_names = []
@@ -50,14 +90,7 @@ for version in VERSIONS:
         file_name = file.name
         if (not file_name.startswith("@")) and file_name.endswith(".xml"):
             classes_names.append(file_name[:-4])
-    classes_names.sort()
     os.chdir(script_path)
 
-    file_content = "// This file was automatically generated from the file names at " + \
-        GODOT_REPOSITORY_URL + "/tree/" + version + "/doc/classes\n"
-    file_content += "[\n"
-    for class_name in classes_names:
-        file_content += f"    \"{class_name}\",\n"
-    file_content += "]"
-    file_path = Path(godot_classes_file).absolute()
-    file_path.write_text(file_content)
+    write_classes_file(version, classes_names, "the file names at " +
+                       GODOT_REPOSITORY_URL + "/tree/" + version + "/doc/classes")
@@ -1,11 +1,16 @@
 use clap::{Arg, ArgAction, Command};
 use gdnative_doc::{
-    backend::BuiltinBackend, init_logger, Builder, ConfigFile, LevelFilter, Package,
+    backend::BuiltinBackend,
+    diagnostics::{Lint, LintLevel},
+    init_logger, Builder, ConfigFile, LevelFilter, Package,
 };
 use std::path::PathBuf;
 
 fn main() -> anyhow::Result<()> {
     let matches = make_app().get_matches();
+    if let Some(("explain", matches)) = matches.subcommand() {
+        return explain(matches.get_one::<String>("code").unwrap());
+    }
     init_logger(match matches.get_count("verbosity") {
         0 => LevelFilter::Info,
         1 => LevelFilter::Debug,
@@ -14,9 +19,23 @@ fn main() -> anyhow::Result<()> {
 
     let mut builder = Builder::new();
 
-    if let Some(config_path) = matches.get_one::<String>("config") {
-        builder = builder.user_config(ConfigFile::load_from_path(PathBuf::from(config_path))?);
+    let mut config = match matches.get_one::<String>("config") {
+        Some(config_path) => ConfigFile::load_from_path(PathBuf::from(config_path))?,
+        None => ConfigFile::default(),
+    };
+    if matches.get_flag("deny_broken_links") {
+        let lints = config.lints.get_or_insert_with(Default::default);
+        // the flag overrides the level set in the configuration, by code or by name
+        lints.retain(|code_or_name, _| Lint::find(code_or_name) != Some(Lint::BrokenLink));
+        lints.insert(Lint::BrokenLink.name().to_string(), LintLevel::Deny);
+    }
+    if let Some(patterns) = matches.get_many::<String>("exclude") {
+        config
+            .exclude_classes
+            .get_or_insert_with(Default::default)
+            .extend(patterns.cloned());
     }
+    builder = builder.user_config(config);
     if let Some(output_dir) = matches.get_one::<String>("markdown") {
         builder = builder.add_backend(BuiltinBackend::Markdown, PathBuf::from(output_dir));
     }
@@ -26,17 +45,65 @@ fn main() -> anyhow::Result<()> {
     if let Some(output_dir) = matches.get_one::<String>("gut") {
         builder = builder.add_backend(BuiltinBackend::Gut, PathBuf::from(output_dir));
     }
+    if let Some(output_dir) = matches.get_one::<String>("godot_xml") {
+        builder = builder.add_backend(BuiltinBackend::GodotXml, PathBuf::from(output_dir));
+    }
+    if let Some(output_dir) = matches.get_one::<String>("json") {
+        builder = builder.add_backend(BuiltinBackend::Json, PathBuf::from(output_dir));
+    }
+    if let Some(output_dir) = matches.get_one::<String>("mdbook") {
+        builder = builder.add_backend(BuiltinBackend::MdBook, PathBuf::from(output_dir));
+    }
+    if let Some(output_dir) = matches.get_one::<String>("docusaurus") {
+        builder = builder.add_backend(BuiltinBackend::Docusaurus, PathBuf::from(output_dir));
+    }
+    if let Some(output_dir) = matches.get_one::<String>("github_wiki") {
+        builder = builder.add_backend(BuiltinBackend::GithubWiki, PathBuf::from(output_dir));
+    }
 
+    if let Some(manifest_path) = matches.get_one::<String>("manifest_path") {
+        builder = builder.manifest_path(PathBuf::from(manifest_path))
+    }
+    if let Some(features) = matches.get_many::<String>("features") {
+        let features: Vec<_> = features.map(String::as_str).collect();
+        builder = builder.features(&features)
+    }
+    if matches.get_flag("no_default_features") {
+        builder = builder.no_default_features()
+    }
+    if matches.get_flag("all_features") {
+        builder = builder.all_features()
+    }
     if let Some(package_name) = matches.get_one::<String>("package") {
         builder = builder.package(Package::Name(package_name.to_string()))
     }
     if let Some(root_file) = matches.get_one::<String>("root_file") {
         builder = builder.package(Package::Root(PathBuf::from(root_file)))
     }
+    if let Some(revision) = matches.get_one::<String>("revision") {
+        builder = builder.at_revision(revision)
+    }
+    if matches.get_flag("dry_run") {
+        builder = builder.dry_run()
+    }
 
+    if matches.get_flag("check") {
+        return Ok(builder.check()?);
+    }
     Ok(builder.build()?)
 }
 
+/// Print the explanation of the lint with the given code or name.
+fn explain(code: &str) -> anyhow::Result<()> {
+    match Lint::find(code) {
+        Some(lint) => {
+            println!("{}\n\n{}", lint, lint.explanation());
+            Ok(())
+        }
+        None => anyhow::bail!("'{}' is not a valid lint code", code),
+    }
+}
+
 fn make_app() -> Command {
     Command::new(env!("CARGO_PKG_NAME"))
         .version(env!("CARGO_PKG_VERSION"))
@@ -53,7 +120,7 @@ fn make_app() -> Command {
                 .long("config")
                 .short('c')
                 .value_name("PATH")
-                .help("Configuration file for gdnative-doc"),
+                .help("Configuration file for gdnative-doc (TOML, JSON or YAML)"),
         )
         .arg(
             Arg::new("markdown")
@@ -74,6 +141,36 @@ fn make_app() -> Command {
                 .value_name("PATH")
                 .help("Directory in which to put the gut output"),
         )
+        .arg(
+            Arg::new("godot_xml")
+                .long("godot-xml")
+                .value_name("PATH")
+                .help("Directory in which to put the godot XML class reference"),
+        )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .value_name("PATH")
+                .help("Directory in which to put the JSON dump of the documentation"),
+        )
+        .arg(
+            Arg::new("mdbook")
+                .long("mdbook")
+                .value_name("PATH")
+                .help("Directory in which to put the mdBook"),
+        )
+        .arg(
+            Arg::new("docusaurus")
+                .long("docusaurus")
+                .value_name("PATH")
+                .help("Directory in which to put the Docusaurus pages"),
+        )
+        .arg(
+            Arg::new("github_wiki")
+                .long("github-wiki")
+                .value_name("PATH")
+                .help("Directory in which to put the GitHub wiki pages"),
+        )
         .arg(
             Arg::new("package")
                 .long("package")
@@ -84,6 +181,36 @@ fn make_app() -> Command {
 This is useful if you are working within a workspace.",
                 ),
         )
+        .arg(
+            Arg::new("manifest_path")
+                .long("manifest-path")
+                .value_name("PATH")
+                .help(
+                    r"Path to the Cargo.toml of the package (or workspace) for which to build the documentation.
+Defaults to the one in the current directory.",
+                ),
+        )
+        .arg(
+            Arg::new("features")
+                .long("features")
+                .short('F')
+                .value_name("FEATURES")
+                .action(ArgAction::Append)
+                .value_delimiter(',')
+                .help("Features of the package to activate, separated by commas (can be repeated)"),
+        )
+        .arg(
+            Arg::new("no_default_features")
+                .long("no-default-features")
+                .action(ArgAction::SetTrue)
+                .help("Do not activate the `default` feature of the package"),
+        )
+        .arg(
+            Arg::new("all_features")
+                .long("all-features")
+                .action(ArgAction::SetTrue)
+                .help("Activate all the features of the package"),
+        )
         .arg(
             Arg::new("root_file")
                 .long("root_file")
@@ -92,6 +219,42 @@ This is useful if you are working within a workspace.",
                     r"Path to the root file of the package for which to build the documentation.",
                 ),
         )
+        .arg(
+            Arg::new("revision")
+                .long("rev")
+                .value_name("REVISION")
+                .help(
+                    r"Git revision (commit, branch, tag...) at which to document the package.
+The sources are checked out in a temporary directory.",
+                ),
+        )
+        .arg(
+            Arg::new("exclude")
+                .long("exclude")
+                .value_name("PATTERN")
+                .action(ArgAction::Append)
+                .help("Leave out the classes matching this glob pattern (can be repeated)"),
+        )
+        .arg(
+            Arg::new("deny_broken_links")
+                .long("deny-broken-links")
+                .action(ArgAction::SetTrue)
+                .help("Fail if a link in the documentation cannot be resolved"),
+        )
+        .arg(
+            Arg::new("check")
+                .long("check")
+                .action(ArgAction::SetTrue)
+                .help(
+                "Fail if the generated files differ from the ones on disk, without writing them",
+            ),
+        )
+        .arg(
+            Arg::new("dry_run")
+                .long("dry-run")
+                .action(ArgAction::SetTrue)
+                .help("Only print the files that would be written, without writing them"),
+        )
         .arg(
             Arg::new("verbosity")
                 .long("verbose")
@@ -99,4 +262,14 @@ This is useful if you are working within a workspace.",
                 .action(ArgAction::Count)
                 .help("Use verbose output (-vv very verbose)"),
         )
+        .subcommand(
+            Command::new("explain")
+                .about("Describe a diagnostic code, and how to fix it")
+                .arg(
+                    Arg::new("code")
+                        .required(true)
+                        .value_name("CODE")
+                        .help("Code (like GDOC001) or name (like broken_link) of the lint"),
+                ),
+        )
 }
@@ -0,0 +1,48 @@
+/*
+ * Light and dark themes.
+ *
+ * The theme chosen with the toggle button is remembered; otherwise the default
+ * theme of the documentation (`data-default-theme` on the root element) is used,
+ * where `auto` follows the preference of the system.
+ *
+ * This is loaded in `<head>`, so that the theme is applied before the page is
+ * displayed.
+ */
+(function () {
+	const KEY = "gdnative-doc-theme";
+	const root = document.documentElement;
+
+	function stored() {
+		try {
+			return localStorage.getItem(KEY);
+		} catch (e) {
+			return null;
+		}
+	}
+
+	function defaultTheme() {
+		const theme = root.dataset.defaultTheme || "dark";
+		if (theme === "auto") {
+			return window.matchMedia("(prefers-color-scheme: light)").matches ? "light" : "dark";
+		}
+		return theme;
+	}
+
+	root.dataset.theme = stored() || defaultTheme();
+
+	document.addEventListener("DOMContentLoaded", () => {
+		const button = document.getElementById("theme-toggle");
+		if (!button) {
+			return;
+		}
+		button.addEventListener("click", () => {
+			const theme = root.dataset.theme === "light" ? "dark" : "light";
+			root.dataset.theme = theme;
+			try {
+				localStorage.setItem(KEY, theme);
+			} catch (e) {
+				// the choice is not remembered
+			}
+		});
+	});
+})();
@@ -18,6 +18,26 @@ pub trait Callbacks {
     /// You can find inspiration about how to implement this in the source code, for
     /// example in `src/backend/html.rs`.
     fn generate_files(&mut self, generator: Generator) -> HashMap<String, String>;
+    /// Directory, relative to the output directory, in which the generated pages
+    /// are written.
+    ///
+    /// Local images referenced by the documentation are copied in its `images`
+    /// subdirectory. If this returns `None`, they are not copied.
+    ///
+    /// **Default**: `None`
+    fn pages_directory(&self) -> Option<&'static str> {
+        None
+    }
+    /// Whether this backend writes one page per class, so that links to the
+    /// classes documented in the crate (and to their members) resolve to these
+    /// pages, like `./MyClass.<extension>`.
+    ///
+    /// If this returns `false`, these links are left unresolved.
+    ///
+    /// **Default**: `true`
+    fn links_to_documented_classes(&self) -> bool {
+        true
+    }
     /// Called before encoding each method.
     ///
     /// **Default**: does nothing
@@ -40,7 +60,8 @@ impl dyn Callbacks {
     /// ________
     /// ```
     ///
-    /// With appropriate linking.
+    /// With appropriate linking. Asynchronous methods are followed by an `*async*`
+    /// marker, and RPC methods by the keyword of their mode (like `*remote*`).
     pub fn start_method_default(&mut self, s: &mut String, property: &Resolver, method: &Method) {
         let link = &format!("<a id=\"func-{}\"></a>", method.name);
         self.encode(
@@ -51,7 +72,13 @@ impl dyn Callbacks {
             ],
         );
         let mut method_header = String::from("func ");
-        method_header.push_str(&method.name);
+        if method.deprecated.is_some() {
+            self.encode(s, vec![Event::Text(CowStr::Borrowed(&method_header))]);
+            method_header.clear();
+            self.encode(s, super::item_name(&method.name, true));
+        } else {
+            method_header.push_str(&method.name);
+        }
         method_header.push('(');
         for (index, (name, typ, _)) in method.parameters.iter().enumerate() {
             method_header.push_str(name);
@@ -66,6 +93,7 @@ impl dyn Callbacks {
         method_header.push_str(") -> ");
         let mut last_events = vec![Event::Text(CowStr::Borrowed(&method_header))];
         last_events.extend(property.encode_type(&method.return_type));
+        last_events.extend(super::method_markers(method));
         last_events.push(Event::End(Tag::Heading(HeadingLevel::H3, None, Vec::new())));
         last_events.push(Event::Rule);
         self.encode(s, last_events);
@@ -87,17 +115,17 @@ impl dyn Callbacks {
         resolver: &Resolver,
         property: &Property,
     ) {
-        let link = &format!(
-            "<a id=\"property-{}\"></a> {}: ",
-            property.name, property.name
-        );
-        self.encode(
-            s,
-            vec![
-                Event::Start(Tag::Heading(HeadingLevel::H3, None, Vec::new())),
-                Event::Html(CowStr::Borrowed(link)),
-            ],
-        );
+        let link = &format!("<a id=\"property-{}\"></a> ", property.name);
+        let mut first_events = vec![
+            Event::Start(Tag::Heading(HeadingLevel::H3, None, Vec::new())),
+            Event::Html(CowStr::Borrowed(link)),
+        ];
+        first_events.extend(super::item_name(
+            &property.name,
+            property.deprecated.is_some(),
+        ));
+        first_events.push(Event::Text(CowStr::Borrowed(": ")));
+        self.encode(s, first_events);
         let mut last_events = resolver.encode_type(&property.typ);
         last_events.push(Event::End(Tag::Heading(HeadingLevel::H3, None, Vec::new())));
         last_events.push(Event::Rule);
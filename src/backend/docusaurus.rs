@@ -0,0 +1,128 @@
+//! Backend generating [MDX](https://mdxjs.com/) pages for a
+//! [Docusaurus](https://docusaurus.io/) site.
+
+use super::{push_escaped, Callbacks, Generator, MarkdownCallbacks, Page};
+use pulldown_cmark::Event;
+use std::{collections::HashMap, fmt::Write as _};
+
+pub(crate) struct DocusaurusCallbacks {
+    markdown: MarkdownCallbacks,
+}
+
+impl Default for DocusaurusCallbacks {
+    fn default() -> Self {
+        Self {
+            markdown: MarkdownCallbacks::mdx(),
+        }
+    }
+}
+
+impl Callbacks for DocusaurusCallbacks {
+    fn extension(&self) -> &'static str {
+        "mdx"
+    }
+
+    fn pages_directory(&self) -> Option<&'static str> {
+        Some("")
+    }
+
+    fn generate_files(&mut self, mut generator: Generator) -> HashMap<String, String> {
+        let pages = generator.pages();
+        let sidebar = sidebar(&generator, &pages);
+        // the front matter is replaced by the one of Docusaurus, and pages have
+        // their own table of contents
+        generator.options.markdown.front_matter = None;
+        generator.options.table_of_contents = false;
+        let mut files = self.markdown.generate_files(generator);
+
+        for (position, page) in pages.iter().enumerate() {
+            if let Some(content) = files.get_mut(&format!("{}.mdx", page.file_stem)) {
+                let mut front_matter = String::from("---\nid: ");
+                yaml_string(&mut front_matter, &page.file_stem);
+                front_matter.push_str("\ntitle: ");
+                yaml_string(&mut front_matter, &page.title);
+                let _ = write!(
+                    front_matter,
+                    "\nsidebar_position: {}\n---\n\n",
+                    position + 1
+                );
+                content.insert_str(0, &front_matter);
+            }
+        }
+        files.insert(String::from("sidebar.js"), sidebar);
+        files
+    }
+
+    fn encode(&mut self, s: &mut String, events: Vec<Event<'_>>) {
+        self.markdown.encode(s, events)
+    }
+}
+
+/// Content of `sidebar.js`, a fragment to include in the `sidebars.js` of the
+/// site.
+///
+/// This will look like:
+/// ```js
+/// module.exports = (prefix = '') => [
+///   { type: 'doc', id: prefix + 'index', label: 'my-crate' },
+///   { type: 'category', label: 'Classes', items: [
+///     { type: 'doc', id: prefix + 'MyClass', label: 'MyClass' },
+///   ] },
+/// ];
+/// ```
+fn sidebar(generator: &Generator, pages: &[Page]) -> String {
+    fn doc_item(sidebar: &mut String, indent: &str, page: &Page) {
+        let _ = write!(sidebar, "{}{{ type: 'doc', id: prefix + ", indent);
+        js_string(sidebar, &page.file_stem);
+        sidebar.push_str(", label: ");
+        js_string(sidebar, &page.title);
+        sidebar.push_str(" },\n");
+    }
+
+    let mut sidebar = String::from(
+        "// Sidebar of the generated documentation.
+//
+// Use it in `sidebars.js` with the path of the output directory in `docs`:
+// `apiSidebar: require('./docs/api/sidebar.js')('api/')`
+module.exports = (prefix = '') => [\n",
+    );
+    let class_count = generator.documentation.classes.len();
+    let (other_pages, class_pages) = pages.split_at(pages.len() - class_count);
+    for page in other_pages {
+        doc_item(&mut sidebar, "  ", page);
+    }
+
+    let grouped = class_pages.iter().any(|page| page.category.is_some());
+    let mut class_pages = class_pages.iter().peekable();
+    while let Some(first) = class_pages.peek() {
+        let category = first.category.clone();
+        let label = match &category {
+            Some(category) => category.as_str(),
+            None if grouped => "Other",
+            None => "Classes",
+        };
+        sidebar.push_str("  { type: 'category', label: ");
+        js_string(&mut sidebar, label);
+        sidebar.push_str(", items: [\n");
+        while let Some(page) = class_pages.next_if(|page| page.category == category) {
+            doc_item(&mut sidebar, "    ", page);
+        }
+        sidebar.push_str("  ] },\n");
+    }
+    sidebar.push_str("];\n");
+    sidebar
+}
+
+/// Write `string` as a double-quoted YAML string.
+fn yaml_string(s: &mut String, string: &str) {
+    s.push('"');
+    push_escaped(s, string, &['"']);
+    s.push('"');
+}
+
+/// Write `string` as a single-quoted javascript string.
+fn js_string(s: &mut String, string: &str) {
+    s.push('\'');
+    push_escaped(s, string, &['\'']);
+    s.push('\'');
+}
@@ -0,0 +1,97 @@
+//! Backend generating the pages of a
+//! [GitHub wiki](https://docs.github.com/en/communities/documenting-your-project-with-wikis).
+
+use super::{push_escaped, Callbacks, Generator, MarkdownCallbacks, Page};
+use pulldown_cmark::Event;
+use std::{collections::HashMap, fmt::Write as _};
+
+pub(crate) struct GithubWikiCallbacks {
+    markdown: MarkdownCallbacks,
+}
+
+impl Default for GithubWikiCallbacks {
+    fn default() -> Self {
+        Self {
+            markdown: MarkdownCallbacks::github_wiki(),
+        }
+    }
+}
+
+impl Callbacks for GithubWikiCallbacks {
+    fn extension(&self) -> &'static str {
+        "md"
+    }
+
+    fn pages_directory(&self) -> Option<&'static str> {
+        Some("")
+    }
+
+    fn generate_files(&mut self, mut generator: Generator) -> HashMap<String, String> {
+        let sidebar = sidebar(&generator, &generator.pages());
+        // the wiki has a page per file, and renders front matter as text
+        generator.options.markdown.front_matter = None;
+        generator.options.markdown.single_file = false;
+        let mut files = self.markdown.generate_files(generator);
+        files.insert(String::from("_Sidebar.md"), sidebar);
+        files
+    }
+
+    fn encode(&mut self, s: &mut String, events: Vec<Event<'_>>) {
+        self.markdown.encode(s, events)
+    }
+}
+
+/// Content of `_Sidebar.md`, displayed next to every page of the wiki.
+///
+/// This will look like:
+/// ```text
+/// **[my-crate](Home)**
+///
+/// - [Guide](guide-guide)
+/// - [graph](module-graph)
+///
+/// **Classes**
+///
+/// - [MyClass](MyClass)
+/// ```
+///
+/// If classes have categories, each category has its own list.
+fn sidebar(generator: &Generator, pages: &[Page]) -> String {
+    fn item(sidebar: &mut String, page: &Page) {
+        sidebar.push_str("- [");
+        push_escaped(sidebar, &page.title, &['[', ']']);
+        let _ = writeln!(sidebar, "]({})", page.file_stem);
+    }
+
+    let class_count = generator.documentation.classes.len();
+    let (other_pages, class_pages) = pages.split_at(pages.len() - class_count);
+    let mut sidebar = String::new();
+    if let Some((index, other_pages)) = other_pages.split_first() {
+        sidebar.push_str("**[");
+        sidebar.push_str(&index.title);
+        sidebar.push_str("](Home)**\n");
+        // guides and modules
+        if !other_pages.is_empty() {
+            sidebar.push('\n');
+        }
+        for page in other_pages {
+            item(&mut sidebar, page);
+        }
+    }
+
+    let grouped = class_pages.iter().any(|page| page.category.is_some());
+    let mut pages = class_pages.iter().peekable();
+    while let Some(first) = pages.peek() {
+        let category = first.category.clone();
+        let title = match &category {
+            Some(category) => category.as_str(),
+            None if grouped => "Other",
+            None => "Classes",
+        };
+        let _ = write!(sidebar, "\n**{}**\n\n", title);
+        while let Some(page) = pages.next_if(|page| page.category == category) {
+            item(&mut sidebar, page);
+        }
+    }
+    sidebar
+}
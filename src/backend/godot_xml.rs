@@ -0,0 +1,475 @@
+//! Backend generating the XML class reference used by godot's built-in help
+//! (the `doc_classes` format), with descriptions converted to BBCode.
+
+use super::{class_file_stem, deprecation_notice, inline_events, tags_list, Callbacks, Generator};
+use crate::{
+    documentation::{GdnativeClass, Type},
+    GodotVersion,
+};
+use pulldown_cmark::{CodeBlockKind, Event, Tag};
+use std::{collections::HashMap, fmt::Write as _, sync::Arc};
+
+#[derive(Default)]
+pub(crate) struct GodotXmlCallbacks {
+    /// Links to the godot documentation, see [`Resolver::godot_items`].
+    ///
+    /// [`Resolver::godot_items`]: super::Resolver::godot_items
+    godot_items: Arc<HashMap<String, String>>,
+    /// Godot classes, by the url of their documentation.
+    godot_classes: Arc<HashMap<String, String>>,
+    /// See [`Resolver::rust_to_godot`](super::Resolver::rust_to_godot).
+    rust_to_godot: Arc<HashMap<String, String>>,
+    /// Lists being encoded, with the number of their next item if they are
+    /// ordered.
+    lists: Vec<Option<u64>>,
+    /// Start (in the output) and destination of the link being encoded.
+    link: Option<(usize, String)>,
+    /// Documented classes, by the link to their page.
+    documented_classes: Arc<HashMap<String, String>>,
+    /// Name of the class being encoded.
+    current_class: String,
+}
+
+impl Callbacks for GodotXmlCallbacks {
+    fn extension(&self) -> &'static str {
+        "xml"
+    }
+
+    fn links_to_documented_classes(&self) -> bool {
+        // these links become references, see `generate_files`
+        false
+    }
+
+    fn generate_files(&mut self, generator: Generator) -> HashMap<String, String> {
+        self.godot_items = Arc::new(generator.resolver.godot_items.clone());
+        self.godot_classes = Arc::new(
+            (self.godot_items.iter())
+                .filter(|(name, url)| {
+                    url.ends_with(&format!("/class_{}.html", name.to_lowercase()))
+                })
+                .map(|(name, url)| (url.clone(), name.clone()))
+                .collect(),
+        );
+        self.rust_to_godot = Arc::new(generator.resolver.rust_to_godot.clone());
+        // links to the documented classes become references, like for godot classes
+        let mut resolver = generator.resolver.clone();
+        resolver.add_documented_classes(generator.documentation, self.extension());
+        self.documented_classes = Arc::new(
+            resolver
+                .documented_classes
+                .iter()
+                .map(|(name, link)| (link.clone(), name.clone()))
+                .collect(),
+        );
+        let generator = Generator {
+            resolver: &resolver,
+            ..generator
+        };
+
+        let this = &*self;
+        let files = generator.map_classes(|name, class| {
+            let mut encoder = Self {
+                godot_items: this.godot_items.clone(),
+                godot_classes: this.godot_classes.clone(),
+                rust_to_godot: this.rust_to_godot.clone(),
+                documented_classes: this.documented_classes.clone(),
+                ..Self::default()
+            };
+            // `Self::member` links resolve to the members of this class
+            let resolver = generator.resolver.for_class(name);
+            (
+                format!("{}.xml", class_file_stem(name)),
+                encoder.class_xml(&generator.with_resolver(&resolver), name, class),
+            )
+        });
+        files.into_iter().collect()
+    }
+
+    fn encode(&mut self, s: &mut String, events: Vec<Event<'_>>) {
+        for event in events {
+            match event {
+                Event::Start(tag) => self.start_tag(s, tag),
+                Event::End(tag) => self.end_tag(s, tag),
+                Event::Text(text) => s.push_str(&text),
+                Event::Code(code) => {
+                    s.push_str("[code]");
+                    s.push_str(&code);
+                    s.push_str("[/code]");
+                }
+                Event::SoftBreak => s.push(' '),
+                Event::HardBreak | Event::Rule => s.push('\n'),
+                Event::TaskListMarker(checked) => s.push_str(if checked { "☑ " } else { "☐ " }),
+                // anchors and footnotes have no equivalent
+                Event::Html(_) | Event::FootnoteReference(_) => {}
+            }
+        }
+    }
+}
+
+impl GodotXmlCallbacks {
+    /// Generate the XML file of `class`.
+    ///
+    /// This will look like:
+    /// ```xml
+    /// <?xml version="1.0" encoding="UTF-8" ?>
+    /// <class name="MyClass" inherits="Node" version="3.5">
+    ///     <brief_description>
+    ///         <summary>
+    ///     </brief_description>
+    ///     <description>
+    ///         <documentation>
+    ///     </description>
+    ///     <tutorials>
+    ///     </tutorials>
+    ///     <methods>
+    ///         <method name="my_method">
+    ///             <return type="int" />
+    ///             <argument index="0" name="x" type="float" />
+    ///             <description>
+    ///                 <documentation>
+    ///             </description>
+    ///         </method>
+    ///     </methods>
+    ///     <members>
+    ///         <member name="my_property" type="int" default="0">
+    ///             <documentation>
+    ///         </member>
+    ///     </members>
+    ///     <constants>
+    ///     </constants>
+    /// </class>
+    /// ```
+    ///
+    /// Since godot 4.0, arguments are written as `<param ... />`.
+    fn class_xml(&mut self, generator: &Generator, name: &str, class: &GdnativeClass) -> String {
+        let resolver = generator.resolver;
+        let godot_version = resolver.godot_version;
+        self.current_class = name.to_string();
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\" ?>\n");
+        if generator.options.opening_comment {
+            xml.push_str("<!-- This file was automatically generated using gdnative-doc-rs (https://github.com/arnaudgolfouse/gdnative-doc-rs)\n\nCrate: ");
+            escape(&mut xml, &generator.documentation.name_and_version());
+            xml.push_str(" -->\n");
+        }
+        xml.push_str("<class name=\"");
+        escape(&mut xml, name);
+        xml.push_str("\" inherits=\"");
+        escape(&mut xml, &class.inherit);
+        let _ = writeln!(xml, "\" version=\"{}\">", godot_version.as_str());
+
+        let mut brief_description = String::new();
+        self.encode(
+            &mut brief_description,
+            inline_events(&class.summary, resolver, generator.options.markdown_options),
+        );
+        push_element(&mut xml, 1, "brief_description", &brief_description);
+
+        let mut description = String::new();
+        if let Some(note) = &class.deprecated {
+            self.encode(
+                &mut description,
+                deprecation_notice(note, resolver, generator.options.markdown_options),
+            );
+        }
+        generator.encode_documentation(&mut description, &class.documentation, "description", self);
+        self.encode(
+            &mut description,
+            tags_list(&class.tags, resolver, generator.options.markdown_options),
+        );
+        push_element(&mut xml, 1, "description", &description);
+        xml.push_str("\t<tutorials>\n\t</tutorials>\n");
+
+        xml.push_str("\t<methods>\n");
+        // accessors are referenced by their property
+        for method in class
+            .methods
+            .iter()
+            .filter(|method| method.accessor_of.is_none())
+        {
+            xml.push_str("\t\t<method name=\"");
+            escape(&mut xml, &method.name);
+            xml.push('"');
+            if !method.has_self && godot_version >= GodotVersion::Version40 {
+                xml.push_str(" qualifiers=\"static\"");
+            }
+            xml.push_str(">\n\t\t\t<return type=\"");
+            escape(&mut xml, type_name(&method.return_type));
+            xml.push_str("\" />\n");
+            let argument = if godot_version >= GodotVersion::Version40 {
+                "param"
+            } else {
+                "argument"
+            };
+            for (index, (name, typ, _)) in method.parameters.iter().enumerate() {
+                let _ = write!(xml, "\t\t\t<{} index=\"{}\" name=\"", argument, index);
+                escape(&mut xml, name);
+                xml.push_str("\" type=\"");
+                escape(&mut xml, type_name(typ));
+                xml.push_str("\" />\n");
+            }
+            let mut description = String::new();
+            if let Some(note) = &method.deprecated {
+                self.encode(
+                    &mut description,
+                    deprecation_notice(note, resolver, generator.options.markdown_options),
+                );
+            }
+            generator.encode_documentation(
+                &mut description,
+                &method.documentation,
+                &format!("func-{}", method.name),
+                self,
+            );
+            self.encode(
+                &mut description,
+                tags_list(&method.tags, resolver, generator.options.markdown_options),
+            );
+            push_element(&mut xml, 3, "description", &description);
+            xml.push_str("\t\t</method>\n");
+        }
+        xml.push_str("\t</methods>\n");
+
+        xml.push_str("\t<members>\n");
+        for property in &class.properties {
+            xml.push_str("\t\t<member name=\"");
+            escape(&mut xml, &property.name);
+            xml.push_str("\" type=\"");
+            escape(&mut xml, type_name(&property.typ));
+            xml.push('"');
+            let attributes = [
+                ("setter", &property.setter),
+                ("getter", &property.getter),
+                ("default", &property.default),
+            ];
+            for (attribute, value) in attributes {
+                if let Some(value) = value {
+                    let _ = write!(xml, " {}=\"", attribute);
+                    escape(&mut xml, value);
+                    xml.push('"');
+                }
+            }
+            xml.push_str(">\n");
+            let mut description = String::new();
+            if let Some(note) = &property.deprecated {
+                self.encode(
+                    &mut description,
+                    deprecation_notice(note, resolver, generator.options.markdown_options),
+                );
+            }
+            generator.encode_documentation(
+                &mut description,
+                &property.documentation,
+                &format!("property-{}", property.name),
+                self,
+            );
+            self.encode(
+                &mut description,
+                tags_list(&property.tags, resolver, generator.options.markdown_options),
+            );
+            push_text(&mut xml, 3, &description);
+            xml.push_str("\t\t</member>\n");
+        }
+        xml.push_str("\t</members>\n");
+        xml.push_str("\t<constants>\n\t</constants>\n</class>\n");
+        xml
+    }
+
+    fn start_tag(&mut self, s: &mut String, tag: Tag<'_>) {
+        match tag {
+            Tag::Heading(..) | Tag::Strong => s.push_str("[b]"),
+            Tag::Emphasis => s.push_str("[i]"),
+            Tag::Strikethrough => s.push_str("[s]"),
+            Tag::CodeBlock(_) => s.push_str("[codeblock]\n"),
+            Tag::List(start) => self.lists.push(start),
+            Tag::Item => {
+                for _ in 1..self.lists.len() {
+                    s.push_str("  ");
+                }
+                match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        let _ = write!(s, "{}. ", number);
+                        *number += 1;
+                    }
+                    _ => s.push_str("- "),
+                }
+            }
+            Tag::Link(_, dest, _) | Tag::Image(_, dest, _) => {
+                self.link = Some((s.len(), dest.to_string()))
+            }
+            Tag::Paragraph
+            | Tag::BlockQuote
+            | Tag::FootnoteDefinition(_)
+            | Tag::Table(_)
+            | Tag::TableHead
+            | Tag::TableRow
+            | Tag::TableCell => {}
+        }
+    }
+
+    fn end_tag(&mut self, s: &mut String, tag: Tag<'_>) {
+        match tag {
+            Tag::Paragraph | Tag::TableHead | Tag::TableRow => s.push('\n'),
+            Tag::Heading(..) => s.push_str("[/b]\n"),
+            Tag::Strong => s.push_str("[/b]"),
+            Tag::Emphasis => s.push_str("[/i]"),
+            Tag::Strikethrough => s.push_str("[/s]"),
+            Tag::CodeBlock(kind) => {
+                if !s.ends_with('\n') {
+                    s.push('\n');
+                }
+                s.push_str("[/codeblock]\n");
+                if let CodeBlockKind::Indented = kind {
+                    s.push('\n');
+                }
+            }
+            Tag::List(_) => {
+                self.lists.pop();
+            }
+            Tag::Item => {
+                if !s.ends_with('\n') {
+                    s.push('\n');
+                }
+            }
+            Tag::TableCell => s.push('\t'),
+            Tag::Link(..) | Tag::Image(..) => self.end_link(s),
+            Tag::BlockQuote | Tag::FootnoteDefinition(_) | Tag::Table(_) => {}
+        }
+    }
+
+    /// Replace the text of the link that just ended in `s`.
+    ///
+    /// Links to godot classes become `[Class]`, and links to godot constants
+    /// become `[constant NAME]`, which godot links to its documentation. Links to
+    /// the documented classes and their members are written the same way, since
+    /// they are registered in godot. Other absolute links become
+    /// `[url=...]text[/url]`, and other relative links are dropped.
+    fn end_link(&mut self, s: &mut String) {
+        let (start, dest) = match self.link.take() {
+            Some(link) => link,
+            None => return,
+        };
+        let text = s.split_off(start);
+        let name = text
+            .strip_prefix("[code]")
+            .and_then(|text| text.strip_suffix("[/code]"))
+            .unwrap_or(&text);
+        let godot_name = self.rust_to_godot.get(name).map_or(name, String::as_str);
+        if let Some(class) = self
+            .godot_classes
+            .get(&dest)
+            .filter(|class| *class == godot_name)
+        {
+            let _ = write!(s, "[{}]", class);
+        } else if self.godot_items.get(name) == Some(&dest) {
+            if dest.contains('#') {
+                let _ = write!(s, "[constant {}]", name);
+            } else {
+                // `true` and `false` link to `bool`
+                let _ = write!(s, "[code]{}[/code]", name);
+            }
+        } else if let Some((tag, class, member)) = godot_member(&dest, name) {
+            let class = self.rust_to_godot.get(class).map_or(class, String::as_str);
+            let _ = write!(s, "[{} {}.{}]", tag, class, member);
+        } else if let Some(reference) = self.documented_reference(&dest) {
+            s.push_str(&reference);
+        } else if dest.starts_with("http://") || dest.starts_with("https://") {
+            let _ = write!(s, "[url={}]{}[/url]", dest, text);
+        } else {
+            s.push_str(&text);
+        }
+    }
+
+    /// Reference to the documented class or member at `dest`, like `[MyClass]`
+    /// or `[method MyClass.my_method]`.
+    fn documented_reference(&self, dest: &str) -> Option<String> {
+        let (page, anchor) = match dest.split_once('#') {
+            Some((page, anchor)) => (page, Some(anchor)),
+            None => (dest, None),
+        };
+        let class = match (page, anchor) {
+            ("", None) => return None,
+            ("", Some(_)) => &self.current_class,
+            (page, _) => self.documented_classes.get(page)?,
+        };
+        match anchor {
+            None => Some(format!("[{}]", class)),
+            Some(anchor) => {
+                if let Some(method) = anchor.strip_prefix("func-") {
+                    Some(format!("[method {}.{}]", class, method))
+                } else {
+                    let property = anchor.strip_prefix("property-")?;
+                    Some(format!("[member {}.{}]", class, property))
+                }
+            }
+        }
+    }
+}
+
+/// Name of `typ` in the XML files.
+fn type_name(typ: &Type) -> &str {
+    match typ {
+        Type::Option(name) | Type::Named(name) => name,
+        Type::Unit => "void",
+    }
+}
+
+/// Write `<element>`, containing `text`, at the given indentation level.
+fn push_element(xml: &mut String, indent: usize, element: &str, text: &str) {
+    push_indent(xml, indent);
+    let _ = writeln!(xml, "<{}>", element);
+    push_text(xml, indent + 1, text);
+    push_indent(xml, indent);
+    let _ = writeln!(xml, "</{}>", element);
+}
+
+/// Write the lines of `text` at the given indentation level.
+///
+/// Godot removes this indentation when loading the file.
+fn push_text(xml: &mut String, indent: usize, text: &str) {
+    for line in text.trim().lines() {
+        if !line.is_empty() {
+            push_indent(xml, indent);
+            escape(xml, line);
+        }
+        xml.push('\n');
+    }
+}
+
+fn push_indent(xml: &mut String, indent: usize) {
+    for _ in 0..indent {
+        xml.push('\t');
+    }
+}
+
+/// Escape `text` for use in XML text or attributes.
+fn escape(xml: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => xml.push_str("&amp;"),
+            '<' => xml.push_str("&lt;"),
+            '>' => xml.push_str("&gt;"),
+            '"' => xml.push_str("&quot;"),
+            c => xml.push(c),
+        }
+    }
+}
+
+/// If `dest` links to a member of a godot class, and `name` is written like
+/// `Class.member` or `Class::member`, returns the BBCode tag of the member (like
+/// `method`), the class and the member.
+fn godot_member<'a>(dest: &str, name: &'a str) -> Option<(&'static str, &'a str, &'a str)> {
+    const TAGS: &[(&str, &str)] = &[
+        ("-method-", "method"),
+        ("-property-", "member"),
+        ("-signal-", "signal"),
+        ("-constant-", "constant"),
+    ];
+    if !dest.starts_with("https://docs.godotengine.org/") {
+        return None;
+    }
+    let (_, anchor) = dest.split_once("#class-")?;
+    let (_, tag) = TAGS.iter().find(|(id, _)| anchor.contains(id))?;
+    let (class, member) = name.split_once("::").or_else(|| name.split_once('.'))?;
+    Some((tag, class, member))
+}
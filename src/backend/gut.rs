@@ -1,12 +1,178 @@
-use super::{Callbacks, Generator, Method};
-use pulldown_cmark::{CodeBlockKind, Event, Tag};
-use std::{collections::HashMap, path::PathBuf, fmt::Write as _};
+use super::{
+    class_file_stem, parse_code_block_info, Callbacks, EventIterator, Generator, HiddenLines,
+    Method, Property,
+};
+use crate::{
+    diagnostics::{diagnostic, Lint},
+    GodotVersion, GutSuite,
+};
+use pulldown_cmark::{CodeBlockKind, Event, Parser, Tag};
+use std::{collections::HashMap, fmt::Write as _, path::PathBuf};
 
 #[derive(Default)]
 pub(crate) struct GutCallbacks {
-    current_method: String,
-    current_method_index: u8,
+    /// Name of the tests of the item being encoded, without the `test_` prefix:
+    /// `class_<name>`, `property_<name>` or the name of a method.
+    current_item: String,
+    current_item_index: u8,
     active: bool,
+    /// Set for deprecated items: their examples do not generate tests.
+    skip_item: bool,
+    /// Is the item being encoded a method ?
+    is_method: bool,
+    /// See [`GutConfig::pending_tests`](crate::GutConfig::pending_tests).
+    pending_tests: bool,
+    /// Name of the test being encoded, and whether it contains an assertion.
+    current_test: Option<(String, bool)>,
+    /// Tests of the script being generated that contain no assertion.
+    tests_without_assertion: Vec<String>,
+    /// Set while encoding a `before_each` or `after_each` code block.
+    fixture: Option<Fixture>,
+    /// Fixtures of the script being generated.
+    fixtures: Fixtures,
+    /// Set while encoding a `params` code block.
+    in_parameters: bool,
+    /// Parameters of the next test of the item, from a `params` code block.
+    parameters: Option<Vec<String>>,
+}
+
+impl GutCallbacks {
+    /// Called when the item being encoded is done: with `pending_tests`, write a
+    /// pending test for a method without example.
+    fn finish_item(&mut self, s: &mut String) {
+        if self.pending_tests && self.is_method && !self.skip_item && self.current_item_index == 0 {
+            let _ = write!(
+                s,
+                "func test_{}():\n    pending(\"no example\")\n\n",
+                self.current_item
+            );
+        }
+        if self.parameters.take().is_some() {
+            log::warn!(
+                "the parameters of '{}' are not followed by an example",
+                self.current_item
+            );
+        }
+        self.is_method = false;
+    }
+
+    /// Write the start of the test function `test`.
+    ///
+    /// If parameters were given in a `params` code block, the test is run once for
+    /// each of them, in a `p` variable.
+    fn start_test(&mut self, s: &mut String, test: &str) {
+        match self.parameters.take() {
+            Some(parameters) => {
+                let _ = writeln!(
+                    s,
+                    "var params_{} = [\n    {}\n]\n",
+                    test,
+                    parameters.join(",\n    ")
+                );
+                let _ = writeln!(s, "func {}(p = use_parameters(params_{})):", test, test);
+            }
+            None => {
+                let _ = writeln!(s, "func {}():", test);
+            }
+        }
+    }
+}
+
+/// Returns `true` if `code` calls an assertion function of gut (like
+/// `assert_eq`) or of gdscript (`assert`), or otherwise sets the result of the
+/// test.
+fn has_assertion_call(code: &str) -> bool {
+    ["assert(", "assert_", "pending(", "pass_test(", "fail_test("]
+        .iter()
+        .any(|call| code.contains(call))
+}
+
+/// Functions run by gut around each test.
+#[derive(Clone, Copy)]
+enum Fixture {
+    BeforeEach,
+    AfterEach,
+}
+
+/// Content of the fixtures of a test script, from the code blocks marked as
+/// ` ```gdscript,before_each ` or ` ```gdscript,after_each `.
+#[derive(Clone, Default)]
+struct Fixtures {
+    /// Members of the script, declared at the top level of `before_each` blocks.
+    members: String,
+    before_each: String,
+    after_each: String,
+}
+
+impl Fixtures {
+    /// Fixtures declared in the crate documentation, shared by all scripts.
+    fn from_documentation(generator: &Generator) -> Self {
+        let mut callbacks = GutCallbacks::default();
+        let events = EventIterator::new(
+            generator.resolver,
+            0,
+            generator.options.hidden_lines,
+            Parser::new_ext(
+                &generator.documentation.root_documentation,
+                generator.options.markdown_options,
+            ),
+        );
+        // tests from the crate documentation are dropped
+        callbacks.encode(&mut String::new(), events.collect());
+        callbacks.fixtures
+    }
+
+    /// Add a line of a `before_each` or `after_each` block.
+    ///
+    /// Top-level variable declarations of `before_each` blocks become members
+    /// of the script, so that tests can use them: `var map = DijkstraMap.new()`
+    /// declares `var map`, and assigns it in `before_each`.
+    fn push_line(&mut self, fixture: Fixture, line: &str) {
+        let body = match fixture {
+            Fixture::BeforeEach => &mut self.before_each,
+            Fixture::AfterEach => &mut self.after_each,
+        };
+        let declaration = line
+            .strip_prefix("var ")
+            .filter(|_| matches!(fixture, Fixture::BeforeEach));
+        let line = match declaration {
+            Some(declaration) => {
+                let (member, value) = match declaration.split_once('=') {
+                    // `var name := value`
+                    Some((member, value)) => (member.trim_end_matches(':'), Some(value)),
+                    None => (declaration, None),
+                };
+                let member = member.trim();
+                let _ = writeln!(self.members, "var {}", member);
+                match value {
+                    Some(value) => {
+                        let name = member.split(':').next().unwrap_or_default().trim();
+                        format!("{} = {}", name, value.trim())
+                    }
+                    None => return,
+                }
+            }
+            None => line.to_string(),
+        };
+        let _ = writeln!(body, "    {}", line);
+    }
+
+    /// Declarations of the members, and `before_each` and `after_each` functions.
+    fn to_gdscript(&self) -> String {
+        let mut s = String::new();
+        if !self.members.is_empty() {
+            let _ = writeln!(s, "{}", self.members);
+        }
+        for (name, body) in [
+            ("before_each", &self.before_each),
+            ("after_each", &self.after_each),
+        ] {
+            if !body.is_empty() {
+                let _ = writeln!(s, "func {}():\n{}", name, body);
+            }
+        }
+        s
+    }
 }
 
 impl Callbacks for GutCallbacks {
@@ -14,12 +180,26 @@ impl Callbacks for GutCallbacks {
         "gd"
     }
 
-    fn generate_files(&mut self, generator: Generator) -> HashMap<String, String> {
-        let mut files = HashMap::new();
+    fn links_to_documented_classes(&self) -> bool {
+        // the tests do not link to other classes
+        false
+    }
+
+    fn generate_files(&mut self, mut generator: Generator) -> HashMap<String, String> {
+        // hidden lines are part of the tests
+        if generator.options.hidden_lines == HiddenLines::Hide {
+            generator.options.hidden_lines = HiddenLines::Show;
+        }
+        let pending_tests = generator.options.gut.pending_tests.unwrap_or(false);
+        let crate_fixtures = Fixtures::from_documentation(&generator);
 
         let root_dir = generator.documentation.root_file.parent();
-        for (name, class) in &generator.documentation.classes {
-            let opening_comment = if generator.opening_comment {
+        let scripts = generator.map_classes(|name, class| {
+            if class.deprecated.is_some() {
+                log::debug!("skipping tests for deprecated class '{}'", name);
+                return None;
+            }
+            let opening_comment = if generator.options.opening_comment {
                 format!(
                     r"# This file was automatically generated using [gdnative-doc-rs](https://github.com/arnaudgolfouse/gdnative-doc-rs)
 # 
@@ -27,7 +207,7 @@ impl Callbacks for GutCallbacks {
 # Source file: {}
 
 ",
-                    generator.documentation.name,
+                    generator.documentation.name_and_version(),
                     root_dir
                         .and_then(|root_dir| class.file.strip_prefix(root_dir).ok())
                         .unwrap_or(&PathBuf::new())
@@ -37,55 +217,141 @@ impl Callbacks for GutCallbacks {
                 String::new()
             };
 
+            let mut encoder = Self {
+                pending_tests,
+                fixtures: crate_fixtures.clone(),
+                current_item: format!("class_{}", class_file_stem(name)),
+                ..Self::default()
+            };
+            let mut tests = generator.generate_file(name, class, &mut encoder);
+            encoder.finish_item(&mut tests);
+            if !encoder.tests_without_assertion.is_empty() {
+                diagnostic!(
+                    Lint::TestWithoutAssertion,
+                    "tests of '{}' without assertion: {}",
+                    name,
+                    encoder.tests_without_assertion.join(", ")
+                );
+            }
             let content = format!(
-                r"{}{}",
+                r"{}{}{}",
                 opening_comment,
-                generator.generate_file(name, class, self)
+                encoder.fixtures.to_gdscript(),
+                tests
             );
-            let name = format!("{}.gd", name);
-            files.insert(
+            let name = if generator.options.gut.module_directories.unwrap_or(false) {
+                let mut path: Vec<&str> = class.module.split("::").collect();
+                path.retain(|module| !module.is_empty());
+                // the last segment of the key, which is the (possibly renamed)
+                // class name
+                path.push(name.rsplit("::").next().unwrap_or(name));
+                format!("{}.gd", path.join("/"))
+            } else {
+                format!("{}.gd", class_file_stem(name))
+            };
+            Some((
                 name,
-                String::from("extends \"res://addons/gut/test.gd\"\n\n") + &content,
-            );
+                format!("{}\n\n{}", generator.options.gut.extends(), content),
+            ))
+        });
+        let mut files: HashMap<String, String> = scripts.into_iter().flatten().collect();
+
+        if let Some(gut_suite) = &generator.options.gut.suite {
+            let mut scripts: Vec<&str> = files.keys().map(String::as_str).collect();
+            scripts.sort_unstable();
+            let gut_config = gut_config(gut_suite, &scripts);
+            if gut_suite.runner_scene.unwrap_or(false) {
+                let godot_version = generator.resolver.godot_version;
+                let (scene, script) = runner_scene(gut_suite, &scripts, godot_version);
+                files.insert(String::from("run_tests.tscn"), scene);
+                files.insert(String::from("run_tests.gd"), script);
+            }
+            files.insert(String::from(".gutconfig.json"), gut_config);
         }
 
         files
     }
 
-    fn start_method(&mut self, _s: &mut String, _resolver: &super::Resolver, method: &Method) {
-        self.current_method = method.name.clone();
-        self.current_method_index = 0;
+    fn start_method(&mut self, s: &mut String, _resolver: &super::Resolver, method: &Method) {
+        self.finish_item(s);
+        self.current_item = method.name.clone();
+        self.current_item_index = 0;
+        self.active = false;
+        self.skip_item = method.deprecated.is_some();
+        self.is_method = true;
+    }
+
+    fn start_property(&mut self, s: &mut String, _resolver: &super::Resolver, property: &Property) {
+        self.finish_item(s);
+        self.current_item = format!("property_{}", property.name);
+        self.current_item_index = 0;
         self.active = false;
+        self.skip_item = property.deprecated.is_some();
+        self.is_method = false;
     }
 
     fn encode(&mut self, s: &mut String, events: Vec<Event>) {
         for event in events {
             match event {
-                Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(lang))) => {
-                    if lang.as_ref() == "gdscript" {
-                        self.active = true;
-                        s.push_str("func test_");
-                        s.push_str(&self.current_method);
-                        if self.current_method_index > 0 {
-                            let _ = write!(s, "_{}", self.current_method_index);
-                        }
-                        s.push_str("():\n");
-                        self.current_method_index += 1;
+                Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info))) if !self.skip_item => {
+                    let info = parse_code_block_info(&info);
+                    // `no_run` examples are only illustrative
+                    if info.lang != "gdscript" || info.has_attribute("no_run") {
+                        continue;
+                    }
+                    self.active = true;
+                    if info.has_attribute("before_each") {
+                        self.fixture = Some(Fixture::BeforeEach);
+                        continue;
+                    } else if info.has_attribute("after_each") {
+                        self.fixture = Some(Fixture::AfterEach);
+                        continue;
+                    } else if info.has_attribute("params") {
+                        self.in_parameters = true;
+                        self.parameters = Some(Vec::new());
+                        continue;
                     }
+                    let mut test = format!("test_{}", self.current_item);
+                    if self.current_item_index > 0 {
+                        let _ = write!(test, "_{}", self.current_item_index);
+                    }
+                    self.start_test(s, &test);
+                    self.current_test = Some((test, false));
+                    self.current_item_index += 1;
                 }
-                Event::End(Tag::CodeBlock(CodeBlockKind::Fenced(lang))) => {
-                    if lang.as_ref() == "gdscript" {
-                        self.active = false;
+                Event::End(Tag::CodeBlock(_)) if self.active => {
+                    self.active = false;
+                    if self.in_parameters {
+                        self.in_parameters = false;
+                    } else if self.fixture.take().is_none() {
                         s.push('\n');
                     }
+                    if let Some((test, false)) = self.current_test.take() {
+                        self.tests_without_assertion.push(test);
+                    }
                 }
-                Event::Text(text) => {
-                    if self.active {
+                Event::Text(text) if self.active => {
+                    if let Some(fixture) = self.fixture {
                         for line in text.as_ref().lines() {
-                            s.push_str("    ");
-                            s.push_str(line);
-                            s.push('\n');
+                            self.fixtures.push_line(fixture, line);
                         }
+                        continue;
+                    }
+                    if self.in_parameters {
+                        let parameters = self.parameters.get_or_insert_with(Vec::new);
+                        (text.lines())
+                            .map(str::trim)
+                            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                            .for_each(|line| parameters.push(line.to_string()));
+                        continue;
+                    }
+                    if let Some((_, has_assertion)) = &mut self.current_test {
+                        *has_assertion |= has_assertion_call(&text);
+                    }
+                    for line in text.as_ref().lines() {
+                        s.push_str("    ");
+                        s.push_str(line);
+                        s.push('\n');
                     }
                 }
                 _ => {}
@@ -93,3 +359,67 @@ impl Callbacks for GutCallbacks {
         }
     }
 }
+
+/// Content of a `run_tests.tscn` scene and of its `run_tests.gd` script, that run
+/// the generated `scripts` when the scene is played.
+fn runner_scene(
+    gut_suite: &GutSuite,
+    scripts: &[&str],
+    godot_version: GodotVersion,
+) -> (String, String) {
+    let directory = gut_suite.directory.as_deref().unwrap_or("res://test");
+    let directory = directory.trim_end_matches('/');
+
+    let mut script = String::from(
+        "extends Node\n\n# Runs the generated tests when the scene is played.\nfunc _ready():\n    var gut = load(\"res://addons/gut/gut.gd\").new()\n    add_child(gut)\n",
+    );
+    for test_script in scripts {
+        let _ = writeln!(
+            script,
+            "    gut.add_script(\"{}/{}\")",
+            directory, test_script
+        );
+    }
+    script.push_str("    gut.test_scripts()\n");
+
+    let scene = if godot_version >= GodotVersion::Version40 {
+        format!(
+            "[gd_scene load_steps=2 format=3]\n\n[ext_resource type=\"Script\" path=\"{}/run_tests.gd\" id=\"1\"]\n\n[node name=\"RunTests\" type=\"Node\"]\nscript = ExtResource(\"1\")\n",
+            directory
+        )
+    } else {
+        format!(
+            "[gd_scene load_steps=2 format=2]\n\n[ext_resource path=\"{}/run_tests.gd\" type=\"Script\" id=1]\n\n[node name=\"RunTests\" type=\"Node\"]\nscript = ExtResource( 1 )\n",
+            directory
+        )
+    };
+    (scene, script)
+}
+
+/// Content of a `.gutconfig.json` file running the generated `scripts`.
+///
+/// It can be used with
+/// `godot -s addons/gut/gut_cmdln.gd -gconfig=<directory>/.gutconfig.json`.
+fn gut_config(gut_suite: &GutSuite, scripts: &[&str]) -> String {
+    #[derive(serde::Serialize)]
+    struct GutConfigFile<'a> {
+        dirs: &'a [String],
+        tests: Vec<String>,
+        should_exit: bool,
+        should_exit_on_success: bool,
+    }
+
+    let directory = gut_suite.directory.as_deref().unwrap_or("res://test");
+    let directory = directory.trim_end_matches('/');
+    let config = GutConfigFile {
+        dirs: gut_suite.dirs.as_deref().unwrap_or_default(),
+        tests: (scripts.iter())
+            .map(|script| format!("{}/{}", directory, script))
+            .collect(),
+        should_exit: gut_suite.exit_on_failure.unwrap_or(true),
+        should_exit_on_success: true,
+    };
+    let mut s = serde_json::to_string_pretty(&config).unwrap_or_default();
+    s.push('\n');
+    s
+}
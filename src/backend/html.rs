@@ -1,20 +1,144 @@
-use super::{Callbacks, Event, Generator, Method, Property, Resolver};
-use std::{collections::HashMap, path::PathBuf};
+use super::{
+    class_file_stem, html_anchor, json::to_json, parse_code_block_info, Callbacks, Event,
+    Generator, Method, Property, Resolver,
+};
+use crate::Error;
+use handlebars::{Handlebars, Template};
+use pulldown_cmark::{
+    escape::{escape_href, escape_html},
+    CodeBlockKind, Tag,
+};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    fmt::Write as _,
+    fs,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use syntect::{
+    html::{ClassStyle, ClassedHTMLGenerator},
+    parsing::{SyntaxDefinition, SyntaxSet},
+    util::LinesWithEndings,
+};
 
-const PRISM_CSS: (&str, &str) = ("prism.css", include_str!("../../html/prism.css"));
-const PRISM_JS: (&str, &str) = ("prism.js", include_str!("../../html/prism.js"));
+const COPY_JS: (&str, &str) = ("copy.js", include_str!("../../html/copy.js"));
+const HIGHLIGHT_CSS: (&str, &str) = ("highlight.css", include_str!("../../html/highlight.css"));
+const SEARCH_JS: (&str, &str) = ("search.js", include_str!("../../html/search.js"));
 const STYLE_CSS: (&str, &str) = ("style.css", include_str!("../../html/style.css"));
+const THEME_JS: (&str, &str) = ("theme.js", include_str!("../../html/theme.js"));
+
+/// Syntax definition of GDScript, which is not part of the syntaxes bundled with
+/// [`syntect`].
+const GDSCRIPT_SYNTAX: &str = include_str!("../../html/GDScript.sublime-syntax");
+
+/// Default templates of the pages, by name.
+///
+/// They can be overridden with [`HtmlConfig::templates`](crate::HtmlConfig::templates).
+const TEMPLATES: [(&str, &str); 5] = [
+    ("layout", include_str!("../../html/templates/layout.hbs")),
+    ("head", include_str!("../../html/templates/head.hbs")),
+    ("header", include_str!("../../html/templates/header.hbs")),
+    ("footer", include_str!("../../html/templates/footer.hbs")),
+    ("class", include_str!("../../html/templates/class.hbs")),
+];
+
+/// Read the templates overriding the default ones in `directory`.
+///
+/// Each template is in a `<name>.hbs` file: other files are ignored, with a
+/// warning for unknown templates.
+pub(super) fn load_templates(directory: &Path) -> Result<HashMap<String, String>, Error> {
+    let entries = match fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(err) => return Err(Error::Io(directory.to_path_buf(), err)),
+    };
+    let mut templates = HashMap::new();
+    for entry in entries {
+        let path = match entry {
+            Ok(entry) => entry.path(),
+            Err(err) => return Err(Error::Io(directory.to_path_buf(), err)),
+        };
+        if path.extension().and_then(|ext| ext.to_str()) != Some("hbs") {
+            continue;
+        }
+        let name = match path.file_stem().and_then(|stem| stem.to_str()) {
+            Some(name) if TEMPLATES.iter().any(|(default, _)| *default == name) => name.to_string(),
+            _ => {
+                log::warn!("unknown html template: {}", path.display());
+                continue;
+            }
+        };
+        let source = match fs::read_to_string(&path) {
+            Ok(source) => source,
+            Err(err) => return Err(Error::Io(path, err)),
+        };
+        if let Err(err) = Template::compile(&source) {
+            return Err(Error::Template(path, err.to_string()));
+        }
+        templates.insert(name, source);
+    }
+    Ok(templates)
+}
+
+/// Data available in the templates.
+#[derive(Serialize)]
+struct PageData<'a> {
+    /// Name of the crate.
+    crate_name: &'a str,
+    /// Version of the crate, if known.
+    crate_version: Option<&'a str>,
+    /// Title of the page, like `MyClass - my-crate`.
+    title: &'a str,
+    /// Description of the page: the summary of the class, or
+    /// [`HtmlConfig::description`](crate::HtmlConfig::description).
+    description: Option<&'a str>,
+    /// See [`HtmlConfig::author`](crate::HtmlConfig::author).
+    author: Option<&'a str>,
+    /// Absolute URL of the logo, for OpenGraph cards.
+    image: Option<&'a str>,
+    /// See [`HtmlConfig::twitter_site`](crate::HtmlConfig::twitter_site).
+    twitter_site: Option<&'a str>,
+    /// Comment at the start of the page, or the empty string.
+    opening_comment: &'a str,
+    /// Default theme, see [`HtmlTheme::as_str`](crate::HtmlTheme::as_str).
+    theme: &'a str,
+    /// Whether `extra.css` must be included.
+    extra_css: bool,
+    /// Absolute URL of the page, if [`HtmlConfig::base_url`](crate::HtmlConfig::base_url)
+    /// is set.
+    canonical_url: Option<&'a str>,
+    /// Html of the sidebar.
+    sidebar: &'a str,
+    /// Name of the documented class, if any.
+    class: Option<&'a str>,
+    /// Html of the documentation.
+    content: &'a str,
+}
 
 /// Implementation of [`Callbacks`] for html.
 #[derive(Default)]
-pub(crate) struct HtmlCallbacks {}
+pub(crate) struct HtmlCallbacks {
+    /// See [`HtmlBackendOptions::line_numbers`](super::HtmlBackendOptions::line_numbers).
+    line_numbers: bool,
+    /// Templates of the pages, see [`TEMPLATES`].
+    templates: Handlebars<'static>,
+    /// Syntaxes used to highlight code blocks, loaded on first use.
+    syntaxes: Option<Arc<SyntaxSet>>,
+    /// Whether we are in a heading.
+    ///
+    /// Headings can be encoded over multiple calls to `encode`.
+    in_heading: bool,
+    /// Id of the current heading, used to add a permalink at its end.
+    heading_id: Option<String>,
+}
 
 impl HtmlCallbacks {
-    /// Generate an opening comment if `generator.opening_comment` is `true`.
+    /// Generate an opening comment if `generator.options.opening_comment` is `true`.
     ///
     /// Else, returns an empty `String`.
     fn make_opening_comment(generator: &Generator, source_file: &dyn std::fmt::Display) -> String {
-        if generator.opening_comment {
+        if generator.options.opening_comment {
             format!(
                 r"<!-- 
 This file was automatically generated using [gdnative-doc-rs](https://github.com/arnaudgolfouse/gdnative-doc-rs)
@@ -24,12 +148,106 @@ Source file: {}
 -->
 
 ",
-                generator.documentation.name, source_file,
+                generator.documentation.name_and_version(),
+                source_file,
             )
         } else {
             String::new()
         }
     }
+
+    /// Register the templates of the pages: the templates in
+    /// [`HtmlBackendOptions::templates`](super::HtmlBackendOptions::templates)
+    /// take precedence over the default ones.
+    fn register_templates(&mut self, generator: &Generator) {
+        for (name, default) in TEMPLATES {
+            let source = generator
+                .options
+                .html
+                .templates
+                .get(name)
+                .map_or(default, String::as_str);
+            if let Err(err) = self.templates.register_template_string(name, source) {
+                log::error!("invalid html template '{}': {}", name, err);
+                let _ = self.templates.register_template_string(name, default);
+            }
+        }
+    }
+
+    /// Render the template `name` with `data`.
+    ///
+    /// If this fails, the default template is used instead.
+    fn render(&self, name: &str, data: &PageData) -> String {
+        match self.templates.render(name, data) {
+            Ok(rendered) => rendered,
+            Err(err) => {
+                log::error!("failed to render html template '{}': {}", name, err);
+                let mut defaults = Handlebars::new();
+                for (name, default) in TEMPLATES {
+                    let _ = defaults.register_template_string(name, default);
+                }
+                defaults.render(name, data).unwrap_or_default()
+            }
+        }
+    }
+
+    /// Complete html page in `file_name`, with the given `title` and `content`.
+    ///
+    /// `class` is the name of the class documented by the page, if any: its
+    /// properties and methods are listed in the sidebar, and `content` is first
+    /// rendered with the `class` template.
+    fn page(
+        &self,
+        generator: &Generator,
+        file_name: &str,
+        source_file: &dyn std::fmt::Display,
+        title: &str,
+        class: Option<&str>,
+        content: &str,
+    ) -> String {
+        let documentation = generator.documentation;
+        let title = if title == documentation.name {
+            title.to_string()
+        } else {
+            format!("{} - {}", title, documentation.name)
+        };
+        let description = class
+            .and_then(|class| documentation.classes.get(class))
+            .map(|class| class.summary.as_str())
+            .filter(|summary| !summary.is_empty())
+            .or(generator.options.html.config.description.as_deref());
+        let image = generator.options.html.config.logo_url();
+        let opening_comment = Self::make_opening_comment(generator, source_file);
+        let sidebar = sidebar(generator, class);
+        let canonical_url = generator.options.html.config.absolute_url(file_name);
+        let mut data = PageData {
+            crate_name: &documentation.name,
+            crate_version: documentation.metadata.version.as_deref(),
+            title: &title,
+            description,
+            author: generator.options.html.config.author.as_deref(),
+            image: image.as_deref(),
+            twitter_site: generator.options.html.config.twitter_site.as_deref(),
+            opening_comment: &opening_comment,
+            theme: generator.options.html.theme.as_str(),
+            extra_css: generator.options.html.extra_css.is_some(),
+            canonical_url: canonical_url.as_deref(),
+            sidebar: &sidebar,
+            class,
+            content,
+        };
+        let class_content;
+        if class.is_some() {
+            class_content = self.render("class", &data);
+            data.content = &class_content;
+        }
+        let page = self.render("layout", &data);
+        let prefix = generator.options.html.config.path_prefix.as_ref();
+        match prefix.or(generator.options.absolute_links.as_ref()) {
+            Some(prefix) => prefix_links(&page, prefix),
+            None => page,
+        }
+    }
 }
 
 impl Callbacks for HtmlCallbacks {
@@ -37,75 +255,136 @@ impl Callbacks for HtmlCallbacks {
         "html"
     }
 
-    fn generate_files(&mut self, generator: Generator) -> HashMap<String, String> {
-        const HTML_START: &str = r#"<!DOCTYPE HTML>
-<html>
-
-<head>
-<meta charset="utf-8" />
-<link rel="stylesheet" href="./prism.css"/>
-<link rel="stylesheet" href="./style.css"/>
-</head>
-
-<body>
-"#;
-        const HTML_END: &str = r#"
-<script src="./prism.js"></script>
-</body>
-
-</html>"#;
+    fn pages_directory(&self) -> Option<&'static str> {
+        Some("")
+    }
 
+    fn generate_files(&mut self, mut generator: Generator) -> HashMap<String, String> {
+        // the sidebar lists the members of the class
+        generator.options.table_of_contents = false;
         let mut files = HashMap::new();
+        self.line_numbers = generator.options.html.line_numbers;
+        self.register_templates(&generator);
 
-        generator
+        let root_file_name = generator
             .documentation
             .root_file
             .file_name()
             .and_then(|name| name.to_str())
             .unwrap_or_default();
 
-        let index_content = format!(
-            r"{}{}{}{}",
-            Self::make_opening_comment(
-                &generator,
-                &generator
-                    .documentation
-                    .root_file
-                    .file_name()
-                    .and_then(|name| name.to_str())
-                    .unwrap_or_default(),
-            ),
-            HTML_START,
-            generator.generate_root_file("html", self),
-            HTML_END
+        let mut root_file = generator.generate_root_file("html", self);
+        root_file.push_str("<p><a href=\"./inheritance.html\">Inheritance</a></p>\n");
+        root_file.push_str(&generator.generate_root_footer(self));
+        let page = self.page(
+            &generator,
+            "index.html",
+            &root_file_name,
+            &generator.documentation.name,
+            None,
+            &root_file,
         );
+        files.insert(String::from("index.html"), page);
 
-        files.insert(String::from("index.html"), index_content);
+        let inheritance = generator.generate_inheritance_file("html", self);
+        let file_name = String::from("inheritance.html");
+        let page = self.page(
+            &generator,
+            &file_name,
+            &root_file_name,
+            "Inheritance",
+            None,
+            &inheritance,
+        );
+        files.insert(file_name, page);
+
+        for (index, guide) in generator.guides.iter().enumerate() {
+            let content = generator.generate_guide_file(index, "html", self);
+            let file_name = format!("{}.html", guide.file_stem);
+            let page = self.page(
+                &generator,
+                &file_name,
+                &root_file_name,
+                &guide.title,
+                None,
+                &content,
+            );
+            files.insert(file_name, page);
+        }
 
         // directory that contains the root file
         let root_dir = generator.documentation.root_file.parent();
-        for (name, class) in &generator.documentation.classes {
-            let content = generator.generate_file(name, class, self);
-            let file_content = format!(
-                r"{}{}{}{}",
-                Self::make_opening_comment(
+        if generator.options.module_pages {
+            for module in &generator.documentation.modules {
+                let content = generator.generate_module_file(module, "html", self);
+                let source_file = root_dir
+                    .and_then(|root_dir| module.file.strip_prefix(root_dir).ok())
+                    .unwrap_or(&PathBuf::new())
+                    .display()
+                    .to_string();
+                let file_name = format!("{}.html", module.file_stem());
+                let page = self.page(
                     &generator,
-                    &root_dir
-                        .and_then(|root_dir| class.file.strip_prefix(root_dir).ok())
-                        .unwrap_or(&PathBuf::new())
-                        .display(),
-                ),
-                HTML_START,
-                content,
-                HTML_END
-            );
-            let name = format!("{}.html", name);
-            files.insert(name.clone(), file_content);
+                    &file_name,
+                    &source_file,
+                    &module.path,
+                    None,
+                    &content,
+                );
+                files.insert(file_name, page);
+            }
+        }
+        // the classes share the syntaxes, instead of each loading them
+        if !generator.documentation.classes.is_empty() {
+            self.syntaxes
+                .get_or_insert_with(|| Arc::new(load_syntaxes()));
         }
+        let this = &*self;
+        let pages = generator.map_classes(|name, class| {
+            let mut encoder = Self {
+                line_numbers: this.line_numbers,
+                syntaxes: this.syntaxes.clone(),
+                ..Self::default()
+            };
+            let content = generator.generate_file(name, class, &mut encoder);
+            let source_file = root_dir
+                .and_then(|root_dir| class.file.strip_prefix(root_dir).ok())
+                .unwrap_or(&PathBuf::new())
+                .display()
+                .to_string();
+            let file_name = format!("{}.html", class_file_stem(name));
+            let page = this.page(
+                &generator,
+                &file_name,
+                &source_file,
+                name,
+                Some(name),
+                &content,
+            );
+            (file_name, page)
+        });
+        files.extend(pages);
 
-        for (name, content) in &[PRISM_CSS, PRISM_JS, STYLE_CSS] {
+        for (name, content) in &[COPY_JS, HIGHLIGHT_CSS, SEARCH_JS, STYLE_CSS, THEME_JS] {
             files.insert(name.to_string(), content.to_string());
         }
+        if let Some(extra_css) = &generator.options.html.extra_css {
+            files.insert(String::from("extra.css"), extra_css.clone());
+        }
+        if generator.options.html.config.github_pages.unwrap_or(false) {
+            files.insert(String::from(".nojekyll"), String::new());
+        }
+        if generator.options.html.config.base_url.is_some() {
+            let mut pages: Vec<&String> = files
+                .keys()
+                .filter(|file_name| file_name.ends_with(".html"))
+                .collect();
+            pages.sort_unstable();
+            let sitemap = sitemap(&generator, &pages);
+            files.insert(String::from("sitemap.xml"), sitemap);
+            files.insert(String::from("robots.txt"), robots_txt(&generator));
+        }
+        files.insert(String::from("search_index.json"), search_index(&generator));
 
         files
     }
@@ -119,6 +398,402 @@ impl Callbacks for HtmlCallbacks {
     }
 
     fn encode(&mut self, s: &mut String, events: Vec<Event<'_>>) {
-        pulldown_cmark::html::push_html(s, events.into_iter())
+        let mut events = events.into_iter();
+        let mut pending = Vec::new();
+        while let Some(event) = events.next() {
+            match event {
+                Event::Start(Tag::Heading(level, id, classes)) => {
+                    self.in_heading = true;
+                    self.heading_id = id.map(String::from);
+                    pending.push(Event::Start(Tag::Heading(level, id, classes)));
+                }
+                Event::Html(html)
+                    if self.in_heading
+                        && self.heading_id.is_none()
+                        && html_anchor(&html).is_some() =>
+                {
+                    self.heading_id = html_anchor(&html).map(String::from);
+                    pending.push(Event::Html(html));
+                }
+                Event::End(Tag::Heading(level, id, classes)) => {
+                    self.in_heading = false;
+                    if let Some(heading_id) = self.heading_id.take() {
+                        let mut permalink = String::from(" <a class=\"permalink\" href=\"#");
+                        let _ = escape_href(&mut permalink, &heading_id);
+                        permalink.push_str("\" aria-label=\"Permalink\">¶</a>");
+                        pending.push(Event::Html(permalink.into()));
+                    }
+                    pending.push(Event::End(Tag::Heading(level, id, classes)));
+                }
+                Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info))) => {
+                    let info = parse_code_block_info(&info);
+                    pulldown_cmark::html::push_html(s, pending.drain(..));
+                    let mut code = String::new();
+                    for event in events.by_ref() {
+                        match event {
+                            Event::Text(text) => code.push_str(&text),
+                            _ => break,
+                        }
+                    }
+                    self.push_code_block(s, info.lang, &code, &info.highlights);
+                }
+                event => pending.push(event),
+            }
+        }
+        pulldown_cmark::html::push_html(s, pending.into_iter())
+    }
+}
+
+impl HtmlCallbacks {
+    /// Write a highlighted code block, with a button to copy it, line numbers (if
+    /// enabled) and highlighted lines.
+    ///
+    /// This looks like:
+    /// ```html
+    /// <div class="code-block">
+    /// <button type="button" class="copy-button" title="Copy to clipboard">Copy</button>
+    /// <pre class="line-numbers" aria-hidden="true"><span>1</span>...</pre>
+    /// <pre class="line-highlights" aria-hidden="true"><span class="highlighted"> </span>...</pre>
+    /// <pre><code class="language-lang">...</code></pre>
+    /// </div>
+    /// ```
+    ///
+    /// The `<pre>` blocks are layed out by `style.css`, so that they are aligned
+    /// with the lines of code.
+    fn push_code_block(
+        &mut self,
+        s: &mut String,
+        lang: &str,
+        code: &str,
+        highlights: &[RangeInclusive<usize>],
+    ) {
+        let line_count = code.lines().count();
+        s.push_str("<div class=\"code-block\">\n");
+        s.push_str("<button type=\"button\" class=\"copy-button\" title=\"Copy to clipboard\">Copy</button>\n");
+        if self.line_numbers {
+            s.push_str("<pre class=\"line-numbers\" aria-hidden=\"true\">");
+            for line in 1..=line_count {
+                s.push_str("<span>");
+                s.push_str(&line.to_string());
+                s.push_str("</span>");
+            }
+            s.push_str("</pre>\n");
+        }
+        if !highlights.is_empty() {
+            s.push_str("<pre class=\"line-highlights\" aria-hidden=\"true\">");
+            for line in 1..=line_count {
+                if highlights.iter().any(|range| range.contains(&line)) {
+                    s.push_str("<span class=\"highlighted\"> </span>");
+                } else {
+                    s.push_str("<span> </span>");
+                }
+            }
+            s.push_str("</pre>\n");
+        }
+        s.push_str("<pre><code");
+        if !lang.is_empty() {
+            s.push_str(" class=\"language-");
+            let _ = escape_html(&mut *s, lang);
+            s.push('"');
+        }
+        s.push('>');
+        self.push_highlighted(s, lang, code);
+        s.push_str("</code></pre>\n</div>\n");
+    }
+
+    /// Write `code` as html, highlighted according to `lang`.
+    ///
+    /// The scopes of the syntax are written as classes prefixed by `hl-`, styled by
+    /// `highlight.css`. If `lang` is unknown, `code` is only escaped.
+    fn push_highlighted(&mut self, s: &mut String, lang: &str, code: &str) {
+        let syntaxes = self
+            .syntaxes
+            .get_or_insert_with(|| Arc::new(load_syntaxes()));
+        let syntax = match syntaxes.find_syntax_by_token(lang) {
+            Some(syntax) if !lang.is_empty() => syntax,
+            _ => {
+                let _ = escape_html(&mut *s, code);
+                return;
+            }
+        };
+        let mut generator = ClassedHTMLGenerator::new_with_class_style(
+            syntax,
+            syntaxes,
+            ClassStyle::SpacedPrefixed { prefix: "hl-" },
+        );
+        for line in LinesWithEndings::from(code) {
+            if let Err(err) = generator.parse_html_for_line_which_includes_newline(line) {
+                log::warn!("failed to highlight '{}' code: {}", lang, err);
+                let _ = escape_html(&mut *s, code);
+                return;
+            }
+        }
+        s.push_str(&generator.finalize());
+    }
+}
+
+/// Syntaxes bundled with [`syntect`], and [`GDSCRIPT_SYNTAX`].
+fn load_syntaxes() -> SyntaxSet {
+    let mut builder = SyntaxSet::load_defaults_newlines().into_builder();
+    match SyntaxDefinition::load_from_str(GDSCRIPT_SYNTAX, true, None) {
+        Ok(gdscript) => builder.add(gdscript),
+        Err(err) => log::error!("invalid GDScript syntax: {}", err),
+    }
+    builder.build()
+}
+
+/// Content of `search_index.json`, used by `search.js`.
+///
+/// This is a list of the classes, methods and properties, like:
+/// ```json
+/// [
+///   {
+///     "name": "add_point",
+///     "kind": "method",
+///     "class": "DijkstraMap",
+///     "aliases": ["insert_point"],
+///     "summary": "Add a new point with the given `terrain_type`.",
+///     "url": "DijkstraMap.html#func-add_point"
+///   }
+/// ]
+/// ```
+fn search_index(generator: &Generator) -> String {
+    #[derive(serde::Serialize)]
+    struct Entry<'a> {
+        name: &'a str,
+        kind: &'static str,
+        class: Option<&'a str>,
+        aliases: &'a [String],
+        summary: &'a str,
+        url: String,
+    }
+
+    let mut names: Vec<&String> = generator.documentation.classes.keys().collect();
+    names.sort_unstable();
+    let mut entries = Vec::new();
+    for name in names {
+        let class = &generator.documentation.classes[name];
+        let file = format!("{}.html", class_file_stem(name));
+        entries.push(Entry {
+            name,
+            kind: "class",
+            class: None,
+            aliases: &class.aliases,
+            summary: &class.summary,
+            url: file.clone(),
+        });
+        for property in &class.properties {
+            entries.push(Entry {
+                name: &property.name,
+                kind: "property",
+                class: Some(name),
+                aliases: &[],
+                summary: &property.summary,
+                url: format!("{}#property-{}", file, property.name),
+            });
+        }
+        // accessors are documented with their property
+        for method in class
+            .methods
+            .iter()
+            .filter(|method| method.accessor_of.is_none())
+        {
+            entries.push(Entry {
+                name: &method.name,
+                kind: "method",
+                class: Some(name),
+                aliases: &method.aliases,
+                summary: &method.summary,
+                url: format!("{}#func-{}", file, method.name),
+            });
+        }
+    }
+    to_json(&entries)
+}
+
+/// Replace the relative links of `page` (`href="./..."` and `src="./..."`) with
+/// links starting with `prefix`.
+///
+/// For example with the prefix `/my-repo`, `href="./MyClass.html"` becomes
+/// `href="/my-repo/MyClass.html"`.
+fn prefix_links(page: &str, prefix: &str) -> String {
+    let mut prefix = prefix.trim_end_matches('/').to_string();
+    prefix.push('/');
+    let mut escaped_prefix = String::new();
+    let _ = escape_href(&mut escaped_prefix, &prefix);
+    page.replace("href=\"./", &format!("href=\"{}", escaped_prefix))
+        .replace("src=\"./", &format!("src=\"{}", escaped_prefix))
+}
+
+/// Content of `sitemap.xml`, listing the html `pages` for search engines.
+///
+/// This looks like:
+/// ```xml
+/// <?xml version="1.0" encoding="UTF-8"?>
+/// <urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+/// <url><loc>https://example.com/docs/index.html</loc></url>
+/// </urlset>
+/// ```
+fn sitemap(generator: &Generator, pages: &[&String]) -> String {
+    let mut sitemap = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for page in pages {
+        if let Some(url) = generator.options.html.config.absolute_url(page) {
+            sitemap.push_str("<url><loc>");
+            let _ = escape_html(&mut sitemap, &url);
+            sitemap.push_str("</loc></url>\n");
+        }
+    }
+    sitemap.push_str("</urlset>\n");
+    sitemap
+}
+
+/// Content of `robots.txt`, allowing all pages and pointing to `sitemap.xml`.
+fn robots_txt(generator: &Generator) -> String {
+    let sitemap = generator
+        .options
+        .html
+        .config
+        .absolute_url("sitemap.xml")
+        .unwrap_or_default();
+    format!("User-agent: *\nAllow: /\nSitemap: {}\n", sitemap)
+}
+
+/// Navigation sidebar of a page, with the search box and links to all pages.
+///
+/// This looks like:
+/// ```html
+/// <nav class="sidebar">
+/// <div class="search">...</div>
+/// <p><a href="./index.html">my-crate</a></p>
+/// <h3>Classes</h3>
+/// <ul>
+/// <li><a href="./MyClass.html" class="current">MyClass</a>
+/// <ul>
+/// <li><a href="#func-my_method">my_method</a></li>
+/// </ul>
+/// </li>
+/// </ul>
+/// </nav>
+/// ```
+///
+/// The properties and methods of `current_class` are listed below it.
+fn sidebar(generator: &Generator, current_class: Option<&str>) -> String {
+    fn link(s: &mut String, href: &str, text: &str, current: bool) {
+        s.push_str("<a href=\"");
+        let _ = escape_href(&mut *s, href);
+        s.push('"');
+        if current {
+            s.push_str(" class=\"current\"");
+        }
+        s.push('>');
+        let _ = escape_html(&mut *s, text);
+        s.push_str("</a>");
+    }
+    fn list(s: &mut String, title: &str, items: Vec<(String, &str)>) {
+        if items.is_empty() {
+            return;
+        }
+        let _ = write!(s, "<h3>{}</h3>\n<ul>\n", title);
+        for (href, text) in items {
+            s.push_str("<li>");
+            link(s, &href, text, false);
+            s.push_str("</li>\n");
+        }
+        s.push_str("</ul>\n");
+    }
+
+    let documentation = generator.documentation;
+    let mut s = String::from(
+        r#"<nav class="sidebar">
+<button type="button" id="theme-toggle" class="theme-toggle" title="Switch between light and dark themes">◐</button>
+<div class="search">
+<input type="search" id="search-input" placeholder="Search" aria-label="Search" autocomplete="off"/>
+<ul id="search-results"></ul>
+</div>
+"#,
+    );
+    if let Some(logo) = &generator.options.html.config.logo {
+        s.push_str("<a href=\"./index.html\"><img class=\"logo\" src=\"");
+        let _ = escape_href(&mut s, logo);
+        s.push_str("\" alt=\"\"/></a>\n");
+    }
+    s.push_str("<p class=\"sidebar-title\">");
+    link(&mut s, "./index.html", &documentation.name, false);
+    s.push_str("</p>\n");
+
+    let guides = (generator.guides.iter())
+        .map(|guide| (format!("./{}.html", guide.file_stem), guide.title.as_str()))
+        .collect();
+    list(&mut s, "Guides", guides);
+    if generator.options.module_pages {
+        let modules = (documentation.modules.iter())
+            .map(|module| {
+                (
+                    format!("./{}.html", module.file_stem()),
+                    module.path.as_str(),
+                )
+            })
+            .collect();
+        list(&mut s, "Modules", modules);
+    }
+
+    let categories = generator.class_categories();
+    let grouped = categories.iter().any(|(category, _)| category.is_some());
+    for (category, class_names) in categories {
+        let title = match category {
+            Some(category) => category,
+            None if grouped => "Other",
+            None => "Classes",
+        };
+        s.push_str("<h3>");
+        let _ = escape_html(&mut s, title);
+        s.push_str("</h3>\n<ul>\n");
+        for name in class_names {
+            let current = current_class == Some(name);
+            s.push_str("<li>");
+            link(
+                &mut s,
+                &format!("./{}.html", class_file_stem(name)),
+                name,
+                current,
+            );
+            if current {
+                let class = &documentation.classes[name];
+                s.push_str("\n<ul class=\"members\">\n");
+                for property in &class.properties {
+                    s.push_str("<li>");
+                    link(
+                        &mut s,
+                        &format!("#property-{}", property.name),
+                        &property.name,
+                        false,
+                    );
+                    s.push_str("</li>\n");
+                }
+                // accessors are documented with their property
+                for method in class
+                    .methods
+                    .iter()
+                    .filter(|method| method.accessor_of.is_none())
+                {
+                    s.push_str("<li>");
+                    link(
+                        &mut s,
+                        &format!("#func-{}", method.name),
+                        &method.name,
+                        false,
+                    );
+                    s.push_str("()</li>\n");
+                }
+                s.push_str("</ul>\n");
+            }
+            s.push_str("</li>\n");
+        }
+        s.push_str("</ul>\n");
     }
+    s.push_str("</nav>\n");
+    s
 }
@@ -0,0 +1,322 @@
+//! Backend dumping the documentation as JSON, for other tools to consume.
+//!
+//! The schema is described in [`BuiltinBackend::Json`](super::BuiltinBackend::Json).
+
+use super::{class_file_stem, Callbacks, Generator, Resolver};
+use crate::documentation::{GdnativeClass, Method, ParameterAttribute, Property, Tag, Type};
+use pulldown_cmark::Event;
+use serde::Serialize;
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+/// Version of the JSON files generated by [`BuiltinBackend::Json`].
+///
+/// It is stored in the `schema_version` field of every file, and incremented
+/// whenever a field is removed or changes meaning. Adding a field does not change
+/// the version.
+///
+/// [`BuiltinBackend::Json`]: super::BuiltinBackend::Json
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Default)]
+pub(crate) struct JsonCallbacks {}
+
+impl Callbacks for JsonCallbacks {
+    fn extension(&self) -> &'static str {
+        "json"
+    }
+
+    fn links_to_documented_classes(&self) -> bool {
+        // only the pages of other backends can be linked to
+        false
+    }
+
+    fn generate_files(&mut self, generator: Generator) -> HashMap<String, String> {
+        let documentation = generator.documentation;
+        let resolver = generator.resolver;
+        let root_dir = documentation.root_file.parent();
+
+        let mut names: Vec<&String> = documentation.classes.keys().collect();
+        names.sort_unstable();
+
+        let mut files = HashMap::new();
+        let mut classes = Vec::new();
+        for name in names {
+            let class = &documentation.classes[name];
+            let file_name = format!("{}.json", class_file_stem(name));
+            let content = class_json(resolver, root_dir, name, class);
+            files.insert(file_name.clone(), to_json(&content));
+            classes.push(ClassEntryJson {
+                name,
+                inherit: &class.inherit,
+                summary: &class.summary,
+                file: file_name,
+            });
+        }
+
+        let metadata = &documentation.metadata;
+        let index = IndexJson {
+            schema_version: JSON_SCHEMA_VERSION,
+            godot_version: resolver.godot_version.as_str(),
+            krate: CrateJson {
+                name: &documentation.name,
+                version: metadata.version.as_deref(),
+                repository: metadata.repository.as_deref(),
+                homepage: metadata.homepage.as_deref(),
+                license: metadata.license.as_deref(),
+            },
+            documentation: &documentation.root_documentation,
+            modules: (documentation.modules.iter())
+                .map(|module| ModuleJson {
+                    path: &module.path,
+                    documentation: &module.documentation,
+                    source: source_file(root_dir, &module.file),
+                })
+                .collect(),
+            classes,
+        };
+        files.insert(String::from("index.json"), to_json(&index));
+
+        files
+    }
+
+    fn encode(&mut self, s: &mut String, events: Vec<Event<'_>>) {
+        // Documentation is exported as markdown, so this is only used by custom
+        // callers: keep the text.
+        for event in events {
+            if let Event::Text(text) | Event::Code(text) = event {
+                s.push_str(&text);
+            }
+        }
+    }
+}
+
+/// Pretty-printed JSON of `value`, ending with a newline.
+///
+/// The fields of structures keep their declaration order.
+pub(super) fn to_json(value: &impl Serialize) -> String {
+    let mut json = serde_json::to_string_pretty(value).unwrap_or_default();
+    json.push('\n');
+    json
+}
+
+/// Content of `index.json`.
+#[derive(Serialize)]
+struct IndexJson<'a> {
+    schema_version: u32,
+    godot_version: &'a str,
+    #[serde(rename = "crate")]
+    krate: CrateJson<'a>,
+    documentation: &'a str,
+    modules: Vec<ModuleJson<'a>>,
+    classes: Vec<ClassEntryJson<'a>>,
+}
+
+#[derive(Serialize)]
+struct CrateJson<'a> {
+    name: &'a str,
+    version: Option<&'a str>,
+    repository: Option<&'a str>,
+    homepage: Option<&'a str>,
+    license: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct ModuleJson<'a> {
+    path: &'a str,
+    documentation: &'a str,
+    source: String,
+}
+
+/// Entry of a class in `index.json`.
+#[derive(Serialize)]
+struct ClassEntryJson<'a> {
+    name: &'a str,
+    inherit: &'a str,
+    summary: &'a str,
+    file: String,
+}
+
+/// JSON document of a class.
+#[derive(Serialize)]
+struct ClassJson<'a> {
+    schema_version: u32,
+    name: &'a str,
+    rust_name: &'a str,
+    inherit: &'a str,
+    inherit_url: Option<Cow<'a, str>>,
+    is_tool: bool,
+    deprecated: Option<&'a str>,
+    summary: &'a str,
+    documentation: &'a str,
+    tags: Vec<TagJson<'a>>,
+    aliases: &'a [String],
+    source: String,
+    line: usize,
+    properties: Vec<PropertyJson<'a>>,
+    methods: Vec<MethodJson<'a>>,
+}
+
+#[derive(Serialize)]
+struct PropertyJson<'a> {
+    name: &'a str,
+    #[serde(rename = "type")]
+    typ: Option<TypeJson<'a>>,
+    default: Option<&'a str>,
+    getter: Option<&'a str>,
+    setter: Option<&'a str>,
+    deprecated: Option<&'a str>,
+    summary: &'a str,
+    documentation: &'a str,
+    tags: Vec<TagJson<'a>>,
+    line: usize,
+}
+
+#[derive(Serialize)]
+struct MethodJson<'a> {
+    name: &'a str,
+    is_static: bool,
+    is_async: bool,
+    is_constructor: bool,
+    rpc_mode: Option<&'static str>,
+    parameters: Vec<ParameterJson<'a>>,
+    return_type: Option<TypeJson<'a>>,
+    accessor_of: Option<&'a str>,
+    deprecated: Option<&'a str>,
+    summary: &'a str,
+    documentation: &'a str,
+    tags: Vec<TagJson<'a>>,
+    aliases: &'a [String],
+    line: usize,
+}
+
+#[derive(Serialize)]
+struct ParameterJson<'a> {
+    name: &'a str,
+    #[serde(rename = "type")]
+    typ: Option<TypeJson<'a>>,
+    optional: bool,
+}
+
+/// A type, with its godot name, whether it is nullable, and the link to its
+/// godot documentation if any.
+#[derive(Serialize)]
+struct TypeJson<'a> {
+    name: &'a str,
+    nullable: bool,
+    url: Option<Cow<'a, str>>,
+}
+
+#[derive(Serialize)]
+struct TagJson<'a> {
+    name: &'a str,
+    value: &'a str,
+}
+
+fn class_json<'a>(
+    resolver: &'a Resolver,
+    root_dir: Option<&Path>,
+    name: &'a str,
+    class: &'a GdnativeClass,
+) -> ClassJson<'a> {
+    ClassJson {
+        schema_version: JSON_SCHEMA_VERSION,
+        name,
+        rust_name: &class.name,
+        inherit: &class.inherit,
+        inherit_url: resolver.resolve(&class.inherit),
+        is_tool: class.is_tool,
+        deprecated: class.deprecated.as_deref(),
+        summary: &class.summary,
+        documentation: &class.documentation,
+        tags: tags_json(&class.tags),
+        aliases: &class.aliases,
+        source: source_file(root_dir, &class.file),
+        line: class.line,
+        properties: (class.properties.iter())
+            .map(|property| property_json(resolver, property))
+            .collect(),
+        methods: (class.methods.iter())
+            .map(|method| method_json(resolver, method))
+            .collect(),
+    }
+}
+
+fn property_json<'a>(resolver: &'a Resolver, property: &'a Property) -> PropertyJson<'a> {
+    PropertyJson {
+        name: &property.name,
+        typ: type_json(resolver, &property.typ),
+        default: property.default.as_deref(),
+        getter: property.getter.as_deref(),
+        setter: property.setter.as_deref(),
+        deprecated: property.deprecated.as_deref(),
+        summary: &property.summary,
+        documentation: &property.documentation,
+        tags: tags_json(&property.tags),
+        line: property.line,
+    }
+}
+
+fn method_json<'a>(resolver: &'a Resolver, method: &'a Method) -> MethodJson<'a> {
+    let parameters = (method.parameters.iter())
+        .map(|(name, typ, attribute)| ParameterJson {
+            name,
+            typ: type_json(resolver, typ),
+            optional: *attribute == ParameterAttribute::Opt,
+        })
+        .collect();
+    MethodJson {
+        name: &method.name,
+        is_static: !method.has_self,
+        is_async: method.is_async,
+        is_constructor: method.is_constructor,
+        rpc_mode: method.rpc_mode.map(|mode| mode.keyword()),
+        parameters,
+        return_type: type_json(resolver, &method.return_type),
+        accessor_of: method.accessor_of.as_deref(),
+        deprecated: method.deprecated.as_deref(),
+        summary: &method.summary,
+        documentation: &method.documentation,
+        tags: tags_json(&method.tags),
+        aliases: &method.aliases,
+        line: method.line,
+    }
+}
+
+/// `None` (serialized as `null`) for [`Type::Unit`].
+fn type_json<'a>(resolver: &'a Resolver, typ: &'a Type) -> Option<TypeJson<'a>> {
+    let (name, nullable) = match typ {
+        Type::Option(name) => (name, true),
+        Type::Named(name) => (name, false),
+        Type::Unit => return None,
+    };
+    Some(TypeJson {
+        name,
+        nullable,
+        url: resolver.resolve(name),
+    })
+}
+
+fn tags_json(tags: &[Tag]) -> Vec<TagJson<'_>> {
+    (tags.iter())
+        .map(|tag| TagJson {
+            name: &tag.name,
+            value: &tag.value,
+        })
+        .collect()
+}
+
+/// Path of `file` relative to the root directory of the crate, with `/`
+/// separators.
+fn source_file(root_dir: Option<&Path>, file: &Path) -> String {
+    root_dir
+        .and_then(|root_dir| file.strip_prefix(root_dir).ok())
+        .unwrap_or(&PathBuf::new())
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
@@ -0,0 +1,117 @@
+//! Anchors generated by markdown renderers from the text of the headings.
+//!
+//! See [`MarkdownConfig::anchor_style`](crate::MarkdownConfig::anchor_style).
+
+use super::{class_file_stem, Callbacks, Flavor, Generator};
+use crate::AnchorStyle;
+use pulldown_cmark::{Event, Tag};
+use std::collections::HashMap;
+
+/// Anchors of the headings of methods and properties, as generated by the
+/// renderer.
+pub(super) struct Anchors {
+    /// Anchors by page, then by id (like `func-new`).
+    pages: HashMap<String, HashMap<String, String>>,
+}
+
+impl Anchors {
+    /// Compute the anchors of every class page.
+    ///
+    /// Returns `None` if the headings use raw html anchors: this is always the
+    /// case in MDX, where anchors use the `{#id}` syntax.
+    pub(super) fn new(generator: &Generator, flavor: Flavor) -> Option<Self> {
+        let style = generator.options.markdown.anchor_style;
+        if style == AnchorStyle::Html || flavor == Flavor::Mdx {
+            return None;
+        }
+        let resolver = generator.resolver;
+        let mut heading = HeadingText::default();
+        let mut pages = HashMap::new();
+        for (name, class) in &generator.documentation.classes {
+            let mut anchors = HashMap::new();
+            for property in &class.properties {
+                (&mut heading as &mut dyn Callbacks).start_property_default(
+                    &mut String::new(),
+                    resolver,
+                    property,
+                );
+                let id = format!("property-{}", property.name);
+                anchors.insert(id, slug(style, &heading.take()));
+            }
+            for method in &class.methods {
+                (&mut heading as &mut dyn Callbacks).start_method_default(
+                    &mut String::new(),
+                    resolver,
+                    method,
+                );
+                let id = format!("func-{}", method.name);
+                anchors.insert(id, slug(style, &heading.take()));
+            }
+            pages.insert(class_file_stem(name), anchors);
+        }
+        Some(Self { pages })
+    }
+
+    /// Anchor generated for the heading with the given `id` in `page`.
+    pub(super) fn get(&self, page: &str, id: &str) -> Option<&str> {
+        self.pages.get(page)?.get(id).map(String::as_str)
+    }
+}
+
+/// Collects the text of the heading encoded by `start_method_default` or
+/// `start_property_default`, as it is rendered.
+#[derive(Default)]
+struct HeadingText {
+    text: String,
+    done: bool,
+}
+
+impl HeadingText {
+    fn take(&mut self) -> String {
+        self.done = false;
+        std::mem::take(&mut self.text)
+    }
+}
+
+impl Callbacks for HeadingText {
+    fn extension(&self) -> &'static str {
+        "md"
+    }
+
+    fn generate_files(&mut self, _: Generator) -> HashMap<String, String> {
+        HashMap::new()
+    }
+
+    fn encode(&mut self, _: &mut String, events: Vec<Event<'_>>) {
+        for event in events {
+            match event {
+                _ if self.done => {}
+                Event::Text(text) | Event::Code(text) => self.text.push_str(&text),
+                Event::End(Tag::Heading(..)) => self.done = true,
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Anchor generated by the renderer for a heading containing `text`.
+///
+/// - GitHub and mdBook lowercase the text, drop punctuation, and replace spaces
+///   with `-`.
+/// - GitLab also merges consecutive `-`.
+pub(super) fn slug(style: AnchorStyle, text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.trim().chars() {
+        if c.is_alphanumeric() || c == '_' || c == '-' {
+            slug.extend(c.to_lowercase());
+        } else if c == ' ' || (style == AnchorStyle::Mdbook && c.is_whitespace()) {
+            slug.push('-');
+        }
+    }
+    if style == AnchorStyle::Gitlab {
+        while slug.contains("--") {
+            slug = slug.replace("--", "-");
+        }
+    }
+    slug
+}
@@ -1,9 +1,24 @@
+mod anchors;
 #[cfg(test)]
 mod tests;
 
-use super::{Callbacks, Generator, Method, Property, Resolver};
-use pulldown_cmark::{Alignment, CodeBlockKind, Event, LinkType, Tag};
-use std::{collections::HashMap, fmt::Write as _, path::PathBuf};
+use self::anchors::Anchors;
+
+use super::{
+    class_file_stem, html_anchor, parse_code_block_info, push_escaped, Callbacks, Generator,
+    Method, Page, Property, Resolver,
+};
+use crate::{
+    diagnostics::{diagnostic, Lint},
+    FrontMatter,
+};
+use pulldown_cmark::{Alignment, CodeBlockKind, CowStr, Event, HeadingLevel, LinkType, Tag};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::Write as _,
+    path::Path,
+    sync::Arc,
+};
 
 #[derive(Clone, Copy, PartialEq)]
 enum Nesting {
@@ -18,6 +33,10 @@ enum Nesting {
 }
 
 /// Implementation of [`Callbacks`] for markdown.
+///
+/// This holds the encoding state of a single file: each file is encoded by a fresh
+/// instance (see [`MarkdownCallbacks::encode_file`]), so that no state leaks from
+/// one file to the next, and files could be encoded in parallel.
 #[derive(Default)]
 pub(crate) struct MarkdownCallbacks {
     /// The same name can be used for multiple shortcut links, because they
@@ -34,48 +53,170 @@ pub(crate) struct MarkdownCallbacks {
     nesting: Vec<Nesting>,
     /// Have we written to the string since we last pushed to `nesting` ?
     top_written: bool,
+    /// Unsupported features encountered in the file, with their count.
+    unsupported: BTreeMap<Unsupported, usize>,
+    /// Variant of markdown to output.
+    flavor: Flavor,
+    /// Are we encoding a heading ?
+    in_heading: bool,
+    /// In MDX, id of the heading being encoded, written as `{#id}` at its end.
+    heading_id: Option<String>,
+    /// Set when all pages are encoded in a single file.
+    single_file: Option<SingleFile>,
+    /// Write shortcut links as inline links, instead of adding their definitions
+    /// at the bottom of the file.
+    inline_links: bool,
+    /// See [`GeneratorOptions::absolute_links`](super::GeneratorOptions::absolute_links).
+    absolute_links: Option<String>,
+    /// Anchors generated by the renderer, if headings have no raw html anchor.
+    anchors: Option<Arc<Anchors>>,
+    /// Name of the page being encoded, without extension.
+    current_page: String,
+}
+
+/// Variant of markdown output by [`MarkdownCallbacks`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Flavor {
+    #[default]
+    Markdown,
+    /// [MDX](https://mdxjs.com/), as used by Docusaurus.
+    ///
+    /// Heading anchors use the `{#id}` syntax, characters starting JSX (`{`, `}`
+    /// and `<`) are escaped in text, and the opening comment is a JSX comment.
+    Mdx,
+    /// Pages of a GitHub wiki.
+    ///
+    /// The index is `Home.md`, and links to other pages have no extension.
+    GithubWiki,
+}
+
+/// State of [`MarkdownCallbacks`] when encoding all pages in a single file.
+///
+/// Links to other pages become links to anchors in the file, and anchors of a
+/// page are prefixed by its name, like `MyClass-func-new`.
+struct SingleFile {
+    /// Names of all the pages, without extension.
+    pages: HashSet<String>,
+    /// Page being encoded, or `None` for the index.
+    current: Option<String>,
+}
+
+/// Markdown features that are not supported by [`MarkdownCallbacks`], and are
+/// dropped from the output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum Unsupported {
+    FootnoteDefinition,
+    FootnoteReference,
+    NestedLink,
+}
+
+impl std::fmt::Display for Unsupported {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::FootnoteDefinition => "footnote definitions",
+            Self::FootnoteReference => "footnote references",
+            Self::NestedLink => "nested links",
+        })
+    }
 }
 
 impl Callbacks for MarkdownCallbacks {
     fn extension(&self) -> &'static str {
-        "md"
+        match self.flavor {
+            Flavor::Markdown | Flavor::GithubWiki => "md",
+            Flavor::Mdx => "mdx",
+        }
+    }
+
+    fn pages_directory(&self) -> Option<&'static str> {
+        Some("")
     }
 
     fn generate_files(&mut self, generator: Generator) -> HashMap<String, String> {
+        if generator.options.markdown.single_file && self.flavor == Flavor::Markdown {
+            return Self::generate_single_file(&generator);
+        }
+        let extension = self.extension();
+        let flavor = self.flavor;
+        self.inline_links = generator.options.markdown.inline_links;
+        self.absolute_links = generator.options.absolute_links.clone();
+        self.anchors = Anchors::new(&generator, flavor).map(Arc::new);
         let mut files = HashMap::new();
+        let mut unsupported_report = BTreeMap::new();
+        let mut add_file =
+            |file_name: String, (content, unsupported): (String, BTreeMap<Unsupported, usize>)| {
+                if !unsupported.is_empty() {
+                    unsupported_report.insert(file_name.clone(), unsupported);
+                }
+                files.insert(file_name, content);
+            };
 
-        let mut index_content = format!(
-            r"{}{}",
-            Self::make_opening_comment(
-                &generator,
-                &generator
-                    .documentation
-                    .root_file
-                    .file_name()
-                    .and_then(|name| name.to_str())
-                    .unwrap_or_default(),
-            ),
-            generator.generate_root_file("md", self),
+        let root_file = generator
+            .documentation
+            .root_file
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        add_file(
+            match flavor {
+                Flavor::GithubWiki => String::from("Home.md"),
+                _ => format!("index.{}", extension),
+            },
+            self.encode_file(&generator, "index", &root_file, |callbacks| {
+                let mut root_file = generator.generate_root_file(extension, callbacks);
+                root_file.push_str(&generator.generate_root_footer(callbacks));
+                root_file
+            }),
         );
-
-        self.finish_encoding(&mut index_content);
-        files.insert(String::from("index.md"), index_content);
-        let root_dir = generator.documentation.root_file.parent();
-        for (name, class) in &generator.documentation.classes {
-            let mut content = format!(
-                r"{}{}",
-                Self::make_opening_comment(
-                    &generator,
-                    &root_dir
-                        .and_then(|root_dir| class.file.strip_prefix(root_dir).ok())
-                        .unwrap_or(&PathBuf::new())
-                        .display(),
-                ),
-                generator.generate_file(name, class, self)
+        for (index, guide) in generator.guides.iter().enumerate() {
+            add_file(
+                format!("{}.{}", guide.file_stem, extension),
+                self.encode_file(&generator, &guide.file_stem, &root_file, |callbacks| {
+                    generator.generate_guide_file(index, extension, callbacks)
+                }),
             );
-            let name = format!("{}.md", name);
-            self.finish_encoding(&mut content);
-            files.insert(name, content);
+        }
+        let root_dir = generator.documentation.root_file.parent();
+        if generator.options.module_pages {
+            for module in &generator.documentation.modules {
+                let source_file = root_dir
+                    .and_then(|root_dir| module.file.strip_prefix(root_dir).ok())
+                    .unwrap_or(Path::new(""))
+                    .display();
+                add_file(
+                    format!("{}.{}", module.file_stem(), extension),
+                    self.encode_file(&generator, &module.file_stem(), &source_file, |callbacks| {
+                        generator.generate_module_file(module, extension, callbacks)
+                    }),
+                );
+            }
+        }
+        let this = &*self;
+        let classes = generator.map_classes(|name, class| {
+            let source_file = root_dir
+                .and_then(|root_dir| class.file.strip_prefix(root_dir).ok())
+                .unwrap_or(Path::new(""))
+                .display();
+            let file_stem = class_file_stem(name);
+            let encoded = this.encode_file(&generator, &file_stem, &source_file, |callbacks| {
+                generator.generate_file(name, class, callbacks)
+            });
+            (format!("{}.{}", file_stem, extension), encoded)
+        });
+        for (file_name, encoded) in classes {
+            add_file(file_name, encoded);
+        }
+        Self::log_unsupported_report(unsupported_report);
+
+        if let Some(front_matter) = &generator.options.markdown.front_matter {
+            let date = current_date();
+            for (index, page) in generator.pages().iter().enumerate() {
+                let file_name = format!("{}.{}", page.file_stem, extension);
+                if let Some(content) = files.get_mut(&file_name) {
+                    content.insert_str(0, &make_front_matter(front_matter, page, index + 1, &date));
+                }
+            }
         }
 
         files
@@ -99,13 +240,30 @@ impl Callbacks for MarkdownCallbacks {
                             self.apply_nesting(s)
                         }
                     }
-                    Tag::Heading(level, _, _) => {
+                    Tag::Heading(mut level, id, _) => {
                         self.apply_nesting(s);
                         self.top_written = true;
+                        self.in_heading = true;
+                        let mut id = id.map(String::from);
+                        if let Some(prefix) = self.anchor_prefix() {
+                            // pages are sections of the single file
+                            level = HeadingLevel::try_from(level as usize + 1)
+                                .unwrap_or(HeadingLevel::H6);
+                            id = id.map(|id| format!("{}-{}", prefix, id));
+                        }
                         for _ in 0..(level as i32) {
                             s.push('#');
                         }
                         s.push(' ');
+                        if let Some(id) = id {
+                            if self.anchors.is_some() {
+                                // the renderer generates the anchor
+                            } else if self.flavor == Flavor::Mdx {
+                                self.heading_id = Some(id.to_string());
+                            } else {
+                                let _ = write!(s, "<a id=\"{}\"></a>", id);
+                            }
+                        }
                     }
                     Tag::BlockQuote => self.nesting.push(Nesting::Quote),
                     Tag::CodeBlock(kind) => match kind {
@@ -119,7 +277,9 @@ impl Callbacks for MarkdownCallbacks {
                             self.apply_nesting(s);
                             self.top_written = true;
                             s.push_str("```");
-                            s.push_str(&lang);
+                            // attributes and highlighted lines are only meaningful
+                            // to gdnative-doc
+                            s.push_str(parse_code_block_info(&lang).lang);
                             self.apply_nesting(s);
                         }
                     },
@@ -131,7 +291,7 @@ impl Callbacks for MarkdownCallbacks {
                         self.top_written = false;
                     }
                     Tag::FootnoteDefinition(_) => {
-                        log::warn!("FootnoteDefinition: Unsupported at the moment")
+                        self.record_unsupported(Unsupported::FootnoteDefinition)
                     }
                     Tag::Table(alignment) => {
                         self.tables_alignements.push(alignment);
@@ -143,9 +303,9 @@ impl Callbacks for MarkdownCallbacks {
                     Tag::Strong => s.push_str("**"),
                     Tag::Strikethrough => s.push_str("~~"),
                     Tag::Link(link_type, _, _) => {
-                        if link_type == LinkType::Shortcut {
+                        if link_type == LinkType::Shortcut && !self.inline_links {
                             if self.shortcut_link.is_some() {
-                                log::error!("Links are not supposed to be nested")
+                                self.record_unsupported(Unsupported::NestedLink)
                             }
                             self.shortcut_link = Some("".to_string());
                         }
@@ -164,7 +324,12 @@ impl Callbacks for MarkdownCallbacks {
                 },
                 Event::End(tag) => match tag {
                     Tag::Paragraph => {}
-                    Tag::Heading(_, _, _) => {}
+                    Tag::Heading(_, _, _) => {
+                        self.in_heading = false;
+                        if let Some(id) = self.heading_id.take() {
+                            let _ = write!(s, " {{#{}}}", id);
+                        }
+                    }
                     Tag::BlockQuote => {
                         self.nesting.pop();
                     }
@@ -206,9 +371,14 @@ impl Callbacks for MarkdownCallbacks {
                     Tag::Strong => s.push_str("**"),
                     Tag::Strikethrough => s.push_str("~~"),
                     Tag::Link(link_type, dest, title) => {
+                        let dest = self.styled_anchor_link(&dest).unwrap_or(dest);
+                        let dest = (self.single_file_link(&dest))
+                            .or_else(|| self.wiki_link(&dest))
+                            .unwrap_or(dest);
+                        let dest = self.absolute_link(&dest).unwrap_or(dest);
                         s.push(']');
                         let closing_character = match link_type {
-                            LinkType::Shortcut => {
+                            LinkType::Shortcut if !self.inline_links => {
                                 if let Some(shortcut) = self.shortcut_link.take() {
                                     self.add_shortcut_link(shortcut, &dest);
                                 }
@@ -234,7 +404,11 @@ impl Callbacks for MarkdownCallbacks {
                 },
                 Event::Text(text) => {
                     self.top_written = true;
-                    self.push_str(s, &text)
+                    if self.flavor == Flavor::Mdx {
+                        self.push_str(s, &escape_mdx(&text))
+                    } else {
+                        self.push_str(s, &text)
+                    }
                 }
                 Event::Code(code) => {
                     self.top_written = true;
@@ -244,10 +418,24 @@ impl Callbacks for MarkdownCallbacks {
                 }
                 Event::Html(html) => {
                     self.top_written = true;
-                    s.push_str(&html)
+                    match (html_anchor(&html), self.anchor_prefix()) {
+                        // the renderer generates the anchor
+                        (Some(_), _) if self.anchors.is_some() => {}
+                        // MDX headings use the `{#id}` syntax
+                        (Some(id), _) if self.flavor == Flavor::Mdx && self.in_heading => {
+                            self.heading_id = Some(id.to_string())
+                        }
+                        (Some(id), Some(prefix)) => {
+                            let _ = write!(s, "<a id=\"{}-{}\"></a>", prefix, id);
+                            if html.ends_with(' ') {
+                                s.push(' ');
+                            }
+                        }
+                        _ => s.push_str(&html),
+                    }
                 }
                 Event::FootnoteReference(_) => {
-                    log::warn!("FootnoteReference: Unsupported at the moment")
+                    self.record_unsupported(Unsupported::FootnoteReference)
                 }
                 Event::SoftBreak => self.apply_nesting(s),
                 Event::HardBreak => {
@@ -268,6 +456,187 @@ impl Callbacks for MarkdownCallbacks {
 }
 
 impl MarkdownCallbacks {
+    /// Callbacks producing [MDX](https://mdxjs.com/), as used by Docusaurus.
+    pub(crate) fn mdx() -> Self {
+        Self {
+            flavor: Flavor::Mdx,
+            ..Self::default()
+        }
+    }
+
+    /// Callbacks producing the pages of a GitHub wiki.
+    pub(crate) fn github_wiki() -> Self {
+        Self {
+            flavor: Flavor::GithubWiki,
+            ..Self::default()
+        }
+    }
+
+    /// Encode all pages in a single `API.md` file.
+    ///
+    /// See [`MarkdownConfig::single_file`](crate::MarkdownConfig::single_file).
+    fn generate_single_file(generator: &Generator) -> HashMap<String, String> {
+        let pages = generator.pages();
+        let mut encoder = Self {
+            single_file: Some(SingleFile {
+                pages: pages.iter().map(|page| page.file_stem.clone()).collect(),
+                current: None,
+            }),
+            inline_links: generator.options.markdown.inline_links,
+            ..Self::default()
+        };
+        let root_file = generator
+            .documentation
+            .root_file
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+
+        let mut content = String::new();
+        if let Some(front_matter) = &generator.options.markdown.front_matter {
+            content.push_str(&make_front_matter(
+                front_matter,
+                &pages[0],
+                1,
+                &current_date(),
+            ));
+        }
+        content.push_str(&Self::make_opening_comment(
+            generator,
+            &root_file,
+            Flavor::Markdown,
+        ));
+        content.push_str(&generator.generate_root_file("md", &mut encoder));
+
+        fn add_page(
+            content: &mut String,
+            encoder: &mut MarkdownCallbacks,
+            file_stem: String,
+            generate: &dyn Fn(&mut dyn Callbacks) -> String,
+        ) {
+            let _ = write!(content, "\n\n<a id=\"{}\"></a>\n\n", file_stem);
+            if let Some(single_file) = &mut encoder.single_file {
+                single_file.current = Some(file_stem);
+            }
+            content.push_str(&generate(encoder));
+        }
+        for (index, guide) in generator.guides.iter().enumerate() {
+            add_page(
+                &mut content,
+                &mut encoder,
+                guide.file_stem.clone(),
+                &|callbacks| generator.generate_guide_file(index, "md", callbacks),
+            );
+        }
+        if generator.options.module_pages {
+            for module in &generator.documentation.modules {
+                add_page(
+                    &mut content,
+                    &mut encoder,
+                    module.file_stem(),
+                    &|callbacks| generator.generate_module_file(module, "md", callbacks),
+                );
+            }
+        }
+        for (_, class_names) in generator.class_categories() {
+            for name in class_names {
+                let class = &generator.documentation.classes[name];
+                add_page(
+                    &mut content,
+                    &mut encoder,
+                    class_file_stem(name),
+                    &|callbacks| generator.generate_file(name, class, callbacks),
+                );
+            }
+        }
+        if let Some(single_file) = &mut encoder.single_file {
+            single_file.current = None;
+        }
+        content.push_str(&generator.generate_root_footer(&mut encoder));
+
+        let file_name = String::from("API.md");
+        let unsupported = encoder.finish_encoding(&mut content);
+        if !unsupported.is_empty() {
+            Self::log_unsupported_report(BTreeMap::from([(file_name.clone(), unsupported)]));
+        }
+        HashMap::from([(file_name, content)])
+    }
+
+    /// Prefix of the anchors of the page being encoded, in a single file.
+    fn anchor_prefix(&self) -> Option<&str> {
+        self.single_file.as_ref()?.current.as_deref()
+    }
+
+    /// In a single file, rewrite a link to another page (like
+    /// `./MyClass.md#func-new`) or to an anchor of the current page (like
+    /// `#func-new`) to the corresponding anchor in the file.
+    fn single_file_link(&self, dest: &str) -> Option<CowStr<'static>> {
+        let single_file = self.single_file.as_ref()?;
+        if let Some(anchor) = dest.strip_prefix('#') {
+            let prefix = single_file.current.as_deref()?;
+            return Some(format!("#{}-{}", prefix, anchor).into());
+        }
+        let (path, anchor) = match dest.split_once('#') {
+            Some((path, anchor)) => (path, Some(anchor)),
+            None => (dest, None),
+        };
+        let page = path.trim_start_matches("./").strip_suffix(".md")?;
+        if page == "index" {
+            return Some(CowStr::Borrowed("#"));
+        }
+        if !single_file.pages.contains(page) {
+            return None;
+        }
+        Some(match anchor {
+            Some(anchor) => format!("#{}-{}", page, anchor).into(),
+            None => format!("#{}", page).into(),
+        })
+    }
+
+    /// In a GitHub wiki, rewrite a link to another page (like
+    /// `./MyClass.md#func-new`) to the name of the page (like `MyClass#func-new`).
+    fn wiki_link(&self, dest: &str) -> Option<CowStr<'static>> {
+        if self.flavor != Flavor::GithubWiki || dest.contains("://") {
+            return None;
+        }
+        let (path, anchor) = match dest.split_once('#') {
+            Some((path, anchor)) => (path, Some(anchor)),
+            None => (dest, None),
+        };
+        let page = path.trim_start_matches("./").strip_suffix(".md")?;
+        let page = if page == "index" { "Home" } else { page };
+        Some(match anchor {
+            Some(anchor) => format!("{}#{}", page, anchor).into(),
+            None => page.to_string().into(),
+        })
+    }
+
+    /// With [`absolute_links`](super::GeneratorOptions::absolute_links), rewrite a link to
+    /// another page (like `./MyClass.md#func-new`) to start with the base URL.
+    fn absolute_link(&self, dest: &str) -> Option<CowStr<'static>> {
+        let base_url = self.absolute_links.as_deref()?;
+        if dest.is_empty() || dest.starts_with(['#', '/']) || dest.contains(':') {
+            return None;
+        }
+        let path = dest.trim_start_matches("./");
+        Some(format!("{}/{}", base_url.trim_end_matches('/'), path).into())
+    }
+
+    /// Without raw html anchors, rewrite a link to a method or property (like
+    /// `./MyClass.md#func-new` or `#func-new`) to the anchor generated by the
+    /// renderer for its heading.
+    fn styled_anchor_link(&self, dest: &str) -> Option<CowStr<'static>> {
+        let anchors = self.anchors.as_ref()?;
+        let (path, id) = dest.split_once('#')?;
+        let page = if path.is_empty() {
+            self.current_page.as_str()
+        } else {
+            path.trim_start_matches("./").strip_suffix(".md")?
+        };
+        let anchor = anchors.get(page, id)?;
+        Some(format!("{}#{}", path, anchor).into())
+    }
+
     /// Push `string` in both `s` and `self.shortcut_link` if is is `Some`.
     fn push_str(&mut self, s: &mut String, string: &str) {
         self.top_written = true;
@@ -284,8 +653,8 @@ impl MarkdownCallbacks {
     ///   - `0`: does nothing.
     ///   - `> 0`: change `shortcut` to `shortcut-index`.
     /// - If it is already present, but none of the `n` links associated
-    /// with it correspond to `link`, add `link` to its list and change
-    /// `shortcut` to `shortcut-n`.
+    ///   with it correspond to `link`, add `link` to its list and change
+    ///   `shortcut` to `shortcut-n`.
     fn add_shortcut_link(&mut self, mut shortcut: String, link: &str) {
         if let Some(links) = self.links.get_mut(&shortcut) {
             if let Some((index, _)) = links.iter().enumerate().find(|(_, l)| l == &link) {
@@ -315,7 +684,7 @@ impl MarkdownCallbacks {
     }
 
     /// - If the last item in `self.nesting` is `Nesting::StartListItem`, replace it
-    /// with `Nesting::ListItem` and returns.
+    ///   with `Nesting::ListItem` and returns.
     /// - Else, push a new line in `s` with indentation given by `self.nesting`.
     fn apply_nesting(&mut self, s: &mut String) {
         if !self.top_written {
@@ -335,13 +704,65 @@ impl MarkdownCallbacks {
         }
     }
 
-    /// Called after encoding a file.
-    fn finish_encoding(&mut self, s: &mut String) {
+    /// Record that an unsupported markdown feature was dropped from the output.
+    fn record_unsupported(&mut self, feature: Unsupported) {
+        log::debug!("unsupported markdown feature: {}", feature);
+        *self.unsupported.entry(feature).or_insert(0) += 1;
+    }
+
+    /// Log the features that were dropped from each file, in a single warning.
+    fn log_unsupported_report(report: BTreeMap<String, BTreeMap<Unsupported, usize>>) {
+        if report.is_empty() {
+            return;
+        }
+        let mut message = String::from("some markdown features are unsupported, and were dropped:");
+        for (file_name, features) in report {
+            let _ = write!(message, "\n  {}:", file_name);
+            for (index, (feature, count)) in features.into_iter().enumerate() {
+                if index > 0 {
+                    message.push(',');
+                }
+                let _ = write!(message, " {} ({})", feature, count);
+            }
+        }
+        diagnostic!(Lint::UnsupportedMarkdown, "{}", message);
+    }
+
+    /// Encode the page `file_stem` with a fresh encoder, with the same options as
+    /// `self`.
+    ///
+    /// `generate` should encode the content of the file with the given callbacks.
+    ///
+    /// Returns the content of the file, and the unsupported features that were
+    /// dropped from it.
+    fn encode_file(
+        &self,
+        generator: &Generator,
+        file_stem: &str,
+        source_file: &dyn std::fmt::Display,
+        generate: impl FnOnce(&mut dyn Callbacks) -> String,
+    ) -> (String, BTreeMap<Unsupported, usize>) {
+        let mut encoder = Self {
+            flavor: self.flavor,
+            inline_links: self.inline_links,
+            absolute_links: self.absolute_links.clone(),
+            anchors: self.anchors.clone(),
+            current_page: file_stem.to_string(),
+            ..Self::default()
+        };
+        let mut content = Self::make_opening_comment(generator, source_file, self.flavor);
+        content.push_str(&generate(&mut encoder));
+        let unsupported = encoder.finish_encoding(&mut content);
+        (content, unsupported)
+    }
+
+    /// Called after encoding the file: add the shortcut links definitions to `s`.
+    ///
+    /// Returns the unsupported features encountered in the file.
+    fn finish_encoding(self, s: &mut String) -> BTreeMap<Unsupported, usize> {
         s.push('\n');
         let mut link_lines = Vec::new();
-        self.shortcut_link.take();
-        let links = std::mem::take(&mut self.links);
-        for (shortcut, links) in links {
+        for (shortcut, links) in self.links {
             for (index, link) in links.into_iter().enumerate() {
                 let mut line = String::new();
                 line.push('[');
@@ -359,23 +780,35 @@ impl MarkdownCallbacks {
             s.push('\n');
             s.push_str(&line)
         }
+        self.unsupported
     }
 
-    /// Generate an opening comment if `generator.opening_comment` is `true`.
+    /// Generate an opening comment if `generator.options.opening_comment` is `true`.
     ///
     /// Else, returns an empty `String`.
-    fn make_opening_comment(generator: &Generator, source_file: &dyn std::fmt::Display) -> String {
-        if generator.opening_comment {
+    fn make_opening_comment(
+        generator: &Generator,
+        source_file: &dyn std::fmt::Display,
+        flavor: Flavor,
+    ) -> String {
+        if generator.options.opening_comment {
+            let (start, end) = match flavor {
+                Flavor::Mdx => ("{/*", "*/}"),
+                Flavor::Markdown | Flavor::GithubWiki => ("<!--", "-->"),
+            };
             format!(
-                r"<!-- 
+                r"{} 
 This file was automatically generated using [gdnative-doc-rs](https://github.com/arnaudgolfouse/gdnative-doc-rs)
 
 Crate: {}
 Source file: {}
--->
+{}
 
 ",
-                generator.documentation.name, source_file,
+                start,
+                generator.documentation.name_and_version(),
+                source_file,
+                end,
             )
         } else {
             String::new()
@@ -383,6 +816,75 @@ Source file: {}
     }
 }
 
+/// Front matter of `page`, at position `weight` in the index.
+///
+/// See [`ConfigFile::front_matter`](crate::ConfigFile::front_matter).
+fn make_front_matter(front_matter: &FrontMatter, page: &Page, weight: usize, date: &str) -> String {
+    let format = front_matter.format.unwrap_or_default();
+    let template = (front_matter.template.as_deref()).unwrap_or(format.default_template());
+    let mut title = String::new();
+    push_escaped(&mut title, &page.title, &['"']);
+    let content = template
+        .replace("{title}", &title)
+        .replace("{weight}", &weight.to_string())
+        .replace("{date}", date)
+        .replace("{file}", &page.file_stem);
+    let delimiter = format.delimiter();
+    let newline = if content.ends_with('\n') { "" } else { "\n" };
+    format!("{delimiter}\n{content}{newline}{delimiter}\n\n")
+}
+
+/// Current date in UTC, like `2022-10-25`.
+///
+/// The `SOURCE_DATE_EPOCH` environment variable is used if it is set, for
+/// reproducible builds.
+fn current_date() -> String {
+    let seconds = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.trim().parse::<u64>().ok())
+        .unwrap_or_else(|| {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_secs())
+        });
+    date_from_timestamp(seconds)
+}
+
+/// Date of a UNIX timestamp, like `2022-10-25`.
+fn date_from_timestamp(seconds: u64) -> String {
+    // days to civil date, from http://howardhinnant.github.io/date_algorithms.html
+    let days = (seconds / 86400) as i64 + 719468;
+    let era = days / 146097;
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Escape the characters of `text` that MDX would parse as JSX.
+fn escape_mdx(text: &str) -> std::borrow::Cow<'_, str> {
+    if !text.contains(['{', '}', '<']) {
+        return text.into();
+    }
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '{' | '}' | '<') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped.into()
+}
+
 /// Remove trailing whitespace.
 fn trim(s: &mut String) {
     while let Some(c) = s.pop() {
@@ -14,23 +14,20 @@ use super::*;
 fn encode(source: &str) -> String {
     let mut callbacks = MarkdownCallbacks::default();
     let mut res = String::new();
-    callbacks.encode(
-        &mut res,
-        pulldown_cmark::Parser::new(source).into_iter().collect(),
-    );
+    callbacks.encode(&mut res, pulldown_cmark::Parser::new(source).collect());
     res
 }
 
 #[test]
 fn simple_text() {
     let simple = encode("hello world !");
-    insta::assert_display_snapshot!(simple)
+    insta::assert_snapshot!(simple)
 }
 
 #[test]
 fn simple_code() {
     let code = encode("`hello code !`");
-    insta::assert_display_snapshot!(code)
+    insta::assert_snapshot!(code)
 }
 
 #[test]
@@ -44,7 +41,7 @@ fn simple_code() {
 }
 ```"#,
     );
-    insta::assert_display_snapshot!(code_block)
+    insta::assert_snapshot!(code_block)
 }
 
 #[test]
@@ -55,7 +52,7 @@ fn simple_quote() {
 > On multiple lines.
 "#,
     );
-    insta::assert_display_snapshot!(quote)
+    insta::assert_snapshot!(quote)
 }
 
 #[test]
@@ -68,7 +65,7 @@ Normal text.
 ~~ Strikethrough ? ~~
 "#,
     );
-    insta::assert_display_snapshot!(text)
+    insta::assert_snapshot!(text)
 }
 
 #[test]
@@ -78,7 +75,7 @@ fn new_paragraph() {
 
 world !",
     );
-    insta::assert_display_snapshot!(new_paragraph)
+    insta::assert_snapshot!(new_paragraph)
 }
 
 #[test]
@@ -94,7 +91,7 @@ fn bullet_list() {
       Dear
   - ?",
     );
-    insta::assert_display_snapshot!(list)
+    insta::assert_snapshot!(list)
 }
 
 #[test]
@@ -110,7 +107,7 @@ fn numbered_list() {
         Dear
     2. ?",
     );
-    insta::assert_display_snapshot!(list)
+    insta::assert_snapshot!(list)
 }
 
 #[test]
@@ -128,7 +125,7 @@ fn complicated_list() {
     Resume on a new paragraph...
 3. Last item."#,
     );
-    insta::assert_display_snapshot!(list)
+    insta::assert_snapshot!(list)
 }
 
 #[test]
@@ -145,5 +142,263 @@ fn quotes_and_lists() {
     > Final quote
 "#,
     );
-    insta::assert_display_snapshot!(mixed)
+    insta::assert_snapshot!(mixed)
+}
+
+#[test]
+fn unsupported_features_report() {
+    let mut callbacks = MarkdownCallbacks::default();
+    let mut res = String::new();
+    callbacks.encode(
+        &mut res,
+        pulldown_cmark::Parser::new_ext(
+            "Text[^1] and[^1]\n\n[^1]: footnote",
+            pulldown_cmark::Options::ENABLE_FOOTNOTES,
+        )
+        .collect(),
+    );
+    let features = callbacks.finish_encoding(&mut res);
+    assert_eq!(features[&Unsupported::FootnoteReference], 2);
+    assert_eq!(features[&Unsupported::FootnoteDefinition], 1);
+    assert!(!features.contains_key(&Unsupported::NestedLink));
+}
+
+#[test]
+fn shortcut_links_are_per_file() {
+    let encode_file = |source: &str| {
+        let mut callbacks = MarkdownCallbacks::default();
+        let mut res = String::new();
+        callbacks.encode(&mut res, pulldown_cmark::Parser::new(source).collect());
+        callbacks.finish_encoding(&mut res);
+        res
+    };
+    let first = encode_file("[link]\n\n[link]: https://first.org");
+    let second = encode_file("[link]\n\n[link]: https://second.org");
+    assert!(first.ends_with("[link]: https://first.org"));
+    assert!(second.ends_with("[link]: https://second.org"));
+    assert!(!second.contains("first.org"));
+}
+
+#[test]
+fn code_block_highlights_are_dropped() {
+    assert_eq!(
+        encode("```gdscript{1,3-4}\nvar a = 1\n```"),
+        encode("```gdscript\nvar a = 1\n```")
+    );
+    assert_eq!(
+        encode("```gdscript, before_each\nvar a = 1\n```"),
+        encode("```gdscript\nvar a = 1\n```")
+    );
+}
+
+#[test]
+fn heading_ids() {
+    use crate::backend::{heading_ids, set_heading_ids};
+
+    let events: Vec<_> =
+        pulldown_cmark::Parser::new("# Errors\n\n# `Errors`\n\n## Panics !").collect();
+    let ids = heading_ids(&events, "func-run");
+    assert_eq!(
+        ids,
+        ["func-run-errors", "func-run-errors-1", "func-run-panics"]
+    );
+
+    let mut callbacks = MarkdownCallbacks::default();
+    let mut res = String::new();
+    callbacks.encode(&mut res, set_heading_ids(events, &ids));
+    assert!(res.contains(r#"# <a id="func-run-errors"></a>Errors"#));
+    assert!(res.contains(r#"## <a id="func-run-panics"></a>Panics !"#));
+}
+
+#[test]
+fn mdx_output() {
+    let mut callbacks = MarkdownCallbacks::mdx();
+    let mut res = String::new();
+    callbacks.encode(
+        &mut res,
+        vec![
+            Event::Start(Tag::Heading(
+                pulldown_cmark::HeadingLevel::H3,
+                None,
+                Vec::new(),
+            )),
+            Event::Html(r#"<a id="func-get"></a>"#.into()),
+            Event::Text("func get() -> Option<{T}>".into()),
+            Event::End(Tag::Heading(
+                pulldown_cmark::HeadingLevel::H3,
+                None,
+                Vec::new(),
+            )),
+        ],
+    );
+    callbacks.encode(
+        &mut res,
+        pulldown_cmark::Parser::new("Returns `Vec<T>` or {}.").collect(),
+    );
+    assert_eq!(
+        res,
+        "### func get() -> Option\\<\\{T\\}> {#func-get}\n\nReturns `Vec<T>` or \\{\\}."
+    );
+}
+
+#[test]
+fn front_matter() {
+    use crate::FrontMatterFormat;
+
+    assert_eq!(date_from_timestamp(0), "1970-01-01");
+    assert_eq!(date_from_timestamp(1_666_656_000), "2022-10-25");
+    assert_eq!(date_from_timestamp(951_782_400), "2000-02-29");
+
+    let page = Page {
+        file_stem: String::from("MyClass"),
+        title: String::from("My \"Class\""),
+        category: None,
+    };
+    let yaml = FrontMatter::default();
+    assert_eq!(
+        make_front_matter(&yaml, &page, 3, "2022-10-25"),
+        "---\ntitle: \"My \\\"Class\\\"\"\nweight: 3\n---\n\n"
+    );
+    let toml = FrontMatter {
+        format: Some(FrontMatterFormat::Toml),
+        template: Some(String::from(
+            "title = \"{title}\"\ndate = {date}\n[extra]\nfile = \"{file}\"",
+        )),
+    };
+    assert_eq!(
+        make_front_matter(&toml, &page, 3, "2022-10-25"),
+        "+++\ntitle = \"My \\\"Class\\\"\"\ndate = 2022-10-25\n[extra]\nfile = \"MyClass\"\n+++\n\n"
+    );
+}
+
+#[test]
+fn single_file_links() {
+    let mut callbacks = MarkdownCallbacks {
+        single_file: Some(SingleFile {
+            pages: HashSet::from([String::from("Other"), String::from("MyClass")]),
+            current: Some(String::from("MyClass")),
+        }),
+        ..MarkdownCallbacks::default()
+    };
+    let mut res = String::new();
+    callbacks.encode(
+        &mut res,
+        pulldown_cmark::Parser::new(
+            "# Title\n\n[a](./Other.md#func-x), [b](#func-y), [c](./index.md), [d](./Unknown.md)",
+        )
+        .collect(),
+    );
+    assert_eq!(
+        res,
+        "## Title\n\n[a](#Other-func-x), [b](#MyClass-func-y), [c](#), [d](./Unknown.md)"
+    );
+}
+
+#[test]
+fn github_wiki_links() {
+    let mut callbacks = MarkdownCallbacks::github_wiki();
+    let mut res = String::new();
+    callbacks.encode(
+        &mut res,
+        pulldown_cmark::Parser::new(
+            "[a](./Other.md#func-x), [b](#func-y), [c](./index.md), [d](https://example.com/a.md)",
+        )
+        .collect(),
+    );
+    assert_eq!(
+        res,
+        "[a](Other#func-x), [b](#func-y), [c](Home), [d](https://example.com/a.md)"
+    );
+}
+
+#[test]
+fn inline_links() {
+    let source = "[a], [b] and [a]\n\n[a]: ./A.md\n[b]: https://example.com";
+    let mut callbacks = MarkdownCallbacks::default();
+    let mut res = String::new();
+    callbacks.encode(&mut res, pulldown_cmark::Parser::new(source).collect());
+    callbacks.finish_encoding(&mut res);
+    assert_eq!(
+        res,
+        "[a], [b] and [a]\n\n[a]: ./A.md\n[b]: https://example.com"
+    );
+
+    let mut callbacks = MarkdownCallbacks {
+        inline_links: true,
+        ..MarkdownCallbacks::default()
+    };
+    let mut res = String::new();
+    callbacks.encode(&mut res, pulldown_cmark::Parser::new(source).collect());
+    callbacks.finish_encoding(&mut res);
+    assert_eq!(
+        res,
+        "[a](./A.md), [b](https://example.com) and [a](./A.md)\n"
+    );
+}
+
+#[test]
+fn absolute_links() {
+    let mut callbacks = MarkdownCallbacks {
+        absolute_links: Some(String::from("https://example.com/doc/")),
+        ..MarkdownCallbacks::default()
+    };
+    let mut res = String::new();
+    callbacks.encode(
+        &mut res,
+        pulldown_cmark::Parser::new(
+            "[a](./A.md#func-x), [b](#func-y), [c](https://docs.godotengine.org)",
+        )
+        .collect(),
+    );
+    assert_eq!(
+        res,
+        "[a](https://example.com/doc/A.md#func-x), [b](#func-y), [c](https://docs.godotengine.org)"
+    );
+}
+
+#[test]
+fn anchor_style() {
+    use super::anchors::slug;
+    use crate::AnchorStyle;
+
+    let heading = "func get_cost_map(from: Vector2) -> Dictionary *async*";
+    assert_eq!(
+        slug(AnchorStyle::Github, heading),
+        "func-get_cost_mapfrom-vector2---dictionary-async"
+    );
+    assert_eq!(
+        slug(AnchorStyle::Gitlab, heading),
+        "func-get_cost_mapfrom-vector2-dictionary-async"
+    );
+    assert_eq!(slug(AnchorStyle::Mdbook, " name:\tString"), "name-string");
+}
+
+#[test]
+fn hidden_lines() {
+    use crate::backend::{apply_hidden_lines, HiddenLines};
+
+    let code = "# var map = DijkstraMap.new()\n#\nmap.clear()\nif true:\n    # map.add_point(0)\n    ## comment\n#no space\n";
+    assert_eq!(
+        apply_hidden_lines(code, HiddenLines::Hide),
+        "map.clear()\nif true:\n    # comment\n#no space\n"
+    );
+    assert_eq!(
+        apply_hidden_lines(code, HiddenLines::Show),
+        "var map = DijkstraMap.new()\n\nmap.clear()\nif true:\n    map.add_point(0)\n    # comment\n#no space\n"
+    );
+}
+
+#[test]
+fn heading_offset() {
+    use crate::backend::offset_heading_level;
+    use pulldown_cmark::HeadingLevel;
+
+    assert_eq!(offset_heading_level(HeadingLevel::H1, 0), HeadingLevel::H1);
+    assert_eq!(offset_heading_level(HeadingLevel::H1, 3), HeadingLevel::H4);
+    assert_eq!(offset_heading_level(HeadingLevel::H2, 3), HeadingLevel::H5);
+    assert_eq!(offset_heading_level(HeadingLevel::H3, 3), HeadingLevel::H6);
+    assert_eq!(
+        offset_heading_level(HeadingLevel::H2, 100),
+        HeadingLevel::H6
+    );
 }
@@ -0,0 +1,112 @@
+//! Backend generating an [mdBook](https://rust-lang.github.io/mdBook/), with the
+//! pages of the markdown backend as chapters.
+
+use super::{class_file_stem, push_escaped, Callbacks, Generator, MarkdownCallbacks};
+use pulldown_cmark::Event;
+use std::{collections::HashMap, fmt::Write as _};
+
+#[derive(Default)]
+pub(crate) struct MdBookCallbacks {
+    markdown: MarkdownCallbacks,
+}
+
+impl Callbacks for MdBookCallbacks {
+    fn extension(&self) -> &'static str {
+        "md"
+    }
+
+    fn pages_directory(&self) -> Option<&'static str> {
+        Some("src")
+    }
+
+    fn generate_files(&mut self, mut generator: Generator) -> HashMap<String, String> {
+        let title = generator.documentation.name.clone();
+        let summary = summary(&generator);
+        // mdBook does not support front matter, and needs a file per chapter
+        generator.options.markdown.front_matter = None;
+        generator.options.markdown.single_file = false;
+        let chapters = self.markdown.generate_files(generator);
+
+        let mut files: HashMap<String, String> = chapters
+            .into_iter()
+            .map(|(file_name, content)| (format!("src/{}", file_name), content))
+            .collect();
+        files.insert(String::from("src/SUMMARY.md"), summary);
+        files.insert(String::from("book.toml"), book_toml(&title));
+        files
+    }
+
+    fn encode(&mut self, s: &mut String, events: Vec<Event<'_>>) {
+        self.markdown.encode(s, events)
+    }
+}
+
+/// Content of `book.toml`.
+fn book_toml(title: &str) -> String {
+    let mut escaped = String::new();
+    push_escaped(&mut escaped, title, &['"']);
+    format!("[book]\ntitle = \"{}\"\nsrc = \"src\"\n", escaped)
+}
+
+/// Content of `SUMMARY.md`.
+///
+/// This will look like:
+/// ```text
+/// # Summary
+///
+/// [my-crate](index.md)
+///
+/// - [Guide](guide-guide.md)
+///
+/// # Modules
+///
+/// - [graph](module-graph.md)
+///
+/// # Classes
+///
+/// - [MyClass](MyClass.md)
+/// ```
+///
+/// If classes have categories, each category is a part of the book.
+fn summary(generator: &Generator) -> String {
+    fn chapter(summary: &mut String, title: &str, file_stem: &str) {
+        summary.push_str("- ");
+        link(summary, title, file_stem);
+    }
+    fn link(summary: &mut String, title: &str, file_stem: &str) {
+        summary.push('[');
+        push_escaped(summary, title, &['[', ']']);
+        let _ = writeln!(summary, "]({}.md)", file_stem);
+    }
+
+    let mut summary = String::from("# Summary\n\n");
+    link(&mut summary, &generator.documentation.name, "index");
+
+    if !generator.guides.is_empty() {
+        summary.push('\n');
+        for guide in &generator.guides {
+            chapter(&mut summary, &guide.title, &guide.file_stem);
+        }
+    }
+    if generator.options.module_pages && !generator.documentation.modules.is_empty() {
+        summary.push_str("\n# Modules\n\n");
+        for module in &generator.documentation.modules {
+            chapter(&mut summary, &module.path, &module.file_stem());
+        }
+    }
+
+    let categories = generator.class_categories();
+    let grouped = categories.iter().any(|(category, _)| category.is_some());
+    for (category, class_names) in categories {
+        let part = match category {
+            Some(category) => category,
+            None if grouped => "Other",
+            None => "Classes",
+        };
+        let _ = write!(summary, "\n# {}\n\n", part);
+        for class_name in class_names {
+            chapter(&mut summary, class_name, &class_file_stem(class_name));
+        }
+    }
+    summary
+}
@@ -2,32 +2,60 @@
 //!
 //! To implement your own backend:
 //! 1. Create a structure that represent your backend, and implement [`Callbacks`] on
-//! it.
+//!    it.
 //!
-//!     You can look in the source code of this crate to get examples of what that
-//!     would look like.
+//!    You can look in the source code of this crate to get examples of what that
+//!    would look like.
 //! 2. Add your backend to the `Builder` via the [`add_backend_with_callbacks`]
-//! method.
+//!    method.
 //!
 //! [`add_backend_with_callbacks`]: crate::Builder::add_backend_with_callbacks
 
 mod callbacks;
+mod docusaurus;
+mod github_wiki;
+mod godot_xml;
 mod gut;
 mod html;
+mod json;
 mod markdown;
+mod mdbook;
 mod resolve;
+#[cfg(test)]
+mod tests;
 
-use crate::documentation::{Documentation, GdnativeClass, Method, Property};
+#[cfg(feature = "serde")]
+use crate::cache::PageCache;
+use crate::{
+    config::Placeholders,
+    diagnostics::{self, diagnostic, Lint},
+    documentation::{self, Documentation, GdnativeClass, Method, Property},
+    AnchorStyle, ConfigFile, Error, FrontMatter, GutConfig, HtmlConfig, HtmlTheme,
+};
 use pulldown_cmark::{
-    Alignment, CowStr, Event, HeadingLevel, LinkType, Options as MarkdownOptions, Parser, Tag,
+    Alignment, CodeBlockKind, CowStr, Event, HeadingLevel, LinkType, Options as MarkdownOptions,
+    Parser, Tag,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write as _,
+    fs,
+    ops::RangeInclusive,
+    path::Path,
 };
 
+pub(super) use docusaurus::DocusaurusCallbacks;
+pub(super) use github_wiki::GithubWikiCallbacks;
+pub(super) use godot_xml::GodotXmlCallbacks;
 pub(super) use gut::GutCallbacks;
 pub(super) use html::HtmlCallbacks;
+pub(super) use json::JsonCallbacks;
 pub(super) use markdown::MarkdownCallbacks;
+pub(super) use mdbook::MdBookCallbacks;
 
 pub use callbacks::Callbacks;
-pub use resolve::Resolver;
+pub use json::JSON_SCHEMA_VERSION;
+pub use resolve::{Disambiguator, ResolveHook, Resolver};
 
 /// Generate a callback to resolve broken links.
 ///
@@ -39,10 +67,8 @@ macro_rules! broken_link_callback {
         move |broken_link: ::pulldown_cmark::BrokenLink| {
             use ::pulldown_cmark::CowStr;
 
-            let mut link: &str = &broken_link.reference;
-            if link.starts_with('`') && link.ends_with('`') && link.len() > 1 {
-                link = &link[1..link.len() - 1];
-            }
+            let link = $crate::backend::strip_backticks(&broken_link.reference).0;
+            // unresolved links are reported by `diagnostics::check_links`
             $resolver
                 .resolve(link)
                 .map(|string| (CowStr::from(string), CowStr::Borrowed("")))
@@ -50,6 +76,19 @@ macro_rules! broken_link_callback {
     };
 }
 
+/// Strip the enclosing \` of a link reference, like in ``[`Node`]``.
+///
+/// Returns the stripped link, and whether it was enclosed in \`.
+pub(crate) fn strip_backticks(reference: &str) -> (&str, bool) {
+    match reference
+        .strip_prefix('`')
+        .and_then(|link| link.strip_suffix('`'))
+    {
+        Some(link) => (link, true),
+        None => (reference, false),
+    }
+}
+
 /// Backend already implemented by this library.
 ///
 /// This must be used in the [`Builder::add_backend`] method.
@@ -100,6 +139,76 @@ pub enum BuiltinBackend {
     ///     assert_eq(x, 0)
     /// ```
     Gut,
+    /// Godot XML backend
+    ///
+    /// This generates a file for every structure that implements `NativeClass`, in
+    /// the XML format used by godot's built-in help (like the `doc_classes`
+    /// directory of godot modules). Descriptions are converted to BBCode.
+    GodotXml,
+    /// JSON backend
+    ///
+    /// This dumps the documentation, after classes are renamed, so that other tools
+    /// can use it without parsing Rust. It generates:
+    /// - `index.json`, with the crate's information, its root and module
+    ///   documentation, and the list of classes (`name`, `inherit`, `summary` and
+    ///   the `file` describing the class).
+    /// - A file for every structure that implements `NativeClass`, with its
+    ///   properties, methods, parameters and types.
+    ///
+    /// Documentation is kept as markdown. Every file has a `schema_version` field,
+    /// equal to [`JSON_SCHEMA_VERSION`]. Fields are always present, with `null`
+    /// for missing values.
+    Json,
+    /// mdBook backend
+    ///
+    /// This generates an [mdBook](https://rust-lang.github.io/mdBook/) that can be
+    /// built with `mdbook build` in the output directory: a `book.toml` file, and
+    /// in `src/`, the files of the [markdown](Self::Markdown) backend with a
+    /// `SUMMARY.md` listing them.
+    MdBook,
+    /// Docusaurus backend
+    ///
+    /// This generates the pages of the [markdown](Self::Markdown) backend as
+    /// `.mdx` files for a [Docusaurus](https://docusaurus.io/) site, with a front
+    /// matter (`id`, `title` and `sidebar_position`), and a `sidebar.js` file
+    /// that can be included in the site's `sidebars.js`.
+    Docusaurus,
+    /// GitHub wiki backend
+    ///
+    /// This generates the pages of the [markdown](Self::Markdown) backend for a
+    /// GitHub wiki: the index is `Home.md`, links to other pages have no
+    /// extension, and a `_Sidebar.md` file lists the pages.
+    GithubWiki,
+}
+
+impl BuiltinBackend {
+    /// Name of the backend in the configuration file, like `github_wiki`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Markdown => "markdown",
+            Self::Html => "html",
+            Self::Gut => "gut",
+            Self::GodotXml => "godot_xml",
+            Self::Json => "json",
+            Self::MdBook => "mdbook",
+            Self::Docusaurus => "docusaurus",
+            Self::GithubWiki => "github_wiki",
+        }
+    }
+}
+
+/// Treatment of the hidden lines of gdscript code blocks.
+///
+/// See [`ConfigFile::hidden_lines`](crate::ConfigFile::hidden_lines).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HiddenLines {
+    /// Lines starting with `#` are regular comments.
+    #[default]
+    Disabled,
+    /// Hidden lines are removed, as in the documentation.
+    Hide,
+    /// Hidden lines are kept without their `# ` prefix, as in the tests.
+    Show,
 }
 
 /// Holds the information necessary to generate the output files.
@@ -111,36 +220,356 @@ pub struct Generator<'a> {
     pub resolver: &'a Resolver,
     /// Holds the crate's documentation.
     pub documentation: &'a Documentation,
-    /// Enabled markdown options
+    /// Options of the generated files.
+    pub options: GeneratorOptions,
+    /// Guide pages split from the root documentation.
+    ///
+    /// This is empty unless [`ConfigFile::guide_pages`](crate::ConfigFile::guide_pages)
+    /// is enabled.
+    pub guides: Vec<Guide<'a>>,
+    /// Part of the root documentation that goes in the index file.
+    index_documentation: &'a str,
+    /// Classes encoded by the last build, see [`map_classes`](Self::map_classes).
+    #[cfg(feature = "serde")]
+    pub(crate) page_cache: Option<&'a PageCache>,
+}
+
+/// Options of a [`Generator`], read from the [configuration](crate::ConfigFile).
+#[derive(Clone, Debug)]
+pub struct GeneratorOptions {
+    /// Enabled markdown options.
+    ///
+    /// See [`ConfigFile::markdown_options`](crate::ConfigFile::markdown_options)
     pub markdown_options: MarkdownOptions,
     /// Control if an opening comment with meta-information should ba added to
     /// generated files.
     ///
     /// See [`ConfigFile::opening_comment`](crate::ConfigFile::opening_comment)
     pub opening_comment: bool,
+    /// Split the root documentation into guide pages.
+    ///
+    /// See [`ConfigFile::guide_pages`](crate::ConfigFile::guide_pages)
+    pub guide_pages: bool,
+    /// Generate a page for each documented submodule.
+    ///
+    /// See [`ConfigFile::module_pages`](crate::ConfigFile::module_pages)
+    pub module_pages: bool,
+    /// Base url for source links.
+    ///
+    /// See [`ConfigFile::repository_url`](crate::ConfigFile::repository_url)
+    pub repository_url: Option<String>,
+    /// Generate the editor registration snippet of renamed classes.
+    ///
+    /// See [`ConfigFile::editor_metadata`](crate::ConfigFile::editor_metadata)
+    pub editor_metadata: bool,
+    /// Editor icons of the classes, by Rust name.
+    ///
+    /// See [`ConfigFile::class_icons`](crate::ConfigFile::class_icons)
+    pub class_icons: HashMap<String, String>,
+    /// Categories of the classes, by Rust name.
+    ///
+    /// See [`ConfigFile::categories`](crate::ConfigFile::categories)
+    pub categories: HashMap<String, String>,
+    /// Number of levels by which the headings of the documentation are shifted.
+    ///
+    /// See [`MarkdownConfig::heading_offset`](crate::MarkdownConfig::heading_offset)
+    /// and [`HtmlConfig::heading_offset`](crate::HtmlConfig::heading_offset)
+    pub heading_offset: usize,
+    /// Insert a table of contents at the top of each class page.
+    ///
+    /// See [`MarkdownConfig::table_of_contents`](crate::MarkdownConfig::table_of_contents)
+    pub table_of_contents: bool,
+    /// Options of the markdown backends.
+    pub markdown: MarkdownBackendOptions,
+    /// Options of the html backend.
+    pub html: HtmlBackendOptions,
+    /// Options of the gut tests.
+    ///
+    /// See [`ConfigFile::gut`](crate::ConfigFile::gut)
+    pub gut: GutConfig,
+    /// Treatment of the hidden lines of gdscript code blocks.
+    ///
+    /// See [`ConfigFile::hidden_lines`](crate::ConfigFile::hidden_lines)
+    pub hidden_lines: HiddenLines,
+    /// Base URL of the generated pages, to make the links between them absolute.
+    ///
+    /// See [`ConfigFile::absolute_links`](crate::ConfigFile::absolute_links)
+    pub absolute_links: Option<String>,
+}
+
+/// Options of the markdown backends, see [`GeneratorOptions::markdown`].
+#[derive(Clone, Debug, Default)]
+pub struct MarkdownBackendOptions {
+    /// Front matter of the markdown files, if any.
+    ///
+    /// See [`MarkdownConfig::front_matter`](crate::MarkdownConfig::front_matter)
+    pub front_matter: Option<FrontMatter>,
+    /// Generate all the pages in a single file.
+    ///
+    /// See [`MarkdownConfig::single_file`](crate::MarkdownConfig::single_file)
+    pub single_file: bool,
+    /// Write links as inline links.
+    ///
+    /// See [`MarkdownConfig::inline_links`](crate::MarkdownConfig::inline_links)
+    pub inline_links: bool,
+    /// Anchors of the headings.
+    ///
+    /// See [`MarkdownConfig::anchor_style`](crate::MarkdownConfig::anchor_style)
+    pub anchor_style: AnchorStyle,
+}
+
+/// Options of the html backend, see [`GeneratorOptions::html`].
+#[derive(Clone, Debug, Default)]
+pub struct HtmlBackendOptions {
+    /// Options of the website.
+    ///
+    /// See [`ConfigFile::html`](crate::ConfigFile::html)
+    pub config: HtmlConfig,
+    /// Default theme.
+    ///
+    /// See [`HtmlConfig::theme`](crate::HtmlConfig::theme)
+    pub theme: HtmlTheme,
+    /// Show line numbers in code blocks.
+    ///
+    /// See [`HtmlConfig::line_numbers`](crate::HtmlConfig::line_numbers)
+    pub line_numbers: bool,
+    /// Content of a CSS file included in the pages.
+    ///
+    /// See [`HtmlConfig::extra_css`](crate::HtmlConfig::extra_css)
+    pub extra_css: Option<String>,
+    /// Sources of the templates overriding the default ones, by name.
+    ///
+    /// See [`HtmlConfig::templates`](crate::HtmlConfig::templates)
+    pub templates: HashMap<String, String>,
+}
+
+/// A guide page, split from the root documentation.
+///
+/// See [`ConfigFile::guide_pages`](crate::ConfigFile::guide_pages).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Guide<'a> {
+    /// Title of the guide (the text of its level 1 heading).
+    pub title: String,
+    /// Name of the generated file, without extension.
+    pub file_stem: String,
+    /// Markdown content of the guide, excluding the title.
+    pub content: &'a str,
+}
+
+/// Result of encoding a class with [`Generator::map_classes`].
+///
+/// With the `serde` feature, it is stored in the cache of
+/// [`Builder::incremental`](crate::Builder::incremental).
+#[cfg(feature = "serde")]
+pub(crate) trait Encoded: Send + serde::Serialize + serde::de::DeserializeOwned {}
+#[cfg(feature = "serde")]
+impl<T: Send + serde::Serialize + serde::de::DeserializeOwned> Encoded for T {}
+#[cfg(not(feature = "serde"))]
+pub(crate) trait Encoded: Send {}
+#[cfg(not(feature = "serde"))]
+impl<T: Send> Encoded for T {}
+
+/// A page generated by the documentation backends, see [`Generator::pages`].
+struct Page {
+    /// Name of the generated file, without extension.
+    file_stem: String,
+    title: String,
+    /// Category of the class documented by the page, if any.
+    category: Option<String>,
+}
+
+impl Default for GeneratorOptions {
+    fn default() -> Self {
+        Self {
+            markdown_options: MarkdownOptions::empty(),
+            opening_comment: true,
+            guide_pages: false,
+            module_pages: false,
+            repository_url: None,
+            editor_metadata: false,
+            class_icons: HashMap::new(),
+            categories: HashMap::new(),
+            heading_offset: 3,
+            table_of_contents: false,
+            markdown: MarkdownBackendOptions::default(),
+            html: HtmlBackendOptions::default(),
+            gut: GutConfig::default(),
+            hidden_lines: HiddenLines::Disabled,
+            absolute_links: None,
+        }
+    }
+}
+
+impl GeneratorOptions {
+    /// Options given by `config`, shared by all the backends.
+    ///
+    /// The `heading_offset` and `table_of_contents` of the `[markdown]` table
+    /// are used by every backend, except for the `heading_offset` of the html
+    /// backend.
+    ///
+    /// This reads the files given in [`HtmlConfig::extra_css`] and
+    /// [`HtmlConfig::templates`].
+    pub(crate) fn from_config(config: &ConfigFile) -> Result<Self, Error> {
+        let markdown = config.markdown.clone().unwrap_or_default();
+        let html = config.html.clone().unwrap_or_default();
+        let extra_css = match &html.extra_css {
+            Some(path) => match fs::read_to_string(path) {
+                Ok(css) => Some(css),
+                Err(err) => return Err(Error::Io(path.clone(), err)),
+            },
+            None => None,
+        };
+        let templates = match &html.templates {
+            Some(directory) => html::load_templates(directory)?,
+            None => HashMap::new(),
+        };
+        Ok(Self {
+            markdown_options: config
+                .markdown_options()
+                .unwrap_or(MarkdownOptions::empty()),
+            opening_comment: config.opening_comment.unwrap_or(true),
+            guide_pages: config.guide_pages.unwrap_or(false),
+            module_pages: config.module_pages.unwrap_or(false),
+            repository_url: config.repository_url.clone(),
+            editor_metadata: config.editor_metadata.unwrap_or(false),
+            class_icons: config.class_icons.clone().unwrap_or_default(),
+            categories: config.categories.clone().unwrap_or_default(),
+            heading_offset: markdown.heading_offset.unwrap_or(3),
+            table_of_contents: markdown.table_of_contents.unwrap_or(false),
+            markdown: MarkdownBackendOptions {
+                front_matter: markdown.front_matter,
+                single_file: markdown.single_file.unwrap_or(false),
+                inline_links: markdown.inline_links.unwrap_or(false),
+                anchor_style: markdown.anchor_style.unwrap_or_default(),
+            },
+            html: HtmlBackendOptions {
+                theme: html.theme.unwrap_or_default(),
+                line_numbers: html.line_numbers.unwrap_or(false),
+                extra_css,
+                templates,
+                config: html,
+            },
+            gut: config.gut.clone().unwrap_or_default(),
+            hidden_lines: if config.hidden_lines.unwrap_or(false) {
+                HiddenLines::Hide
+            } else {
+                HiddenLines::Disabled
+            },
+            absolute_links: None,
+        })
+    }
+
+    /// Options of `backend`, where the `opening_comment` of the `[markdown]`,
+    /// `[html]` and `[gut]` tables, the `heading_offset` of the `[html]` table and
+    /// [`ConfigFile::absolute_links`] of `config` override the shared options.
+    pub(crate) fn for_backend(&self, backend: Option<BuiltinBackend>, config: &ConfigFile) -> Self {
+        let mut options = self.clone();
+        let opening_comment = match backend {
+            Some(BuiltinBackend::Markdown) => {
+                (config.markdown.as_ref()).and_then(|markdown| markdown.opening_comment)
+            }
+            Some(BuiltinBackend::Html) => {
+                options.heading_offset = self.html.config.heading_offset.unwrap_or(3);
+                self.html.config.opening_comment
+            }
+            Some(BuiltinBackend::Gut) => self.gut.opening_comment,
+            _ => None,
+        };
+        options.opening_comment = opening_comment.unwrap_or(self.opening_comment);
+        options.absolute_links = backend.and_then(|backend| {
+            let absolute_links = config.absolute_links.as_ref()?;
+            absolute_links.get(backend.name()).cloned()
+        });
+        options
+    }
+
+    /// Replace the `{crate_name}` and `{crate_version}` placeholders in the URLs.
+    pub(crate) fn replace_placeholders(&mut self, placeholders: &Placeholders) {
+        let urls = [
+            &mut self.repository_url,
+            &mut self.html.config.base_url,
+            &mut self.html.config.path_prefix,
+            &mut self.html.config.logo,
+            &mut self.absolute_links,
+        ];
+        for url in urls.into_iter().flatten() {
+            *url = placeholders.replace(url);
+        }
+    }
 }
 
 impl<'a> Generator<'a> {
     pub(crate) fn new(
         resolver: &'a Resolver,
         documentation: &'a Documentation,
-        markdown_options: MarkdownOptions,
-        opening_comment: bool,
+        options: GeneratorOptions,
     ) -> Self {
+        let (index_documentation, guides) = if options.guide_pages {
+            split_guides(documentation, options.markdown_options)
+        } else {
+            (documentation.root_documentation.as_str(), Vec::new())
+        };
         Self {
             resolver,
             documentation,
-            markdown_options,
-            opening_comment,
+            options,
+            guides,
+            index_documentation,
+            #[cfg(feature = "serde")]
+            page_cache: None,
         }
     }
 
+    /// Copy of this generator, resolving links with `resolver`.
+    pub(crate) fn with_resolver<'b>(&self, resolver: &'b Resolver) -> Generator<'b>
+    where
+        'a: 'b,
+    {
+        Generator {
+            resolver,
+            documentation: self.documentation,
+            options: self.options.clone(),
+            guides: self.guides.clone(),
+            index_documentation: self.index_documentation,
+            #[cfg(feature = "serde")]
+            page_cache: self.page_cache,
+        }
+    }
+
+    /// Returns the link to `line` in `file`, if
+    /// [`repository_url`](GeneratorOptions::repository_url) is set.
+    ///
+    /// `file` must be in the directory of the root file.
+    pub fn source_link(&self, file: &Path, line: usize) -> Option<String> {
+        let repository_url = self.options.repository_url.as_deref()?;
+        if line == 0 {
+            return None;
+        }
+        let root_dir = self.documentation.root_file.parent()?;
+        let relative_path = file.strip_prefix(root_dir).ok()?;
+        let mut link = repository_url.trim_end_matches('/').to_string();
+        for component in relative_path.components() {
+            link.push('/');
+            link.push_str(&component.as_os_str().to_string_lossy());
+        }
+        let _ = write!(link, "#L{}", line);
+        Some(link)
+    }
+
     /// Generate the root documentation file of the crate.
     ///
     /// The following will be generated (in markdown style):
     /// ```text
     /// <crate documentation>
     ///
+    /// # Guides:
+    ///
+    /// <list of guides, if any>
+    ///
+    /// # Modules:
+    ///
+    /// <list of documented modules, if module pages are enabled>
+    ///
     /// # Classes:
     ///
     /// <list of GDNative classes>
@@ -150,207 +579,956 @@ impl<'a> Generator<'a> {
     pub fn generate_root_file(&self, extension: &str, callbacks: &mut dyn Callbacks) -> String {
         let resolver = self.resolver;
         let mut broken_link_callback = broken_link_callback!(resolver);
-        let class_iterator = EventIterator {
-            context: resolver,
-            parser: pulldown_cmark::Parser::new_with_broken_link_callback(
-                &self.documentation.root_documentation,
-                self.markdown_options,
+        let class_iterator = EventIterator::new(
+            resolver,
+            self.options.heading_offset,
+            self.options.hidden_lines,
+            pulldown_cmark::Parser::new_with_broken_link_callback(
+                self.index_documentation,
+                self.options.markdown_options,
                 Some(&mut broken_link_callback),
             ),
-        };
-        let mut events: Vec<_> = class_iterator.into_iter().collect();
+        );
+        let index_events: Vec<_> = class_iterator.collect();
+        let ids = heading_ids(&index_events, "");
+        let mut events = set_heading_ids(index_events, &ids);
+        if !self.guides.is_empty() {
+            events.extend(vec![
+                Event::Start(Tag::Heading(HeadingLevel::H1, None, Vec::new())),
+                Event::Text(CowStr::Borrowed("Guides:")),
+                Event::End(Tag::Heading(HeadingLevel::H1, None, Vec::new())),
+                Event::Start(Tag::List(None)),
+            ]);
+            for guide in &self.guides {
+                let link = Tag::Link(
+                    LinkType::Inline,
+                    format!("./{}.{}", guide.file_stem, extension).into(),
+                    CowStr::Borrowed(""),
+                );
+                events.extend(vec![
+                    Event::Start(Tag::Item),
+                    Event::Start(link.clone()),
+                    Event::Text(CowStr::Borrowed(&guide.title)),
+                    Event::End(link),
+                    Event::End(Tag::Item),
+                ])
+            }
+            events.push(Event::End(Tag::List(None)));
+        }
+        if self.options.module_pages && !self.documentation.modules.is_empty() {
+            events.extend(vec![
+                Event::Start(Tag::Heading(HeadingLevel::H1, None, Vec::new())),
+                Event::Text(CowStr::Borrowed("Modules:")),
+                Event::End(Tag::Heading(HeadingLevel::H1, None, Vec::new())),
+                Event::Start(Tag::List(None)),
+            ]);
+            for module in &self.documentation.modules {
+                let link = Tag::Link(
+                    LinkType::Inline,
+                    format!("./{}.{}", module.file_stem(), extension).into(),
+                    CowStr::Borrowed(""),
+                );
+                events.extend(vec![
+                    Event::Start(Tag::Item),
+                    Event::Start(link.clone()),
+                    Event::Code(CowStr::Borrowed(&module.path)),
+                    Event::End(link),
+                    Event::End(Tag::Item),
+                ])
+            }
+            events.push(Event::End(Tag::List(None)));
+        }
         events.extend(vec![
             Event::Start(Tag::Heading(HeadingLevel::H1, None, Vec::new())),
             Event::Text(CowStr::Borrowed("Classes:")),
             Event::End(Tag::Heading(HeadingLevel::H1, None, Vec::new())),
-            Event::Start(Tag::List(None)),
         ]);
-        for class_name in self.documentation.classes.keys() {
-            let link = Tag::Link(
-                LinkType::Inline,
-                format!("./{}.{}", class_name, extension).into(),
-                CowStr::Borrowed(""),
-            );
-            events.extend(vec![
-                Event::Start(Tag::Item),
-                Event::Start(link.clone()),
-                Event::Text(CowStr::Borrowed(class_name)),
-                Event::End(link.clone()),
-                Event::End(Tag::Item),
-            ])
+        let categories = self.class_categories();
+        // without categories, this is a simple list
+        let grouped = categories.iter().any(|(category, _)| category.is_some());
+        for (category, class_names) in categories {
+            if grouped {
+                events.extend(vec![
+                    Event::Start(Tag::Heading(HeadingLevel::H2, None, Vec::new())),
+                    Event::Text(CowStr::Borrowed(category.unwrap_or("Other"))),
+                    Event::End(Tag::Heading(HeadingLevel::H2, None, Vec::new())),
+                ]);
+            }
+            events.push(Event::Start(Tag::List(None)));
+            for class_name in class_names {
+                let link = Tag::Link(
+                    LinkType::Inline,
+                    format!("./{}.{}", class_file_stem(class_name), extension).into(),
+                    CowStr::Borrowed(""),
+                );
+                events.extend(vec![
+                    Event::Start(Tag::Item),
+                    Event::Start(link.clone()),
+                    Event::Text(CowStr::Borrowed(class_name)),
+                    Event::End(link.clone()),
+                    Event::End(Tag::Item),
+                ])
+            }
+            events.push(Event::End(Tag::List(None)));
         }
-        events.push(Event::End(Tag::List(None)));
         let mut root_file = String::new();
         callbacks.encode(&mut root_file, events);
         root_file
     }
 
-    /// Generate the documentation for a class.
+    /// Generate the footer of the root documentation file, identifying the crate
+    /// release.
     ///
     /// The following will be generated (in markdown style):
     /// ```text
-    /// # <class name>
+    /// ________
     ///
-    /// **Inherit:** <inherited class>
-    ///
-    /// ## Description
-    ///
-    /// <class documentation>
+    /// <crate> <version> · License: <license> · [Repository](<link>) · [Homepage](<link>)
+    /// ```
     ///
-    /// ## Properties
+    /// This is empty if the [crate metadata](documentation::CrateMetadata) is not
+    /// known.
     ///
-    /// <table of class properties>
+    /// This then uses [`Callbacks::encode`] to encode this in the target format.
+    pub fn generate_root_footer(&self, callbacks: &mut dyn Callbacks) -> String {
+        let mut footer = String::new();
+        let metadata = &self.documentation.metadata;
+        if metadata.is_empty() {
+            return footer;
+        }
+        let mut events = vec![
+            Event::Rule,
+            Event::Start(Tag::Paragraph),
+            Event::Text(self.documentation.name_and_version().into()),
+        ];
+        if let Some(license) = &metadata.license {
+            events.push(Event::Text(format!(" · License: {}", license).into()));
+        }
+        for (text, link) in [
+            ("Repository", &metadata.repository),
+            ("Homepage", &metadata.homepage),
+        ] {
+            if let Some(link) = link {
+                let link = Tag::Link(
+                    LinkType::Inline,
+                    CowStr::Borrowed(link),
+                    CowStr::Borrowed(""),
+                );
+                events.extend(vec![
+                    Event::Text(CowStr::Borrowed(" · ")),
+                    Event::Start(link.clone()),
+                    Event::Text(CowStr::Borrowed(text)),
+                    Event::End(link),
+                ]);
+            }
+        }
+        events.push(Event::End(Tag::Paragraph));
+        callbacks.encode(&mut footer, events);
+        footer
+    }
+
+    /// Returns the category of the class `name`.
     ///
-    /// ## Methods
+    /// This is either set in [`categories`](Self::categories), or with an
+    /// `@category` tag in the documentation of the class.
+    pub fn class_category(&self, name: &str) -> Option<&str> {
+        let class = self.documentation.classes.get(name)?;
+        match self.options.categories.get(&class.name) {
+            Some(category) => Some(category),
+            None => class
+                .tags
+                .iter()
+                .find(|tag| tag.name == "category")
+                .map(|tag| tag.value.as_str()),
+        }
+    }
+
+    /// Sort the classes by category, and then by name.
     ///
-    /// <table of class methods>
+    /// Classes without a category come last.
+    pub(crate) fn class_categories(&self) -> Vec<(Option<&str>, Vec<&str>)> {
+        let mut categories: BTreeMap<(bool, Option<&str>), Vec<&str>> = BTreeMap::new();
+        for name in self.documentation.classes.keys() {
+            let category = self.class_category(name);
+            categories
+                .entry((category.is_none(), category))
+                .or_default()
+                .push(name);
+        }
+        categories
+            .into_iter()
+            .map(|((_, category), mut names)| {
+                names.sort_unstable();
+                (category, names)
+            })
+            .collect()
+    }
+
+    /// Generated pages, in the order of the index: the index, guides, modules
+    /// (if [`module_pages`](GeneratorOptions::module_pages) is set), and then classes.
+    fn pages(&self) -> Vec<Page> {
+        let page = |file_stem: String, title: &str| Page {
+            file_stem,
+            title: title.to_string(),
+            category: None,
+        };
+        let mut pages = vec![page(String::from("index"), &self.documentation.name)];
+        for guide in &self.guides {
+            pages.push(page(guide.file_stem.clone(), &guide.title));
+        }
+        if self.options.module_pages {
+            for module in &self.documentation.modules {
+                pages.push(page(module.file_stem(), &module.path));
+            }
+        }
+        for (category, class_names) in self.class_categories() {
+            for class_name in class_names {
+                pages.push(Page {
+                    category: category.map(String::from),
+                    ..page(class_file_stem(class_name), class_name)
+                });
+            }
+        }
+        pages
+    }
+
+    /// Generate the page for the guide at `index` in [`guides`](Self::guides).
     ///
-    /// ## Properties Descriptions
+    /// The following will be generated (in markdown style):
+    /// ```text
+    /// # <guide title>
     ///
-    /// <list of the class properties with their documentation>
+    /// <guide content>
     ///
-    /// ## Methods Descriptions
+    /// ________
     ///
-    /// <list of the class methods with their documentation>
+    /// [Previous: <title>](<link>) | [Index](<link>) | [Next: <title>](<link>)
     /// ```
     ///
     /// This then uses [`Callbacks::encode`] to encode this in the target format.
-    pub fn generate_file(
+    pub fn generate_guide_file(
         &self,
-        name: &str,
-        class: &GdnativeClass,
+        index: usize,
+        extension: &str,
         callbacks: &mut dyn Callbacks,
     ) -> String {
-        let mut class_file = String::new();
-        let resolver = &self.resolver;
-
-        let inherit_link = resolver.resolve(&class.inherit);
-
-        // Name of the class + inherit
+        let guide = &self.guides[index];
+        let resolver = self.resolver;
         let mut events = vec![
             Event::Start(Tag::Heading(HeadingLevel::H1, None, Vec::new())),
-            Event::Text(CowStr::Borrowed(name)),
+            Event::Text(CowStr::Borrowed(&guide.title)),
             Event::End(Tag::Heading(HeadingLevel::H1, None, Vec::new())),
-            Event::Start(Tag::Paragraph),
-            Event::Start(Tag::Strong),
-            Event::Text(CowStr::Borrowed("Inherit:")),
-            Event::End(Tag::Strong),
-            Event::Text(CowStr::Borrowed(" ")),
         ];
-        if let Some(inherit_link) = inherit_link.as_ref() {
-            let link = Tag::Link(
-                LinkType::Shortcut,
-                CowStr::Borrowed(inherit_link),
-                CowStr::Borrowed(""),
-            );
-            events.extend(vec![
-                Event::Start(link.clone()),
-                Event::Text(CowStr::Borrowed(&class.inherit)),
-                Event::End(link),
-            ])
-        } else {
-            events.push(Event::Text(CowStr::Borrowed(&class.inherit)))
-        }
-        events.extend(vec![
-            Event::End(Tag::Paragraph),
-            Event::Start(Tag::Heading(HeadingLevel::H2, None, Vec::new())),
-            Event::Text(CowStr::Borrowed("Description")),
-            Event::End(Tag::Heading(HeadingLevel::H2, None, Vec::new())),
-        ]);
-        callbacks.encode(&mut class_file, events);
-
-        // Class description
         let mut broken_link_callback = broken_link_callback!(resolver);
-        let class_documentation = EventIterator {
-            context: resolver,
-            parser: pulldown_cmark::Parser::new_with_broken_link_callback(
-                &class.documentation,
-                self.markdown_options,
+        let content: Vec<_> = EventIterator::new(
+            resolver,
+            self.options.heading_offset,
+            self.options.hidden_lines,
+            pulldown_cmark::Parser::new_with_broken_link_callback(
+                guide.content,
+                self.options.markdown_options,
                 Some(&mut broken_link_callback),
             ),
-        }
-        .into_iter()
+        )
         .collect();
-        callbacks.encode(&mut class_file, class_documentation);
+        let ids = heading_ids(&content, "");
+        events.extend(set_heading_ids(content, &ids));
 
-        // Properties table
-        if !class.properties.is_empty() {
-            callbacks.encode(
-                &mut class_file,
-                Self::properties_table(&class.properties, resolver),
-            )
+        // Navigation
+        let mut navigation = Vec::new();
+        if let Some(previous) = index.checked_sub(1).map(|index| &self.guides[index]) {
+            navigation.push((
+                format!("Previous: {}", previous.title),
+                format!("./{}.{}", previous.file_stem, extension),
+            ));
         }
-
-        // Methods table
-        callbacks.encode(
-            &mut class_file,
-            Self::methods_table(&class.methods, resolver),
-        );
-
-        // Properties descriptions
-        if !class.properties.is_empty() {
-            callbacks.encode(
-                &mut class_file,
-                vec![
-                    Event::Start(Tag::Heading(HeadingLevel::H2, None, Vec::new())),
-                    Event::Text(CowStr::Borrowed("Properties Descriptions")),
-                    Event::End(Tag::Heading(HeadingLevel::H2, None, Vec::new())),
-                ],
-            );
-            for property in &class.properties {
-                callbacks.start_property(&mut class_file, resolver, property);
-                let mut broken_link_callback = broken_link_callback!(resolver);
-                let property_documentation = EventIterator {
-                    context: resolver,
-                    parser: pulldown_cmark::Parser::new_with_broken_link_callback(
-                        &property.documentation,
-                        self.markdown_options,
-                        Some(&mut broken_link_callback),
-                    ),
-                }
-                .into_iter()
-                .collect();
-                callbacks.encode(&mut class_file, property_documentation);
+        navigation.push((String::from("Index"), format!("./index.{}", extension)));
+        if let Some(next) = self.guides.get(index + 1) {
+            navigation.push((
+                format!("Next: {}", next.title),
+                format!("./{}.{}", next.file_stem, extension),
+            ));
+        }
+        events.extend(vec![Event::Rule, Event::Start(Tag::Paragraph)]);
+        for (index, (text, dest)) in navigation.into_iter().enumerate() {
+            if index > 0 {
+                events.push(Event::Text(CowStr::Borrowed(" | ")));
             }
+            let link = Tag::Link(LinkType::Inline, dest.into(), CowStr::Borrowed(""));
+            events.extend(vec![
+                Event::Start(link.clone()),
+                Event::Text(text.into()),
+                Event::End(link),
+            ]);
         }
+        events.push(Event::End(Tag::Paragraph));
 
-        // Methods descriptions
+        let mut guide_file = String::new();
+        callbacks.encode(&mut guide_file, events);
+        guide_file
+    }
+
+    /// Generate the page of a documented submodule.
+    ///
+    /// The following will be generated (in markdown style):
+    /// ```text
+    /// # Module `<module path>`
+    ///
+    /// <module documentation>
+    ///
+    /// ________
+    ///
+    /// [Index](<link>)
+    /// ```
+    ///
+    /// This then uses [`Callbacks::encode`] to encode this in the target format.
+    pub fn generate_module_file(
+        &self,
+        module: &documentation::Module,
+        extension: &str,
+        callbacks: &mut dyn Callbacks,
+    ) -> String {
+        let mut module_file = String::new();
         callbacks.encode(
-            &mut class_file,
+            &mut module_file,
             vec![
-                Event::Start(Tag::Heading(HeadingLevel::H2, None, Vec::new())),
-                Event::Text(CowStr::Borrowed("Methods Descriptions")),
-                Event::End(Tag::Heading(HeadingLevel::H2, None, Vec::new())),
+                Event::Start(Tag::Heading(HeadingLevel::H1, None, Vec::new())),
+                Event::Text(CowStr::Borrowed("Module ")),
+                Event::Code(CowStr::Borrowed(&module.path)),
+                Event::End(Tag::Heading(HeadingLevel::H1, None, Vec::new())),
             ],
         );
-        for method in &class.methods {
-            callbacks.start_method(&mut class_file, resolver, method);
-            let mut broken_link_callback = broken_link_callback!(resolver);
-            let method_documentation = EventIterator {
-                context: resolver,
-                parser: pulldown_cmark::Parser::new_with_broken_link_callback(
-                    &method.documentation,
-                    self.markdown_options,
-                    Some(&mut broken_link_callback),
-                ),
-            }
-            .into_iter()
-            .collect();
-            callbacks.encode(&mut class_file, method_documentation);
-        }
-        class_file
+        self.encode_documentation(&mut module_file, &module.documentation, "module", callbacks);
+        let link = Tag::Link(
+            LinkType::Inline,
+            format!("./index.{}", extension).into(),
+            CowStr::Borrowed(""),
+        );
+        callbacks.encode(
+            &mut module_file,
+            vec![
+                Event::Rule,
+                Event::Start(Tag::Paragraph),
+                Event::Start(link.clone()),
+                Event::Text(CowStr::Borrowed("Index")),
+                Event::End(link),
+                Event::End(Tag::Paragraph),
+            ],
+        );
+        module_file
     }
 
-    /// Create a table summarizing the properties.
-    fn properties_table<'ev>(
-        properties: &'ev [Property],
-        resolver: &'ev Resolver,
-    ) -> Vec<Event<'ev>> {
-        let mut events = vec![
-            Event::Start(Tag::Heading(HeadingLevel::H2, None, Vec::new())),
-            Event::Text(CowStr::Borrowed("Properties")),
+    /// Generate an overview of the inheritance of the documented classes.
+    ///
+    /// Classes are grouped under the godot class they ultimately inherit, and
+    /// classes inheriting other documented classes are nested under them.
+    ///
+    /// The following will be generated (in markdown style):
+    /// ```text
+    /// # Inheritance
+    ///
+    /// ## <godot class> (<number of classes>)
+    ///
+    /// - <class>
+    ///   - <class inheriting it>
+    /// - ...
+    ///
+    /// [Index](<link>)
+    /// ```
+    ///
+    /// This then uses [`Callbacks::encode`] to encode this in the target format.
+    pub fn generate_inheritance_file(
+        &self,
+        extension: &str,
+        callbacks: &mut dyn Callbacks,
+    ) -> String {
+        let documentation = self.documentation;
+        // godot base class -> number of documented classes deriving from it
+        let mut bases: BTreeMap<&str, usize> = BTreeMap::new();
+        for name in documentation.classes.keys() {
+            if let Some(base) = documentation.inheritance_chain(name).last() {
+                if !documentation.classes.contains_key(*base) {
+                    *bases.entry(base).or_default() += 1;
+                }
+            }
+        }
+
+        fn class_tree<'ev>(
+            generator: &'ev Generator,
+            classes: Vec<&'ev str>,
+            extension: &str,
+            events: &mut Vec<Event<'ev>>,
+        ) {
+            events.push(Event::Start(Tag::List(None)));
+            for class in classes {
+                events.push(Event::Start(Tag::Item));
+                events.extend(generator.class_link(class, extension));
+                let children = generator.documentation.inherited_by(class);
+                if !children.is_empty() {
+                    class_tree(generator, children, extension, events);
+                }
+                events.push(Event::End(Tag::Item));
+            }
+            events.push(Event::End(Tag::List(None)));
+        }
+
+        let mut events = vec![
+            Event::Start(Tag::Heading(HeadingLevel::H1, None, Vec::new())),
+            Event::Text(CowStr::Borrowed("Inheritance")),
+            Event::End(Tag::Heading(HeadingLevel::H1, None, Vec::new())),
+        ];
+        for (base, count) in bases {
+            events.push(Event::Start(Tag::Heading(
+                HeadingLevel::H2,
+                None,
+                Vec::new(),
+            )));
+            events.extend(self.class_link(base, extension));
+            events.extend(vec![
+                Event::Text(format!(" ({})", count).into()),
+                Event::End(Tag::Heading(HeadingLevel::H2, None, Vec::new())),
+            ]);
+            class_tree(
+                self,
+                documentation.inherited_by(base),
+                extension,
+                &mut events,
+            );
+        }
+        let link = Tag::Link(
+            LinkType::Inline,
+            format!("./index.{}", extension).into(),
+            CowStr::Borrowed(""),
+        );
+        events.extend(vec![
+            Event::Rule,
+            Event::Start(Tag::Paragraph),
+            Event::Start(link.clone()),
+            Event::Text(CowStr::Borrowed("Index")),
+            Event::End(link),
+            Event::End(Tag::Paragraph),
+        ]);
+
+        let mut inheritance_file = String::new();
+        callbacks.encode(&mut inheritance_file, events);
+        inheritance_file
+    }
+
+    /// Call `encode` on each documented class, in the order of their names.
+    ///
+    /// With the `rayon` feature, the classes are encoded in parallel: their
+    /// diagnostics are still emitted in order.
+    ///
+    /// With [`Builder::incremental`](crate::Builder::incremental), the classes
+    /// that did not change since the last build are taken from the cache instead.
+    pub(crate) fn map_classes<T: Encoded>(
+        &self,
+        encode: impl Fn(&str, &GdnativeClass) -> T + Sync,
+    ) -> Vec<T> {
+        let mut classes: Vec<_> = self.documentation.classes.iter().collect();
+        classes.sort_unstable_by_key(|(name, _)| *name);
+        let encode = |(name, class): &(&String, &GdnativeClass)| {
+            #[cfg(feature = "serde")]
+            if let Some(cached) = self.page_cache.and_then(|cache| cache.get(name)) {
+                return cached;
+            }
+            let (result, emitted) = diagnostics::defer(|| encode(name, class));
+            #[cfg(feature = "serde")]
+            if let Some(cache) = self.page_cache {
+                cache.insert(name, &result, &emitted);
+            }
+            (result, emitted)
+        };
+        #[cfg(feature = "rayon")]
+        let encoded: Vec<_> = {
+            use rayon::prelude::*;
+            classes.par_iter().map(encode).collect()
+        };
+        #[cfg(not(feature = "rayon"))]
+        let encoded: Vec<_> = classes.iter().map(encode).collect();
+
+        let mut results = Vec::with_capacity(encoded.len());
+        for (result, emitted) in encoded {
+            diagnostics::emit_deferred(emitted);
+            results.push(result);
+        }
+        results
+    }
+
+    /// Generate the documentation for a class.
+    ///
+    /// The following will be generated (in markdown style):
+    /// ```text
+    /// # <class name>
+    ///
+    /// **Inherit:** <inherited class> < <its own parent> < ...
+    ///
+    /// **Inherited By:** <documented classes inheriting this one>
+    ///
+    /// ## Description
+    ///
+    /// <class documentation>
+    ///
+    /// ## Properties
+    ///
+    /// <table of class properties, with their default value and summary>
+    ///
+    /// ## Constructors
+    ///
+    /// <table of class constructors, with their summary>
+    ///
+    /// ## Methods
+    ///
+    /// <table of class methods, with their summary>
+    ///
+    /// ## Properties Descriptions
+    ///
+    /// <list of the class properties with their documentation>
+    ///
+    /// ## Constructors Descriptions
+    ///
+    /// <list of the class constructors with their documentation>
+    ///
+    /// ## Methods Descriptions
+    ///
+    /// <list of the class methods with their documentation>
+    ///
+    /// ## Referenced By
+    ///
+    /// <list of methods of other classes that mention this class, if any>
+    /// ```
+    ///
+    /// This then uses [`Callbacks::encode`] to encode this in the target format.
+    pub fn generate_file(
+        &self,
+        name: &str,
+        class: &GdnativeClass,
+        callbacks: &mut dyn Callbacks,
+    ) -> String {
+        // `Self::member` links resolve to the members of this class
+        let resolver = self.resolver.for_class(name);
+        self.with_resolver(&resolver)
+            .class_file(name, class, callbacks)
+    }
+
+    /// Body of [`generate_file`](Self::generate_file), with a resolver for the
+    /// class `name`.
+    fn class_file(
+        &self,
+        name: &str,
+        class: &GdnativeClass,
+        callbacks: &mut dyn Callbacks,
+    ) -> String {
+        let mut class_file = String::new();
+        let resolver = &self.resolver;
+        let extension = callbacks.extension();
+
+        // Name of the class (with a badge for tool classes) + inherit
+        let mut events = vec![
+            Event::Start(Tag::Heading(HeadingLevel::H1, None, Vec::new())),
+            Event::Text(CowStr::Borrowed(name)),
+        ];
+        if class.is_tool {
+            events.extend(vec![
+                Event::Text(CowStr::Borrowed(" ")),
+                Event::Code(CowStr::Borrowed("tool")),
+            ]);
+        }
+        events.extend(vec![
+            Event::End(Tag::Heading(HeadingLevel::H1, None, Vec::new())),
+            Event::Start(Tag::Paragraph),
+            Event::Start(Tag::Strong),
+            Event::Text(CowStr::Borrowed("Inherit:")),
+            Event::End(Tag::Strong),
+            Event::Text(CowStr::Borrowed(" ")),
+        ]);
+        for (index, parent) in self
+            .documentation
+            .inheritance_chain(name)
+            .into_iter()
+            .enumerate()
+        {
+            if index > 0 {
+                events.push(Event::Text(CowStr::Borrowed(" < ")));
+            }
+            events.extend(self.class_link(parent, extension));
+        }
+        events.push(Event::End(Tag::Paragraph));
+        let inherited_by = self.documentation.inherited_by(name);
+        if !inherited_by.is_empty() {
+            events.extend(vec![
+                Event::Start(Tag::Paragraph),
+                Event::Start(Tag::Strong),
+                Event::Text(CowStr::Borrowed("Inherited By:")),
+                Event::End(Tag::Strong),
+                Event::Text(CowStr::Borrowed(" ")),
+            ]);
+            for (index, child) in inherited_by.into_iter().enumerate() {
+                if index > 0 {
+                    events.push(Event::Text(CowStr::Borrowed(", ")));
+                }
+                events.extend(self.class_link(child, extension));
+            }
+            events.push(Event::End(Tag::Paragraph));
+        }
+        if let Some(link) = self.source_link(&class.file, class.line) {
+            events.extend(source_link(link));
+        }
+        if let Some(note) = &class.deprecated {
+            events.extend(deprecation_notice(
+                note,
+                resolver,
+                self.options.markdown_options,
+            ));
+        }
+        let (constructors, methods): (Vec<_>, Vec<_>) = class
+            .methods
+            .iter()
+            .partition(|method| method.is_constructor);
+        // ids of the sections, for the table of contents
+        let section_id = |id: &'static str| self.options.table_of_contents.then_some(id);
+        if self.options.table_of_contents {
+            events.extend(table_of_contents(class, &constructors, &methods));
+        }
+        events.extend(vec![
+            Event::Start(Tag::Heading(
+                HeadingLevel::H2,
+                section_id("description"),
+                Vec::new(),
+            )),
+            Event::Text(CowStr::Borrowed("Description")),
+            Event::End(Tag::Heading(
+                HeadingLevel::H2,
+                section_id("description"),
+                Vec::new(),
+            )),
+        ]);
+        callbacks.encode(&mut class_file, events);
+
+        // Class description
+        self.encode_documentation(
+            &mut class_file,
+            &class.documentation,
+            "description",
+            callbacks,
+        );
+        callbacks.encode(
+            &mut class_file,
+            tags_list(&class.tags, resolver, self.options.markdown_options),
+        );
+
+        // Editor registration
+        if let Some(events) = self.editor_registration(class) {
+            callbacks.encode(&mut class_file, events);
+        }
+
+        // Properties table
+        if !class.properties.is_empty() {
+            let mut events =
+                Self::properties_table(&class.properties, resolver, self.options.markdown_options);
+            set_section_id(&mut events, section_id("properties"));
+            callbacks.encode(&mut class_file, events)
+        }
+
+        // Constructors table
+        if !constructors.is_empty() {
+            let mut events = Self::methods_table(
+                "Constructors",
+                &constructors,
+                resolver,
+                self.options.markdown_options,
+            );
+            set_section_id(&mut events, section_id("constructors"));
+            callbacks.encode(&mut class_file, events);
+        }
+
+        // Methods table
+        let mut events =
+            Self::methods_table("Methods", &methods, resolver, self.options.markdown_options);
+        set_section_id(&mut events, section_id("methods"));
+        callbacks.encode(&mut class_file, events);
+
+        // Properties descriptions
+        if !class.properties.is_empty() {
+            callbacks.encode(
+                &mut class_file,
+                vec![
+                    Event::Start(Tag::Heading(HeadingLevel::H2, None, Vec::new())),
+                    Event::Text(CowStr::Borrowed("Properties Descriptions")),
+                    Event::End(Tag::Heading(HeadingLevel::H2, None, Vec::new())),
+                ],
+            );
+            for property in &class.properties {
+                callbacks.start_property(&mut class_file, resolver, property);
+                if let Some(link) = self.source_link(&class.file, property.line) {
+                    callbacks.encode(&mut class_file, source_link(link));
+                }
+                if let Some(note) = &property.deprecated {
+                    callbacks.encode(
+                        &mut class_file,
+                        deprecation_notice(note, resolver, self.options.markdown_options),
+                    );
+                }
+                self.encode_documentation(
+                    &mut class_file,
+                    &property.documentation,
+                    &format!("property-{}", property.name),
+                    callbacks,
+                );
+                callbacks.encode(
+                    &mut class_file,
+                    tags_list(&property.tags, resolver, self.options.markdown_options),
+                );
+
+                // Getter and setter
+                let accessors = [("Getter:", &property.getter), ("Setter:", &property.setter)];
+                let accessors = accessors.into_iter().filter_map(|(kind, accessor)| {
+                    let accessor = accessor.as_ref()?;
+                    let method = class.methods.iter().find(|method| &method.name == accessor);
+                    Some((kind, accessor, method))
+                });
+                for (kind, accessor, method) in accessors.clone() {
+                    let mut events = vec![
+                        Event::Start(Tag::Paragraph),
+                        Event::Start(Tag::Strong),
+                        Event::Text(CowStr::Borrowed(kind)),
+                        Event::End(Tag::Strong),
+                        Event::Text(CowStr::Borrowed(" ")),
+                    ];
+                    if method.is_some() {
+                        let link = Tag::Link(
+                            LinkType::Reference,
+                            format!("#func-{}", accessor).into(),
+                            accessor.as_str().into(),
+                        );
+                        events.extend(vec![
+                            Event::Start(link.clone()),
+                            Event::Code(accessor.as_str().into()),
+                            Event::End(link),
+                        ]);
+                    } else {
+                        events.push(Event::Code(accessor.as_str().into()));
+                    }
+                    events.push(Event::End(Tag::Paragraph));
+                    callbacks.encode(&mut class_file, events);
+                }
+                for (_, _, method) in accessors {
+                    if let Some(method) = method {
+                        self.encode_method(&mut class_file, method, callbacks);
+                    }
+                }
+            }
+        }
+
+        // Constructors descriptions
+        if !constructors.is_empty() {
+            callbacks.encode(
+                &mut class_file,
+                vec![
+                    Event::Start(Tag::Heading(HeadingLevel::H2, None, Vec::new())),
+                    Event::Text(CowStr::Borrowed("Constructors Descriptions")),
+                    Event::End(Tag::Heading(HeadingLevel::H2, None, Vec::new())),
+                ],
+            );
+            for method in constructors {
+                self.encode_method(&mut class_file, method, callbacks);
+            }
+        }
+
+        // Methods descriptions
+        callbacks.encode(
+            &mut class_file,
+            vec![
+                Event::Start(Tag::Heading(HeadingLevel::H2, None, Vec::new())),
+                Event::Text(CowStr::Borrowed("Methods Descriptions")),
+                Event::End(Tag::Heading(HeadingLevel::H2, None, Vec::new())),
+            ],
+        );
+        // accessors are described with their property
+        for method in methods {
+            if method.accessor_of.is_none() {
+                self.encode_method(&mut class_file, method, callbacks);
+            }
+        }
+
+        // Referenced by
+        let references = self.documentation.referenced_by(name);
+        if !references.is_empty() {
+            let mut events = vec![
+                Event::Start(Tag::Heading(HeadingLevel::H2, None, Vec::new())),
+                Event::Text(CowStr::Borrowed("Referenced By")),
+                Event::End(Tag::Heading(HeadingLevel::H2, None, Vec::new())),
+                Event::Start(Tag::List(None)),
+            ];
+            for (class_name, method) in references {
+                let link = Tag::Link(
+                    LinkType::Inline,
+                    format!(
+                        "./{}.{}#func-{}",
+                        class_file_stem(class_name),
+                        extension,
+                        method.name
+                    )
+                    .into(),
+                    CowStr::Borrowed(""),
+                );
+                events.push(Event::Start(Tag::Item));
+                events.extend(self.class_link(class_name, extension));
+                events.extend(vec![
+                    Event::Text(CowStr::Borrowed(": ")),
+                    Event::Start(link.clone()),
+                    Event::Text(CowStr::Borrowed(&method.name)),
+                    Event::End(link),
+                    Event::End(Tag::Item),
+                ]);
+            }
+            events.push(Event::End(Tag::List(None)));
+            callbacks.encode(&mut class_file, events);
+        }
+        class_file
+    }
+
+    /// Link to the class `name`.
+    ///
+    /// Classes documented in this crate link to their generated file (with the
+    /// given `extension`), other classes are resolved by the [`Resolver`].
+    fn class_link<'ev>(&'ev self, name: &'ev str, extension: &str) -> Vec<Event<'ev>> {
+        let link = if self.documentation.classes.contains_key(name) {
+            Tag::Link(
+                LinkType::Inline,
+                format!("./{}.{}", class_file_stem(name), extension).into(),
+                CowStr::Borrowed(""),
+            )
+        } else if let Some(link) = self.resolver.resolve(name) {
+            Tag::Link(LinkType::Shortcut, CowStr::from(link), CowStr::Borrowed(""))
+        } else {
+            return vec![Event::Text(CowStr::Borrowed(name))];
+        };
+        vec![
+            Event::Start(link.clone()),
+            Event::Text(CowStr::Borrowed(name)),
+            Event::End(link),
+        ]
+    }
+
+    /// Section explaining how to register `class` in the editor, if it is renamed
+    /// and [`editor_metadata`](Self::editor_metadata) is enabled.
+    ///
+    /// This will look like (in markdown):
+    /// ````markdown
+    /// ## Editor Registration
+    ///
+    /// Add these lines to the `[resource]` section of the class's `.gdns` file:
+    /// ```ini
+    /// class_name = "<rust name>"
+    /// script_class_name = "<godot name>"
+    /// script_class_icon_path = "<icon>"
+    /// ```
+    /// ````
+    fn editor_registration(&self, class: &GdnativeClass) -> Option<Vec<Event<'static>>> {
+        if !self.options.editor_metadata {
+            return None;
+        }
+        let script_class_name = self.resolver.rename_classes.get(&class.name)?;
+        let mut snippet = format!(
+            "class_name = \"{}\"\nscript_class_name = \"{}\"\n",
+            class.name, script_class_name
+        );
+        if let Some(icon) = self.options.class_icons.get(&class.name) {
+            let _ = writeln!(snippet, "script_class_icon_path = \"{}\"", icon);
+        }
+        let code_block = Tag::CodeBlock(CodeBlockKind::Fenced(CowStr::Borrowed("ini")));
+        Some(vec![
+            Event::Start(Tag::Heading(HeadingLevel::H2, None, Vec::new())),
+            Event::Text(CowStr::Borrowed("Editor Registration")),
+            Event::End(Tag::Heading(HeadingLevel::H2, None, Vec::new())),
+            Event::Start(Tag::Paragraph),
+            Event::Text(CowStr::Borrowed("Add these lines to the ")),
+            Event::Code(CowStr::Borrowed("[resource]")),
+            Event::Text(CowStr::Borrowed(" section of the class's ")),
+            Event::Code(CowStr::Borrowed(".gdns")),
+            Event::Text(CowStr::Borrowed(" file:")),
+            Event::End(Tag::Paragraph),
+            Event::Start(code_block.clone()),
+            Event::Text(snippet.into()),
+            Event::End(code_block),
+        ])
+    }
+
+    /// Encode the description of `method`: its signature and documentation.
+    ///
+    /// Tags in [`SIGNATURE_TAGS`] are put right under the signature, the other
+    /// ones after the documentation.
+    fn encode_method(&self, s: &mut String, method: &Method, callbacks: &mut dyn Callbacks) {
+        let resolver = self.resolver;
+        let (signature_tags, tags): (Vec<_>, Vec<_>) = method
+            .tags
+            .iter()
+            .cloned()
+            .partition(|tag| SIGNATURE_TAGS.contains(&tag.name.as_str()));
+        callbacks.start_method(s, resolver, method);
+        callbacks.encode(
+            s,
+            tags_list(&signature_tags, resolver, self.options.markdown_options),
+        );
+        if let Some(link) = self.source_link(&method.file, method.line) {
+            callbacks.encode(s, source_link(link));
+        }
+        if let Some(note) = &method.deprecated {
+            callbacks.encode(
+                s,
+                deprecation_notice(note, resolver, self.options.markdown_options),
+            );
+        }
+        self.encode_documentation(
+            s,
+            &method.documentation,
+            &format!("func-{}", method.name),
+            callbacks,
+        );
+        callbacks.encode(s, tags_list(&tags, resolver, self.options.markdown_options));
+    }
+
+    /// Encode the documentation of an item, with resolved links.
+    ///
+    /// Headings in `documentation` get stable ids of the form
+    /// `<id_prefix>-<slug>`, like `func-recalculate-errors`, so that they can be
+    /// linked to.
+    fn encode_documentation(
+        &self,
+        s: &mut String,
+        documentation: &str,
+        id_prefix: &str,
+        callbacks: &mut dyn Callbacks,
+    ) {
+        let resolver = self.resolver;
+        let mut broken_link_callback = broken_link_callback!(resolver);
+        let events: Vec<_> = EventIterator::new(
+            resolver,
+            self.options.heading_offset,
+            self.options.hidden_lines,
+            pulldown_cmark::Parser::new_with_broken_link_callback(
+                documentation,
+                self.options.markdown_options,
+                Some(&mut broken_link_callback),
+            ),
+        )
+        .collect();
+        let ids = heading_ids(&events, id_prefix);
+        callbacks.encode(s, set_heading_ids(events, &ids));
+    }
+
+    /// Create a table summarizing the properties.
+    fn properties_table<'ev>(
+        properties: &'ev [Property],
+        resolver: &'ev Resolver,
+        markdown_options: MarkdownOptions,
+    ) -> Vec<Event<'ev>> {
+        let mut events = vec![
+            Event::Start(Tag::Heading(HeadingLevel::H2, None, Vec::new())),
+            Event::Text(CowStr::Borrowed("Properties")),
             Event::End(Tag::Heading(HeadingLevel::H2, None, Vec::new())),
-            Event::Start(Tag::Table(vec![Alignment::Left, Alignment::Left])),
+            Event::Start(Tag::Table(vec![Alignment::Left; 4])),
             Event::Start(Tag::TableHead),
             Event::Start(Tag::TableCell),
             Event::Text(CowStr::Borrowed("type")),
@@ -358,6 +1536,12 @@ impl<'a> Generator<'a> {
             Event::Start(Tag::TableCell),
             Event::Text(CowStr::Borrowed("property")),
             Event::End(Tag::TableCell),
+            Event::Start(Tag::TableCell),
+            Event::Text(CowStr::Borrowed("default")),
+            Event::End(Tag::TableCell),
+            Event::Start(Tag::TableCell),
+            Event::Text(CowStr::Borrowed("description")),
+            Event::End(Tag::TableCell),
             Event::End(Tag::TableHead),
         ];
 
@@ -374,28 +1558,42 @@ impl<'a> Generator<'a> {
                 Event::End(Tag::TableCell),
                 Event::Start(Tag::TableCell),
                 Event::Start(link.clone()),
-                Event::Text(CowStr::Borrowed(property.name.as_str())),
+            ]);
+            events.extend(item_name(&property.name, property.deprecated.is_some()));
+            events.extend(vec![
                 Event::End(link),
                 Event::End(Tag::TableCell),
-                Event::End(Tag::TableRow),
+                Event::Start(Tag::TableCell),
+            ]);
+            if let Some(default) = &property.default {
+                events.push(Event::Code(default.as_str().into()));
+            }
+            events.extend(vec![
+                Event::End(Tag::TableCell),
+                Event::Start(Tag::TableCell),
             ]);
+            events.extend(inline_events(&property.summary, resolver, markdown_options));
+            events.extend(vec![Event::End(Tag::TableCell), Event::End(Tag::TableRow)]);
         }
 
-        events.push(Event::End(Tag::Table(vec![
-            Alignment::Left,
-            Alignment::Left,
-        ])));
+        events.push(Event::End(Tag::Table(vec![Alignment::Left; 4])));
 
         events
     }
 
-    /// Create a table summarizing the methods.
-    fn methods_table<'ev>(methods: &'ev [Method], resolver: &'ev Resolver) -> Vec<Event<'ev>> {
+    /// Create a table summarizing the methods, under a heading with the given
+    /// `title`.
+    fn methods_table<'ev>(
+        title: &'static str,
+        methods: &[&'ev Method],
+        resolver: &'ev Resolver,
+        markdown_options: MarkdownOptions,
+    ) -> Vec<Event<'ev>> {
         let mut events = vec![
             Event::Start(Tag::Heading(HeadingLevel::H2, None, Vec::new())),
-            Event::Text(CowStr::Borrowed("Methods")),
+            Event::Text(CowStr::Borrowed(title)),
             Event::End(Tag::Heading(HeadingLevel::H2, None, Vec::new())),
-            Event::Start(Tag::Table(vec![Alignment::Left, Alignment::Left])),
+            Event::Start(Tag::Table(vec![Alignment::Left; 3])),
             Event::Start(Tag::TableHead),
             Event::Start(Tag::TableCell),
             Event::Text(CowStr::Borrowed("returns")),
@@ -403,10 +1601,14 @@ impl<'a> Generator<'a> {
             Event::Start(Tag::TableCell),
             Event::Text(CowStr::Borrowed("method")),
             Event::End(Tag::TableCell),
+            Event::Start(Tag::TableCell),
+            Event::Text(CowStr::Borrowed("description")),
+            Event::End(Tag::TableCell),
             Event::End(Tag::TableHead),
         ];
 
-        for method in methods {
+        // accessors are listed with their property
+        for method in methods.iter().filter(|method| method.accessor_of.is_none()) {
             let link = format!("#func-{}", method.name);
             events.push(Event::Start(Tag::TableRow));
             events.push(Event::Start(Tag::TableCell));
@@ -419,12 +1621,9 @@ impl<'a> Generator<'a> {
                 link.into(),
                 method.name.as_str().into(),
             );
-            events.extend(vec![
-                Event::Start(link.clone()),
-                Event::Text(CowStr::Borrowed(&method.name)),
-                Event::End(link),
-                Event::Text(CowStr::Borrowed("( ")),
-            ]);
+            events.push(Event::Start(link.clone()));
+            events.extend(item_name(&method.name, method.deprecated.is_some()));
+            events.extend(vec![Event::End(link), Event::Text(CowStr::Borrowed("( "))]);
             for (index, (name, typ, _)) in method.parameters.iter().enumerate() {
                 events.push(Event::Text(format!("{}: ", name).into()));
                 events.extend(resolver.encode_type(typ));
@@ -433,29 +1632,380 @@ impl<'a> Generator<'a> {
                 }
             }
 
+            events.push(Event::Text(CowStr::Borrowed(" )")));
+            events.extend(method_markers(method));
             events.extend(vec![
-                Event::Text(CowStr::Borrowed(" )")),
                 Event::End(Tag::TableCell),
-                Event::End(Tag::TableRow),
+                Event::Start(Tag::TableCell),
             ]);
+            events.extend(inline_events(&method.summary, resolver, markdown_options));
+            events.extend(vec![Event::End(Tag::TableCell), Event::End(Tag::TableRow)]);
         }
 
-        events.push(Event::End(Tag::Table(vec![
-            Alignment::Left,
-            Alignment::Left,
-        ])));
+        events.push(Event::End(Tag::Table(vec![Alignment::Left; 3])));
 
         events
     }
 }
 
-/// Iterate over [events](Event), resolving links and changing the resolved
-/// broken links types.
+/// Split the root documentation on its level 1 headings.
+///
+/// Returns the text preceding the first heading, and the guides.
+fn split_guides(
+    documentation: &Documentation,
+    markdown_options: MarkdownOptions,
+) -> (&str, Vec<Guide<'_>>) {
+    let text = documentation.root_documentation.as_str();
+    // (start of the heading, end of the heading, title)
+    let mut headings = Vec::new();
+    let mut current_heading: Option<(usize, String)> = None;
+    for (event, range) in Parser::new_ext(text, markdown_options).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Heading(HeadingLevel::H1, _, _)) => {
+                current_heading = Some((range.start, String::new()))
+            }
+            Event::End(Tag::Heading(HeadingLevel::H1, _, _)) => {
+                if let Some((start, title)) = current_heading.take() {
+                    headings.push((start, range.end, title))
+                }
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some((_, title)) = &mut current_heading {
+                    title.push_str(&text)
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let index_end = headings.first().map(|(start, _, _)| *start);
+    let mut guides: Vec<Guide> = Vec::new();
+    for (index, (_, heading_end, title)) in headings.iter().enumerate() {
+        let content_end = headings
+            .get(index + 1)
+            .map(|(start, _, _)| *start)
+            .unwrap_or(text.len());
+        let slug = slugify(title);
+        let mut file_stem = slug.clone();
+        let mut suffix = 0;
+        while file_stem.is_empty()
+            || file_stem == "index"
+            || documentation
+                .classes
+                .keys()
+                .any(|name| class_file_stem(name).eq_ignore_ascii_case(&file_stem))
+            || guides.iter().any(|guide| guide.file_stem == file_stem)
+        {
+            suffix += 1;
+            file_stem = format!("{}-{}", slug, suffix);
+        }
+        guides.push(Guide {
+            title: title.trim().to_string(),
+            file_stem,
+            content: &text[*heading_end..content_end],
+        });
+    }
+    (&text[..index_end.unwrap_or(text.len())], guides)
+}
+
+/// Convert `text` into a lowercase, dash-separated identifier suitable for file
+/// names and anchors.
+fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.chars() {
+        if c.is_alphanumeric() || c == '_' {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    while slug.ends_with('-') {
+        slug.pop();
+    }
+    slug
+}
+
+/// Events for the name of an item, struck through if it is deprecated.
+fn item_name(name: &str, deprecated: bool) -> Vec<Event<'_>> {
+    if deprecated {
+        vec![
+            Event::Start(Tag::Strikethrough),
+            Event::Text(CowStr::Borrowed(name)),
+            Event::End(Tag::Strikethrough),
+        ]
+    } else {
+        vec![Event::Text(CowStr::Borrowed(name))]
+    }
+}
+
+/// Table of contents of a class page, linking to its sections and members.
+///
+/// This looks like (in markdown):
+/// ```markdown
+/// - [Description](#description)
+/// - [Properties](#properties)
+///   - [my_property](#property-my_property)
+/// - [Methods](#methods)
+///   - [my_method](#func-my_method)
+/// ```
+fn table_of_contents<'ev>(
+    class: &'ev GdnativeClass,
+    constructors: &[&'ev Method],
+    methods: &[&'ev Method],
+) -> Vec<Event<'ev>> {
+    fn link<'ev>(events: &mut Vec<Event<'ev>>, text: &'ev str, dest: String) {
+        let link = Tag::Link(LinkType::Inline, dest.into(), CowStr::Borrowed(""));
+        events.extend(vec![
+            Event::Start(link.clone()),
+            Event::Text(CowStr::Borrowed(text)),
+            Event::End(link),
+        ]);
+    }
+    fn section<'ev>(
+        events: &mut Vec<Event<'ev>>,
+        title: &'static str,
+        id: &str,
+        items: Vec<(&'ev str, String)>,
+    ) {
+        events.push(Event::Start(Tag::Item));
+        link(events, title, format!("#{}", id));
+        if !items.is_empty() {
+            events.push(Event::Start(Tag::List(None)));
+            for (text, dest) in items {
+                events.push(Event::Start(Tag::Item));
+                link(events, text, dest);
+                events.push(Event::End(Tag::Item));
+            }
+            events.push(Event::End(Tag::List(None)));
+        }
+        events.push(Event::End(Tag::Item));
+    }
+    fn method_items<'ev>(methods: &[&'ev Method]) -> Vec<(&'ev str, String)> {
+        // accessors are listed with their property
+        (methods.iter())
+            .filter(|method| method.accessor_of.is_none())
+            .map(|method| (method.name.as_str(), format!("#func-{}", method.name)))
+            .collect()
+    }
+
+    let mut events = vec![Event::Start(Tag::List(None))];
+    section(&mut events, "Description", "description", Vec::new());
+    if !class.properties.is_empty() {
+        let properties = (class.properties.iter())
+            .map(|property| {
+                let dest = format!("#property-{}", property.name);
+                (property.name.as_str(), dest)
+            })
+            .collect();
+        section(&mut events, "Properties", "properties", properties);
+    }
+    if !constructors.is_empty() {
+        let constructors = method_items(constructors);
+        section(&mut events, "Constructors", "constructors", constructors);
+    }
+    section(&mut events, "Methods", "methods", method_items(methods));
+    events.push(Event::End(Tag::List(None)));
+    events
+}
+
+/// Set the id of the first heading in `events`.
+fn set_section_id<'ev>(events: &mut [Event<'ev>], id: Option<&'ev str>) {
+    let mut found_start = false;
+    for event in events {
+        match event {
+            Event::Start(Tag::Heading(_, heading_id, _)) if !found_start => {
+                *heading_id = id;
+                found_start = true;
+            }
+            Event::End(Tag::Heading(_, heading_id, _)) => {
+                *heading_id = id;
+                return;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Inline events for `text` (like [`Method::summary`]), with resolved links.
+fn inline_events<'ev>(
+    text: &'ev str,
+    resolver: &'ev Resolver,
+    markdown_options: MarkdownOptions,
+) -> Vec<Event<'ev>> {
+    let mut broken_link_callback = broken_link_callback!(resolver);
+    EventIterator::new(
+        resolver,
+        0,
+        HiddenLines::Disabled,
+        pulldown_cmark::Parser::new_with_broken_link_callback(
+            text,
+            markdown_options,
+            Some(&mut broken_link_callback),
+        ),
+    )
+    .filter(|event| {
+        !matches!(
+            event,
+            Event::Start(Tag::Paragraph) | Event::End(Tag::Paragraph)
+        )
+    })
+    .collect()
+}
+
+/// Tags of a method that describe how to call it, shown under its signature.
+const SIGNATURE_TAGS: [&str; 2] = ["complexity", "requires"];
+
+/// One paragraph per tag, of the form (in markdown):
+/// ```markdown
+/// **Since:** <value>
+/// ```
+fn tags_list<'ev>(
+    tags: &'ev [documentation::Tag],
+    resolver: &'ev Resolver,
+    markdown_options: MarkdownOptions,
+) -> Vec<Event<'ev>> {
+    let mut events = Vec::new();
+    for tag in tags {
+        let mut name = tag.name.chars();
+        let title: String = name
+            .next()
+            .map(|first| first.to_uppercase().chain(name).collect())
+            .unwrap_or_default();
+        events.extend(vec![
+            Event::Start(Tag::Paragraph),
+            Event::Start(Tag::Strong),
+            Event::Text(format!("{}:", title).into()),
+            Event::End(Tag::Strong),
+        ]);
+        if !tag.value.is_empty() {
+            events.push(Event::Text(CowStr::Borrowed(" ")));
+            events.extend(inline_events(&tag.value, resolver, markdown_options));
+        }
+        events.push(Event::End(Tag::Paragraph));
+    }
+    events
+}
+
+/// Paragraph containing a link to the source code.
+fn source_link(link: String) -> Vec<Event<'static>> {
+    let link = Tag::Link(LinkType::Inline, link.into(), CowStr::Borrowed(""));
+    vec![
+        Event::Start(Tag::Paragraph),
+        Event::Start(link.clone()),
+        Event::Text(CowStr::Borrowed("source")),
+        Event::End(link),
+        Event::End(Tag::Paragraph),
+    ]
+}
+
+/// Name of the file generated for the class `name` (a key of
+/// [`Documentation::classes`]), without extension.
+///
+/// Classes with the same name in different modules are keyed by their module
+/// path, like `graph::Interface`: this gives `graph-Interface`.
+pub fn class_file_stem(name: &str) -> String {
+    name.replace("::", "-")
+}
+
+/// Push `text` to `s`, escaping backslashes and the characters in `special`
+/// with a backslash.
+///
+/// This escapes the text of markdown links (with `['[', ']']`), or quoted strings
+/// in TOML, YAML and javascript (with the quote).
+fn push_escaped(s: &mut String, text: &str, special: &[char]) {
+    for c in text.chars() {
+        if c == '\\' || special.contains(&c) {
+            s.push('\\');
+        }
+        s.push(c);
+    }
+}
+
+/// Markers added after the signature of a method: `async` for asynchronous
+/// methods, and the keyword of its [RPC mode](documentation::RpcMode).
+///
+/// This will look like (in markdown):
+/// ```markdown
+///  *async* *remote*
+/// ```
+fn method_markers(method: &Method) -> Vec<Event<'static>> {
+    let mut events = Vec::new();
+    let async_keyword = method.is_async.then_some("async");
+    let rpc_keyword = method.rpc_mode.map(documentation::RpcMode::keyword);
+    for keyword in async_keyword.into_iter().chain(rpc_keyword) {
+        events.extend([
+            Event::Text(CowStr::Borrowed(" ")),
+            Event::Start(Tag::Emphasis),
+            Event::Text(CowStr::Borrowed(keyword)),
+            Event::End(Tag::Emphasis),
+        ]);
+    }
+    events
+}
+
+/// Warning banner for a deprecated item, with resolved links in `note`.
+///
+/// This will look like (in markdown):
+/// ```markdown
+/// > **Deprecated:** <note>
+/// ```
+fn deprecation_notice<'ev>(
+    note: &'ev str,
+    resolver: &'ev Resolver,
+    markdown_options: MarkdownOptions,
+) -> Vec<Event<'ev>> {
+    let mut events = vec![
+        Event::Start(Tag::BlockQuote),
+        Event::Start(Tag::Paragraph),
+        Event::Start(Tag::Strong),
+        Event::Text(CowStr::Borrowed("Deprecated:")),
+        Event::End(Tag::Strong),
+    ];
+    if !note.is_empty() {
+        events.push(Event::Text(CowStr::Borrowed(" ")));
+        events.extend(inline_events(note, resolver, markdown_options));
+    }
+    events.extend(vec![
+        Event::End(Tag::Paragraph),
+        Event::End(Tag::BlockQuote),
+    ]);
+    events
+}
+
+/// Iterate over [events](Event), resolving links, changing the resolved
+/// broken links types, shifting the headings and handling the hidden lines of
+/// gdscript code blocks.
 struct EventIterator<'resolver, 'input, 'cb> {
     context: &'resolver Resolver,
+    /// See [`GeneratorOptions::heading_offset`].
+    heading_offset: usize,
+    /// See [`GeneratorOptions::hidden_lines`].
+    hidden_lines: HiddenLines,
+    /// Are we in a gdscript code block ?
+    in_gdscript: bool,
+    /// Are we in a shortcut link (like `[fn@clear]`) ?
+    in_shortcut_link: bool,
     parser: Parser<'input, 'cb>,
 }
 
+impl<'resolver, 'input, 'cb> EventIterator<'resolver, 'input, 'cb> {
+    fn new(
+        context: &'resolver Resolver,
+        heading_offset: usize,
+        hidden_lines: HiddenLines,
+        parser: Parser<'input, 'cb>,
+    ) -> Self {
+        Self {
+            context,
+            heading_offset,
+            hidden_lines,
+            in_gdscript: false,
+            in_shortcut_link: false,
+            parser,
+        }
+    }
+}
+
 impl<'resolver, 'input, 'cb> Iterator for EventIterator<'resolver, 'input, 'cb> {
     type Item = Event<'input>;
 
@@ -473,6 +2023,180 @@ impl<'resolver, 'input, 'cb> Iterator for EventIterator<'resolver, 'input, 'cb>
             _ => next_event,
         };
         self.context.resolve_event(&mut next_event);
+        match &mut next_event {
+            Event::Start(Tag::Heading(level, _, _)) | Event::End(Tag::Heading(level, _, _)) => {
+                *level = offset_heading_level(*level, self.heading_offset);
+            }
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info))) => {
+                self.in_gdscript = parse_code_block_info(info).lang == "gdscript";
+            }
+            Event::End(Tag::CodeBlock(_)) => self.in_gdscript = false,
+            Event::Start(Tag::Link(LinkType::Shortcut, _, _)) => self.in_shortcut_link = true,
+            Event::End(Tag::Link(..)) => self.in_shortcut_link = false,
+            // like rustdoc, do not display the `fn@` of `[fn@clear]`
+            Event::Text(text) | Event::Code(text)
+                if self.in_shortcut_link && text.contains('@') =>
+            {
+                if let (Some(_), rest) = Disambiguator::strip(text) {
+                    *text = rest.to_string().into();
+                }
+            }
+            Event::Text(text) if self.in_gdscript && self.hidden_lines != HiddenLines::Disabled => {
+                *text = apply_hidden_lines(text, self.hidden_lines).into();
+            }
+            _ => {}
+        }
         Some(next_event)
     }
 }
+
+/// Hide or show the hidden lines of the gdscript code in `text`.
+///
+/// Like in rustdoc, a line starting with `# ` (or just `#`) is hidden, and `##`
+/// escapes a `#`. Indentation is preserved.
+fn apply_hidden_lines(text: &str, hidden_lines: HiddenLines) -> String {
+    let mut result = String::new();
+    for line in text.split_inclusive('\n') {
+        let content = line.trim_start();
+        let indentation = &line[..line.len() - content.len()];
+        let (hidden, content) = if let Some(rest) = content.strip_prefix("##") {
+            (false, format!("#{}", rest))
+        } else if let Some(rest) = content.strip_prefix("# ") {
+            (true, rest.to_string())
+        } else if content == "#" || content.starts_with("#\n") {
+            (true, content[1..].to_string())
+        } else {
+            (false, content.to_string())
+        };
+        if !hidden || hidden_lines == HiddenLines::Show {
+            result.push_str(indentation);
+            result.push_str(&content);
+        }
+    }
+    result
+}
+
+/// Shift `level` by `offset`, without going past [`HeadingLevel::H6`].
+fn offset_heading_level(level: HeadingLevel, offset: usize) -> HeadingLevel {
+    HeadingLevel::try_from((level as usize + offset).min(6)).unwrap_or(HeadingLevel::H6)
+}
+
+/// Compute the ids of the headings in `events`, of the form `<prefix>-<slug>`
+/// (or `<slug>` if `prefix` is empty).
+///
+/// Duplicated ids are disambiguated by appending `-1`, `-2`...
+fn heading_ids(events: &[Event], prefix: &str) -> Vec<String> {
+    let mut ids: Vec<String> = Vec::new();
+    let mut heading_text = None;
+    for event in events {
+        match event {
+            Event::Start(Tag::Heading(..)) => heading_text = Some(String::new()),
+            Event::Text(text) | Event::Code(text) => {
+                if let Some(heading_text) = &mut heading_text {
+                    heading_text.push_str(text)
+                }
+            }
+            Event::End(Tag::Heading(..)) => {
+                let slug = slugify(&heading_text.take().unwrap_or_default());
+                let base = if prefix.is_empty() {
+                    slug
+                } else {
+                    format!("{}-{}", prefix, slug)
+                };
+                let mut id = base.clone();
+                let mut index = 0;
+                while ids.contains(&id) {
+                    index += 1;
+                    id = format!("{}-{}", base, index);
+                }
+                ids.push(id);
+            }
+            _ => {}
+        }
+    }
+    ids
+}
+
+/// Returns `id` if `html` is an anchor like `<a id="id"></a>`.
+fn html_anchor(html: &str) -> Option<&str> {
+    html.trim()
+        .strip_prefix("<a id=\"")?
+        .strip_suffix("\"></a>")
+        .filter(|id| !id.contains('"'))
+}
+
+/// Set the id of the headings in `events` to `ids`, in order (see
+/// [`heading_ids`]).
+fn set_heading_ids<'ev>(events: Vec<Event<'ev>>, ids: &'ev [String]) -> Vec<Event<'ev>> {
+    let mut ids = ids.iter();
+    let mut current_id = None;
+    events
+        .into_iter()
+        .map(|event| match event {
+            Event::Start(Tag::Heading(level, _, classes)) => {
+                current_id = ids.next().map(String::as_str);
+                Event::Start(Tag::Heading(level, current_id, classes))
+            }
+            Event::End(Tag::Heading(level, _, classes)) => {
+                Event::End(Tag::Heading(level, current_id.take(), classes))
+            }
+            event => event,
+        })
+        .collect()
+}
+
+/// Parsed info string of a fenced code block, like `gdscript,before_each{3-5}`.
+struct CodeBlockInfo<'a> {
+    /// Language of the code block.
+    lang: &'a str,
+    /// Attributes following the language, separated by commas (like
+    /// `before_each`).
+    attributes: Vec<&'a str>,
+    /// Ranges of lines (starting at 1) to highlight.
+    highlights: Vec<RangeInclusive<usize>>,
+}
+
+impl CodeBlockInfo<'_> {
+    /// Returns `true` if the code block has the given `attribute`.
+    fn has_attribute(&self, attribute: &str) -> bool {
+        self.attributes.contains(&attribute)
+    }
+}
+
+/// Split the info string of a fenced code block into its language, its attributes
+/// and the ranges of lines (starting at 1) to highlight.
+///
+/// For example, `gdscript,before_each{3-5,7}` gives a language of `gdscript`, a
+/// `before_each` attribute, and the ranges `[3..=5, 7..=7]`.
+fn parse_code_block_info(info: &str) -> CodeBlockInfo<'_> {
+    let (lang, annotation) = match info.split_once('{') {
+        Some((lang, annotation)) => (lang, Some(annotation.trim_end().trim_end_matches('}'))),
+        None => (info, None),
+    };
+    let mut attributes = lang.split(',').map(str::trim);
+    let mut info = CodeBlockInfo {
+        lang: attributes.next().unwrap_or_default(),
+        attributes: attributes
+            .filter(|attribute| !attribute.is_empty())
+            .collect(),
+        highlights: Vec::new(),
+    };
+    let annotation = match annotation {
+        Some(annotation) => annotation,
+        None => return info,
+    };
+    let mut ranges = Vec::new();
+    for range in annotation.split(',').map(str::trim) {
+        let (start, end) = range.split_once('-').unwrap_or((range, range));
+        match (start.trim().parse(), end.trim().parse()) {
+            (Ok(start), Ok(end)) if start <= end => ranges.push(start..=end),
+            _ => diagnostic!(
+                Lint::InvalidLineRange,
+                "invalid line range in code block: '{}'",
+                range
+            ),
+        }
+    }
+    info.highlights = ranges;
+    info
+}
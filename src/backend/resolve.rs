@@ -1,20 +1,33 @@
 //! Facilities related to link resolution.
 
+#[cfg(test)]
+mod tests;
+
 use crate::{
-    config::ConfigFile,
+    config::{ConfigFile, Placeholders},
+    diagnostics::{diagnostic, Lint},
     documentation::{self, Documentation, Type},
     GodotVersion,
 };
 use pulldown_cmark::{CowStr, Event, Tag};
-use std::collections::HashMap;
+use std::{borrow::Cow, collections::HashMap, fmt, sync::Arc};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 /// Information to resolve links.
 pub struct Resolver {
+    /// Version of godot that is documented.
+    pub godot_version: GodotVersion,
     /// Link to godot items' documentation.
     ///
     /// Contains the link to godot classes, but also `true`, `INF`, `Err`...
     pub godot_items: HashMap<String, String>,
+    /// Kind of the members of godot classes, by `Class.member`: `"method"`,
+    /// `"property"`, `"signal"` or `"constant"`.
+    ///
+    /// These are read from the class references given in
+    /// [`ConfigFile::godot_classes_file`]. The kind of other members is guessed
+    /// from their name, see [`resolve_godot_member`](Self::resolve_godot_member).
+    pub godot_members: HashMap<String, String>,
     /// Mapping from Rust to Godot types.
     pub rust_to_godot: HashMap<String, String>,
     /// User-defined overrides.
@@ -25,13 +38,92 @@ pub struct Resolver {
     ///
     /// These are defined in the [toml configuration file](crate::ConfigFile).
     pub rename_classes: HashMap<String, String>,
+    /// User-defined renaming of methods, by name or by `Class::method`.
+    ///
+    /// See [`ConfigFile::rename_methods`].
+    pub rename_methods: HashMap<String, String>,
+    /// User-defined renaming of properties, by name or by `Class::property`.
+    ///
+    /// See [`ConfigFile::rename_properties`].
+    pub rename_properties: HashMap<String, String>,
+    /// Aliases of the documented classes and methods, mapped to their name.
+    ///
+    /// These are declared with `#[doc(alias = "...")]`: a link to an alias is
+    /// resolved like a link to the item it refers to.
+    pub aliases: HashMap<String, String>,
+    /// Links to the pages of the classes documented in this crate, like
+    /// `./MyClass.md`.
+    ///
+    /// These depend on the extension of the pages, and are thus filled for each
+    /// backend.
+    pub documented_classes: HashMap<String, String>,
+    /// Links to the methods and properties of the documented classes, by
+    /// `Class::member`, like `./MyClass.md#func-run`.
+    pub documented_members: HashMap<String, String>,
+    /// Documented classes inherited by each documented class, starting with its
+    /// direct parent.
+    pub inherited_classes: HashMap<String, Vec<String>>,
+    /// Documented class whose page is being generated, if any.
+    ///
+    /// `Self::member` links to a member of this class, or of the documented
+    /// classes it inherits. Their properties can also be linked by their name
+    /// alone, like `speed`. See [`for_class`](Self::for_class).
+    pub current_class: Option<String>,
+    /// Base URL of the documentation of Rust crates, by crate name.
+    ///
+    /// See [`ConfigFile::rust_docs`].
+    pub rust_docs: HashMap<String, String>,
+    /// Custom resolution of the links unknown to the built-in tables.
+    ///
+    /// See [`Builder::resolve_hook`](crate::Builder::resolve_hook).
+    pub hooks: Vec<Arc<dyn ResolveHook + Send + Sync>>,
+}
+
+/// Custom resolution of links, for names that the built-in tables do not know.
+///
+/// This is implemented for closures of type `Fn(&str) -> Option<String>`.
+///
+/// See [`Builder::resolve_hook`](crate::Builder::resolve_hook).
+pub trait ResolveHook {
+    /// Resolve `link` (stripped of its enclosing \` and disambiguator) to an URL.
+    ///
+    /// Returns `None` to let the next hook resolve it.
+    fn resolve(&self, link: &str) -> Option<String>;
+}
+
+impl<F: Fn(&str) -> Option<String>> ResolveHook for F {
+    fn resolve(&self, link: &str) -> Option<String> {
+        self(link)
+    }
+}
+
+impl fmt::Debug for dyn ResolveHook + Send + Sync {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("ResolveHook")
+    }
+}
+
+/// Hooks are only equal to themselves.
+impl PartialEq for dyn ResolveHook + Send + Sync {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(
+            self as *const Self as *const (),
+            other as *const Self as *const (),
+        )
+    }
 }
 
+impl Eq for dyn ResolveHook + Send + Sync {}
+
 /// Url for the (stable) godot documentation
 const GODOT_DOCUMENTATION_URL_3_2: &str = "https://docs.godotengine.org/en/3.2/classes";
 const GODOT_DOCUMENTATION_URL_3_3: &str = "https://docs.godotengine.org/en/3.3/classes";
 const GODOT_DOCUMENTATION_URL_3_4: &str = "https://docs.godotengine.org/en/3.4/classes";
 const GODOT_DOCUMENTATION_URL_3_5: &str = "https://docs.godotengine.org/en/3.5/classes";
+const GODOT_DOCUMENTATION_URL_4_0: &str = "https://docs.godotengine.org/en/4.0/classes";
+const GODOT_DOCUMENTATION_URL_4_1: &str = "https://docs.godotengine.org/en/4.1/classes";
+const GODOT_DOCUMENTATION_URL_4_2: &str = "https://docs.godotengine.org/en/4.2/classes";
+const GODOT_DOCUMENTATION_URL_4_3: &str = "https://docs.godotengine.org/en/4.3/classes";
 
 /// List of godot 3.2 classes, like `Array`, `int`, `Transform2D`...
 const GODOT_CLASSES_3_2: &[&str] = &include!("../../fetch_godot_classes/godot_classes-3.2.txt");
@@ -41,6 +133,14 @@ const GODOT_CLASSES_3_3: &[&str] = &include!("../../fetch_godot_classes/godot_cl
 const GODOT_CLASSES_3_4: &[&str] = &include!("../../fetch_godot_classes/godot_classes-3.4.txt");
 /// List of godot 3.5 classes, like `Array`, `int`, `Transform2D`...
 const GODOT_CLASSES_3_5: &[&str] = &include!("../../fetch_godot_classes/godot_classes-3.5.txt");
+/// List of godot 4.0 classes, like `Array`, `int`, `Transform3D`...
+const GODOT_CLASSES_4_0: &[&str] = &include!("../../fetch_godot_classes/godot_classes-4.0.txt");
+/// List of godot 4.1 classes, like `Array`, `int`, `Transform3D`...
+const GODOT_CLASSES_4_1: &[&str] = &include!("../../fetch_godot_classes/godot_classes-4.1.txt");
+/// List of godot 4.2 classes, like `Array`, `int`, `Transform3D`...
+const GODOT_CLASSES_4_2: &[&str] = &include!("../../fetch_godot_classes/godot_classes-4.2.txt");
+/// List of godot 4.3 classes, like `Array`, `int`, `Transform3D`...
+const GODOT_CLASSES_4_3: &[&str] = &include!("../../fetch_godot_classes/godot_classes-4.3.txt");
 
 /// List of some godot constants and information about where they sould link to.
 ///
@@ -56,6 +156,42 @@ const GODOT_CONSTANTS: &[(&str, &str, &str)] = &[
     ("OK", "class_@globalscope", "enum-globalscope-error"),
 ];
 
+/// First words of the names of godot methods, like `add` in `add_child`.
+///
+/// Used to guess whether a member of a godot class is a method or a property,
+/// when it is not in [`Resolver::godot_members`].
+const GODOT_METHOD_VERBS: &[&str] = &[
+    "add",
+    "apply",
+    "call",
+    "can",
+    "clear",
+    "connect",
+    "create",
+    "disconnect",
+    "duplicate",
+    "emit",
+    "find",
+    "get",
+    "has",
+    "hide",
+    "is",
+    "load",
+    "look",
+    "make",
+    "move",
+    "play",
+    "queue",
+    "remove",
+    "rotate",
+    "save",
+    "set",
+    "show",
+    "start",
+    "stop",
+    "update",
+];
+
 /// Mapping from Rust to Godot types.
 const RUST_TO_GODOT: &[(&str, &str)] = &[
     ("i32", "int"),
@@ -68,31 +204,144 @@ const RUST_TO_GODOT: &[(&str, &str)] = &[
     ("Float32Array", "PoolRealArray"),
 ];
 
+/// Mapping from Rust to Godot types that changed in godot 4.
+///
+/// These take precedence over [`RUST_TO_GODOT`] for godot 4.x.
+const RUST_TO_GODOT_4: &[(&str, &str)] = &[
+    ("ByteArray", "PackedByteArray"),
+    ("Int32Array", "PackedInt32Array"),
+    ("Float32Array", "PackedFloat32Array"),
+    ("StringArray", "PackedStringArray"),
+    ("Vector2Array", "PackedVector2Array"),
+    ("Vector3Array", "PackedVector3Array"),
+    ("ColorArray", "PackedColorArray"),
+    ("Reference", "RefCounted"),
+    ("Spatial", "Node3D"),
+    ("Transform", "Transform3D"),
+    ("Quat", "Quaternion"),
+];
+
+/// Rustdoc-style disambiguator of a link, like `fn@` in `fn@clear`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Disambiguator {
+    /// `struct@`, `enum@`, `trait@`, `type@`, `union@`, `prim@` or `primitive@`:
+    /// a class or type.
+    Type,
+    /// `fn@` or `method@`, or the `()` suffix: a method.
+    Method,
+    /// `field@` or `property@`: a property.
+    Property,
+}
+
+impl Disambiguator {
+    /// Split the disambiguator of `link` from the rest of the link.
+    ///
+    /// For example, `fn@clear` and `clear()` give `(Some(Method), "clear")`.
+    pub fn strip(link: &str) -> (Option<Self>, &str) {
+        if let Some((prefix, rest)) = link.split_once('@') {
+            let disambiguator = match prefix {
+                "struct" | "enum" | "trait" | "type" | "union" | "prim" | "primitive" => Self::Type,
+                "fn" | "method" => Self::Method,
+                "field" | "property" => Self::Property,
+                _ => return (None, link),
+            };
+            (Some(disambiguator), rest)
+        } else if let Some(rest) = link.strip_suffix("()") {
+            (Some(Self::Method), rest)
+        } else {
+            (None, link)
+        }
+    }
+}
+
+/// Crates of the standard library, documented at `https://doc.rust-lang.org`.
+const STANDARD_CRATES: &[&str] = &["std", "core", "alloc"];
+
+/// Common types of the standard library, and their page in its documentation.
+const STANDARD_TYPES: &[(&str, &str)] = &[
+    ("Arc", "sync/struct.Arc.html"),
+    ("BTreeMap", "collections/struct.BTreeMap.html"),
+    ("BTreeSet", "collections/struct.BTreeSet.html"),
+    ("Box", "boxed/struct.Box.html"),
+    ("Duration", "time/struct.Duration.html"),
+    ("HashMap", "collections/struct.HashMap.html"),
+    ("HashSet", "collections/struct.HashSet.html"),
+    ("Mutex", "sync/struct.Mutex.html"),
+    ("Option", "option/enum.Option.html"),
+    ("PathBuf", "path/struct.PathBuf.html"),
+    ("Rc", "rc/struct.Rc.html"),
+    ("Result", "result/enum.Result.html"),
+    ("Vec", "vec/struct.Vec.html"),
+    ("VecDeque", "collections/struct.VecDeque.html"),
+    ("char", "primitive.char.html"),
+    ("i8", "primitive.i8.html"),
+    ("i16", "primitive.i16.html"),
+    ("isize", "primitive.isize.html"),
+    ("u8", "primitive.u8.html"),
+    ("u16", "primitive.u16.html"),
+    ("u32", "primitive.u32.html"),
+    ("u64", "primitive.u64.html"),
+    ("usize", "primitive.usize.html"),
+];
+
 impl Resolver {
     pub(crate) fn new(godot_version: GodotVersion) -> Self {
         Self {
+            godot_version,
             godot_items: Self::godot_items(godot_version),
-            rust_to_godot: Self::rust_to_godot(),
+            godot_members: HashMap::new(),
+            rust_to_godot: Self::rust_to_godot(godot_version),
             url_overrides: HashMap::new(),
             rename_classes: HashMap::new(),
+            rename_methods: HashMap::new(),
+            rename_properties: HashMap::new(),
+            aliases: HashMap::new(),
+            documented_classes: HashMap::new(),
+            documented_members: HashMap::new(),
+            inherited_classes: HashMap::new(),
+            current_class: None,
+            rust_docs: HashMap::new(),
+            hooks: Vec::new(),
         }
     }
 
     fn godot_items(godot_version: GodotVersion) -> HashMap<String, String> {
-        let mut godot_items = HashMap::new();
-        let classes = match godot_version {
-            GodotVersion::Version32 => GODOT_CLASSES_3_2,
-            GodotVersion::Version33 => GODOT_CLASSES_3_3,
-            GodotVersion::Version34 => GODOT_CLASSES_3_4,
-            GodotVersion::Version35 => GODOT_CLASSES_3_5,
-        };
         let documentation_url = match godot_version {
             GodotVersion::Version32 => GODOT_DOCUMENTATION_URL_3_2,
             GodotVersion::Version33 => GODOT_DOCUMENTATION_URL_3_3,
             GodotVersion::Version34 => GODOT_DOCUMENTATION_URL_3_4,
             GodotVersion::Version35 => GODOT_DOCUMENTATION_URL_3_5,
+            GodotVersion::Version40 => GODOT_DOCUMENTATION_URL_4_0,
+            GodotVersion::Version41 => GODOT_DOCUMENTATION_URL_4_1,
+            GodotVersion::Version42 => GODOT_DOCUMENTATION_URL_4_2,
+            GodotVersion::Version43 => GODOT_DOCUMENTATION_URL_4_3,
         };
+        Self::godot_items_from(Self::bundled_classes(godot_version), documentation_url)
+    }
+
+    /// List of godot classes bundled with this crate for `godot_version`.
+    pub(crate) fn bundled_classes(godot_version: GodotVersion) -> &'static [&'static str] {
+        match godot_version {
+            GodotVersion::Version32 => GODOT_CLASSES_3_2,
+            GodotVersion::Version33 => GODOT_CLASSES_3_3,
+            GodotVersion::Version34 => GODOT_CLASSES_3_4,
+            GodotVersion::Version35 => GODOT_CLASSES_3_5,
+            GodotVersion::Version40 => GODOT_CLASSES_4_0,
+            GodotVersion::Version41 => GODOT_CLASSES_4_1,
+            GodotVersion::Version42 => GODOT_CLASSES_4_2,
+            GodotVersion::Version43 => GODOT_CLASSES_4_3,
+        }
+    }
+
+    /// Links to the given godot `classes` and to the godot constants, in the
+    /// documentation at `documentation_url`.
+    fn godot_items_from<S: AsRef<str>>(
+        classes: &[S],
+        documentation_url: &str,
+    ) -> HashMap<String, String> {
+        let mut godot_items = HashMap::new();
         for class in classes {
+            let class = class.as_ref();
             godot_items.insert(
                 class.to_string(),
                 format!("{}/class_{}.html", documentation_url, class.to_lowercase()),
@@ -110,24 +359,75 @@ impl Resolver {
         godot_items
     }
 
-    fn rust_to_godot() -> HashMap<String, String> {
+    fn rust_to_godot(godot_version: GodotVersion) -> HashMap<String, String> {
         let mut rust_to_godot = HashMap::new();
         for (rust, godot) in RUST_TO_GODOT {
             rust_to_godot.insert(rust.to_string(), godot.to_string());
         }
+        if godot_version >= GodotVersion::Version40 {
+            for (rust, godot) in RUST_TO_GODOT_4 {
+                rust_to_godot.insert(rust.to_string(), godot.to_string());
+            }
+        }
         rust_to_godot
     }
 
+    /// Replace the bundled list of godot classes by `classes`, linking to the
+    /// documentation of the godot `version` (like `"4.5"` or `"stable"`).
+    ///
+    /// See [`ConfigFile::godot_classes_file`].
+    pub(crate) fn set_godot_classes<S: AsRef<str>>(&mut self, classes: &[S], version: &str) {
+        let documentation_url = format!("https://docs.godotengine.org/en/{}/classes", version);
+        self.godot_items = Self::godot_items_from(classes, &documentation_url);
+    }
+
+    /// Replace the `{crate_name}` and `{crate_version}` placeholders in the URLs of
+    /// [`url_overrides`](Self::url_overrides) and [`rust_docs`](Self::rust_docs).
+    pub(crate) fn replace_placeholders(&mut self, placeholders: &Placeholders) {
+        for url in self
+            .url_overrides
+            .values_mut()
+            .chain(self.rust_docs.values_mut())
+        {
+            *url = placeholders.replace(url);
+        }
+    }
+
     pub(crate) fn apply_user_config(&mut self, user_config: &ConfigFile) {
         self.url_overrides = user_config.url_overrides.clone().unwrap_or_default();
         self.rename_classes = user_config.rename_classes.clone().unwrap_or_default();
+        self.rename_methods = user_config.rename_methods.clone().unwrap_or_default();
+        self.rename_properties = user_config.rename_properties.clone().unwrap_or_default();
+        if let Some(rust_to_godot) = &user_config.rust_to_godot {
+            self.rust_to_godot.extend(rust_to_godot.clone());
+        }
+        self.rust_docs = user_config
+            .rust_docs
+            .iter()
+            .flatten()
+            .map(|(krate, url)| {
+                let url = if !url.is_empty() {
+                    url.trim_end_matches('/').to_string()
+                } else if STANDARD_CRATES.contains(&krate.as_str()) {
+                    format!("https://doc.rust-lang.org/{}", krate)
+                } else {
+                    format!(
+                        "https://docs.rs/{0}/latest/{1}",
+                        krate,
+                        krate.replace('-', "_")
+                    )
+                };
+                (krate.replace('-', "_"), url)
+            })
+            .collect();
     }
 
     /// Convert all type names from Rust to Godot.
     ///
     /// This will convert `i32` to `int`, `Int32Array` to `PoolIntArray`...
     ///
-    /// See [`ConfigFile::rename_classes`] for user-defined renaming.
+    /// See [`ConfigFile::rename_classes`] for user-defined renaming. Methods and
+    /// properties are also renamed, see [`rename_members`](Self::rename_members).
     pub(crate) fn rename_classes(&self, documentation: &mut Documentation) {
         let replace = |name: &mut String| {
             if let Some(rename) = self.rename_classes.get(name) {
@@ -140,6 +440,7 @@ impl Resolver {
         let mut renamed_classes = HashMap::new();
         let classes = std::mem::take(&mut documentation.classes);
         for (mut name, mut class) in classes {
+            self.rename_members(&name, &mut class);
             for method in &mut class.methods {
                 for (_, typ, _) in &mut method.parameters {
                     match typ {
@@ -169,19 +470,240 @@ impl Resolver {
         documentation.classes = renamed_classes;
     }
 
+    /// Rename the methods and properties of `class`, whose Rust name is `name`.
+    ///
+    /// See [`ConfigFile::rename_methods`] and [`ConfigFile::rename_properties`].
+    /// The previous name of a method becomes one of its aliases, so that links to
+    /// it still resolve.
+    fn rename_members(&self, name: &str, class: &mut documentation::GdnativeClass) {
+        let godot_name = self.rename_classes.get(name).map_or(name, String::as_str);
+        let new_name = |renames: &HashMap<String, String>, member: &str| {
+            renames
+                .get(&format!("{}::{}", name, member))
+                .or_else(|| renames.get(&format!("{}::{}", godot_name, member)))
+                .or_else(|| renames.get(member))
+                .cloned()
+        };
+        for method in &mut class.methods {
+            let Some(new_name) = new_name(&self.rename_methods, &method.name) else {
+                continue;
+            };
+            for property in &mut class.properties {
+                for accessor in [&mut property.getter, &mut property.setter] {
+                    if accessor.as_ref() == Some(&method.name) {
+                        *accessor = Some(new_name.clone());
+                    }
+                }
+            }
+            let old_name = std::mem::replace(&mut method.name, new_name);
+            method.aliases.push(old_name);
+        }
+        for property in &mut class.properties {
+            let Some(new_name) = new_name(&self.rename_properties, &property.name) else {
+                continue;
+            };
+            for method in &mut class.methods {
+                if method.accessor_of.as_ref() == Some(&property.name) {
+                    method.accessor_of = Some(new_name.clone());
+                }
+            }
+            property.name = new_name;
+        }
+    }
+
+    /// Record the aliases of the classes and methods in `documentation`.
+    ///
+    /// Class aliases refer to the keys of [`Documentation::classes`]: this must be
+    /// called after [`rename_classes`](Self::rename_classes), so that they refer
+    /// to the renamed classes.
+    pub(crate) fn add_aliases(&mut self, documentation: &Documentation) {
+        let mut add_alias = |alias: &str, name: &str| {
+            if documentation.classes.contains_key(alias) {
+                diagnostic!(
+                    Lint::ConflictingAlias,
+                    "alias '{alias}' of '{name}' is already the name of a class, ignoring it"
+                );
+                return;
+            }
+            if let Some(previous) = self.aliases.insert(alias.to_string(), name.to_string()) {
+                if previous != name {
+                    diagnostic!(
+                        Lint::ConflictingAlias,
+                        "alias '{alias}' is used by both '{previous}' and '{name}'"
+                    );
+                }
+            }
+        };
+        for (name, class) in &documentation.classes {
+            for alias in &class.aliases {
+                add_alias(alias, name)
+            }
+            for method in &class.methods {
+                for alias in &method.aliases {
+                    add_alias(alias, &method.name)
+                }
+            }
+        }
+    }
+
+    /// Record the links to the pages of the classes in `documentation` (and to
+    /// their members), with the given `extension`.
+    ///
+    /// This must be called after [`rename_classes`](Self::rename_classes), so that
+    /// links refer to the renamed classes.
+    pub(crate) fn add_documented_classes(
+        &mut self,
+        documentation: &Documentation,
+        extension: &str,
+    ) {
+        self.documented_classes = documentation
+            .classes
+            .keys()
+            .map(|name| {
+                let link = format!("./{}.{}", super::class_file_stem(name), extension);
+                (name.clone(), link)
+            })
+            .collect();
+        self.documented_members.clear();
+        for (name, class) in &documentation.classes {
+            let page = &self.documented_classes[name];
+            let methods = class.methods.iter().map(|method| (&method.name, "func"));
+            let properties = class.properties.iter().map(|p| (&p.name, "property"));
+            for (member, kind) in methods.chain(properties) {
+                let id = format!("{}-{}", kind, member);
+                self.documented_members
+                    .insert(format!("{}::{}", name, member), format!("{}#{}", page, id));
+            }
+        }
+        self.inherited_classes = documentation
+            .classes
+            .keys()
+            .map(|name| {
+                let inherited = (documentation.inheritance_chain(name).into_iter())
+                    .filter(|parent| documentation.classes.contains_key(*parent))
+                    .map(String::from)
+                    .collect();
+                (name.clone(), inherited)
+            })
+            .collect();
+    }
+
+    /// Copy of this resolver for the page of the documented class `name`.
+    ///
+    /// See [`current_class`](Self::current_class).
+    pub(crate) fn for_class(&self, name: &str) -> Self {
+        Self {
+            current_class: Some(name.to_string()),
+            ..self.clone()
+        }
+    }
+
+    /// Link to `member` in the [current class](Self::current_class), like
+    /// `#func-run`, or in a documented class it inherits, like
+    /// `./Base.md#func-run`.
+    fn resolve_own_member(&self, member: &str) -> Option<&str> {
+        let class = self.current_class.as_deref()?;
+        if let Some(link) = self.resolve_member(class, member) {
+            // the member is on the current page
+            return link.find('#').map(|anchor| &link[anchor..]);
+        }
+        let inherited = self.inherited_classes.get(class)?;
+        inherited
+            .iter()
+            .find_map(|parent| self.resolve_member(parent, member))
+    }
+
+    /// Link to `member` in `class`, like `./MyClass.md#func-run`.
+    fn resolve_member(&self, class: &str, member: &str) -> Option<&str> {
+        let class = self.aliases.get(class).map_or(class, String::as_str);
+        // the member may be both the name of a member and the alias of another
+        let aliased = self.aliases.get(member).map_or(member, String::as_str);
+        [member, aliased].into_iter().find_map(|member| {
+            self.documented_members
+                .get(&format!("{}::{}", class, member))
+                .map(String::as_str)
+        })
+    }
+
     /// Resolve a name to the location it must link to.
     ///
     /// `link` must already have been stripped off the enclosing \`.
-    pub fn resolve(&self, link: &str) -> Option<&str> {
+    ///
+    /// `link` may start with a rustdoc-style disambiguator (see
+    /// [`Disambiguator`]), so that it only resolves to an item of this kind.
+    pub fn resolve(&self, link: &str) -> Option<Cow<'_, str>> {
+        let (disambiguator, link) = Disambiguator::strip(link);
+        let is_member = |url: &str| url.contains("#func-") || url.contains("#property-");
+        let resolved = match disambiguator {
+            None => self.resolve_item(link),
+            Some(Disambiguator::Type) => self.resolve_item(link).filter(|url| !is_member(url)),
+            Some(Disambiguator::Method | Disambiguator::Property) => {
+                let id = match disambiguator {
+                    Some(Disambiguator::Method) => "#func-",
+                    _ => "#property-",
+                };
+                // a lone name refers to a member of the current class
+                let resolved = if link.contains("::") {
+                    self.resolve_item(link)
+                } else {
+                    self.resolve_item(&format!("Self::{}", link))
+                };
+                return match resolved.filter(|url| url.contains(id)) {
+                    Some(url) => Some(Cow::Borrowed(url)),
+                    None => self
+                        .resolve_godot_member(link, disambiguator)
+                        .map(Cow::Owned),
+                };
+            }
+        };
+        if let Some(link) = resolved {
+            return Some(Cow::Borrowed(link));
+        }
+        if disambiguator.is_none() {
+            if let Some(link) = self.resolve_godot_member(link, None) {
+                return Some(Cow::Owned(link));
+            }
+        }
+        self.hooks
+            .iter()
+            .find_map(|hook| hook.resolve(link))
+            .or_else(|| self.resolve_rust_item(link))
+            .map(Cow::Owned)
+    }
+
+    /// Resolve a link to a godot item, or to an item documented in this crate.
+    fn resolve_item(&self, link: &str) -> Option<&str> {
         if let Some(link) = self.url_overrides.get(link) {
             return Some(link);
         }
+        if let Some(member) = link.strip_prefix("Self::") {
+            return self.resolve_own_member(member);
+        }
+        if let Some(link) = self.documented_classes.get(link) {
+            return Some(link);
+        }
+        if let Some(link) = self.documented_members.get(link) {
+            return Some(link);
+        }
+        if let Some(link) = self.resolve_own_member(link) {
+            if link.contains("#property-") {
+                return Some(link);
+            }
+        }
         let temporary;
         let base = if let Ok(link) = syn::parse_str::<syn::Path>(link) {
-            match link.segments.last() {
+            let mut segments = link.segments.iter().rev();
+            match segments.next() {
                 None => return None,
                 Some(base) => {
                     temporary = base.ident.to_string();
+                    // `Class::method`
+                    if let Some(class) = segments.next() {
+                        let class = class.ident.to_string();
+                        if let Some(link) = self.resolve_member(&class, &temporary) {
+                            return Some(link);
+                        }
+                    }
                     &temporary
                 }
             }
@@ -189,8 +711,14 @@ impl Resolver {
             link
         };
 
+        let base = match self.aliases.get(base) {
+            Some(name) => name.as_str(),
+            None => base,
+        };
         if let Some(path) = self.url_overrides.get(base) {
             Some(path)
+        } else if let Some(path) = self.documented_classes.get(base) {
+            Some(path)
         } else {
             let base = match self.rust_to_godot.get(base) {
                 Some(base) => base.as_str(),
@@ -204,28 +732,89 @@ impl Resolver {
         }
     }
 
-    /// Increase the header count, and resolve link destinations
-    pub(super) fn resolve_event(&self, event: &mut Event) {
-        use pulldown_cmark::HeadingLevel;
-        fn increase_heading_level(level: HeadingLevel) -> HeadingLevel {
-            match level {
-                HeadingLevel::H1 => HeadingLevel::H4,
-                HeadingLevel::H2 => HeadingLevel::H5,
-                HeadingLevel::H3 | HeadingLevel::H4 | HeadingLevel::H5 | HeadingLevel::H6 => {
-                    HeadingLevel::H6
-                }
-            }
+    /// Resolve a link to a member of a godot class, like `Node.add_child` or
+    /// `Rect2::size`, to its anchor in the class page (like
+    /// `class_node.html#class-node-method-add-child`).
+    ///
+    /// The kind of the member is given by `disambiguator`, or by
+    /// [`godot_members`](Self::godot_members). Otherwise, names in
+    /// `SCREAMING_CASE` are constants, names starting with a verb (like `add` or
+    /// `get`) are methods, and other names are properties.
+    fn resolve_godot_member(
+        &self,
+        link: &str,
+        disambiguator: Option<Disambiguator>,
+    ) -> Option<String> {
+        let (class, member) = link.split_once("::").or_else(|| link.split_once('.'))?;
+        let is_identifier = |name: &str| {
+            !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        };
+        if !is_identifier(member) {
+            return None;
         }
-        match event {
-            Event::Start(Tag::Link(_, dest, _)) | Event::End(Tag::Link(_, dest, _)) => {
-                if let Some(new_dest) = self.resolve(dest) {
-                    *dest = new_dest.to_string().into()
+        let class = self.rust_to_godot.get(class).map_or(class, String::as_str);
+        let page = self
+            .godot_items
+            .get(class)
+            .filter(|page| !page.contains('#'))?;
+        let kind = match disambiguator {
+            Some(Disambiguator::Method) => "method",
+            Some(Disambiguator::Property) => "property",
+            Some(Disambiguator::Type) => return None,
+            None => match self.godot_members.get(&format!("{}.{}", class, member)) {
+                Some(kind) => kind.as_str(),
+                None if member.chars().all(|c| !c.is_ascii_lowercase()) => "constant",
+                None if GODOT_METHOD_VERBS
+                    .contains(&member.split('_').next().unwrap_or_default()) =>
+                {
+                    "method"
                 }
+                None => "property",
+            },
+        };
+        Some(format!(
+            "{}#class-{}-{}-{}",
+            page,
+            class.to_lowercase().trim_start_matches('@'),
+            kind,
+            member.to_lowercase().replace('_', "-")
+        ))
+    }
+
+    /// Resolve a link to the documentation of a Rust item, if its crate is in
+    /// [`rust_docs`](Self::rust_docs).
+    ///
+    /// Paths starting with the crate's name link to a search in its
+    /// documentation, and common types of the standard library (like `HashMap`)
+    /// link to their page.
+    fn resolve_rust_item(&self, link: &str) -> Option<String> {
+        let path = syn::parse_str::<syn::Path>(link).ok()?;
+        let segments: Vec<String> = path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect();
+        let (krate, rest) = match segments.split_first()? {
+            (krate, rest) if !rest.is_empty() => (krate.as_str(), rest),
+            _ => ("std", segments.as_slice()),
+        };
+        let url = self.rust_docs.get(krate)?;
+        let name = rest.last()?;
+        if STANDARD_CRATES.contains(&krate) {
+            if let Some((_, page)) = STANDARD_TYPES.iter().find(|(standard, _)| standard == name) {
+                return Some(format!("{}/{}", url, page));
             }
-            Event::Start(Tag::Heading(n, _, _)) | Event::End(Tag::Heading(n, _, _)) => {
-                *n = increase_heading_level(*n);
+        }
+        // other names are too ambiguous without their crate
+        (segments.len() > 1).then(|| format!("{}/?search={}", url, rest.join("::")))
+    }
+
+    /// Resolve link destinations
+    pub(super) fn resolve_event(&self, event: &mut Event) {
+        if let Event::Start(Tag::Link(_, dest, _)) | Event::End(Tag::Link(_, dest, _)) = event {
+            if let Some(new_dest) = self.resolve(dest) {
+                *dest = new_dest.to_string().into()
             }
-            _ => {}
         }
     }
 
@@ -238,7 +827,7 @@ impl Resolver {
         let mut events = match self.resolve(type_name).map(|return_link| {
             Tag::Link(
                 pulldown_cmark::LinkType::Shortcut,
-                CowStr::Borrowed(return_link),
+                CowStr::from(return_link),
                 CowStr::Borrowed(""),
             )
         }) {
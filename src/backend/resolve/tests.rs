@@ -0,0 +1,364 @@
+use super::*;
+use crate::documentation::{
+    tests::{documentation, method},
+    Property,
+};
+
+#[test]
+fn documented_class_links() {
+    let mut documentation = documentation(vec![
+        ("MyClass", vec![method("run", &[], Type::Unit)]),
+        (
+            "graph::Node",
+            vec![method("connect_points", &[], Type::Unit)],
+        ),
+        ("Child", Vec::new()),
+    ]);
+    documentation.classes.get_mut("Child").unwrap().inherit = String::from("MyClass");
+    let class = documentation.classes.get_mut("MyClass").unwrap();
+    class.methods[0].aliases = vec![String::from("go")];
+    class.properties.push(Property {
+        name: String::from("speed"),
+        typ: Type::Named(String::from("f32")),
+        default: None,
+        getter: None,
+        setter: None,
+        documentation: String::new(),
+        summary: String::new(),
+        tags: Vec::new(),
+        deprecated: None,
+        line: 0,
+    });
+    let mut resolver = Resolver::new(GodotVersion::Version35);
+    resolver.add_aliases(&documentation);
+    resolver.add_documented_classes(&documentation, "html");
+    assert_eq!(
+        resolver.resolve("MyClass").as_deref(),
+        Some("./MyClass.html")
+    );
+    assert_eq!(
+        resolver.resolve("crate::MyClass").as_deref(),
+        Some("./MyClass.html")
+    );
+    assert_eq!(
+        resolver.resolve("graph::Node").as_deref(),
+        Some("./graph-Node.html")
+    );
+    assert_eq!(
+        resolver.resolve("MyClass::run").as_deref(),
+        Some("./MyClass.html#func-run")
+    );
+    assert_eq!(
+        resolver.resolve("crate::MyClass::go").as_deref(),
+        Some("./MyClass.html#func-run")
+    );
+    assert_eq!(
+        resolver.resolve("graph::Node::connect_points").as_deref(),
+        Some("./graph-Node.html#func-connect_points")
+    );
+    assert_eq!(resolver.resolve("MyClass::jump").as_deref(), None);
+    assert_eq!(
+        resolver.resolve("MyClass::speed").as_deref(),
+        Some("./MyClass.html#property-speed")
+    );
+    assert_eq!(resolver.resolve("run").as_deref(), None);
+    assert_eq!(
+        resolver.resolve("method@MyClass::run").as_deref(),
+        Some("./MyClass.html#func-run")
+    );
+    assert_eq!(resolver.resolve("fn@speed").as_deref(), None);
+    // `Self` and bare names are only known on the page of a class
+    assert_eq!(resolver.resolve("Self::run").as_deref(), None);
+    assert_eq!(resolver.resolve("speed").as_deref(), None);
+    let my_class = resolver.for_class("MyClass");
+    assert_eq!(my_class.resolve("Self::run").as_deref(), Some("#func-run"));
+    assert_eq!(
+        my_class.resolve("Self::speed").as_deref(),
+        Some("#property-speed")
+    );
+    assert_eq!(
+        my_class.resolve("speed").as_deref(),
+        Some("#property-speed")
+    );
+    assert_eq!(my_class.resolve("run").as_deref(), None);
+    assert_eq!(my_class.resolve("fn@run").as_deref(), Some("#func-run"));
+    assert_eq!(my_class.resolve("go()").as_deref(), Some("#func-run"));
+    assert_eq!(
+        my_class.resolve("field@speed").as_deref(),
+        Some("#property-speed")
+    );
+    assert_eq!(my_class.resolve("Self::connect_points").as_deref(), None);
+    let node = resolver.for_class("graph::Node");
+    assert_eq!(
+        node.resolve("Self::connect_points").as_deref(),
+        Some("#func-connect_points")
+    );
+    assert_eq!(node.resolve("speed").as_deref(), None);
+    assert_eq!(node.resolve("field@speed").as_deref(), None);
+    // members of the documented parent classes link to their page
+    let child = resolver.for_class("Child");
+    assert_eq!(
+        child.resolve("speed").as_deref(),
+        Some("./MyClass.html#property-speed")
+    );
+    assert_eq!(
+        child.resolve("Self::run").as_deref(),
+        Some("./MyClass.html#func-run")
+    );
+    assert_eq!(resolver.resolve("struct@MyClass::run").as_deref(), None);
+    assert_eq!(
+        resolver.resolve("struct@MyClass").as_deref(),
+        Some("./MyClass.html")
+    );
+    assert_eq!(
+        resolver.resolve("Node").as_deref(),
+        Some("https://docs.godotengine.org/en/3.5/classes/class_node.html")
+    );
+}
+
+#[test]
+fn renamed_class_aliases() {
+    let mut documentation = documentation(vec![
+        ("RustName", Vec::new()),
+        ("graph::Interface", Vec::new()),
+    ]);
+    documentation.classes.get_mut("RustName").unwrap().aliases = vec![String::from("Alias")];
+    documentation
+        .classes
+        .get_mut("graph::Interface")
+        .unwrap()
+        .aliases = vec![String::from("GraphInterface")];
+    let mut resolver = Resolver::new(GodotVersion::Version35);
+    resolver.apply_user_config(&ConfigFile {
+        rename_classes: Some(HashMap::from([(
+            String::from("RustName"),
+            String::from("GodotName"),
+        )])),
+        ..Default::default()
+    });
+    resolver.rename_classes(&mut documentation);
+    resolver.add_aliases(&documentation);
+    resolver.add_documented_classes(&documentation, "md");
+    assert_eq!(resolver.resolve("Alias").as_deref(), Some("./GodotName.md"));
+    assert_eq!(
+        resolver.resolve("GraphInterface").as_deref(),
+        Some("./graph-Interface.md")
+    );
+}
+
+#[test]
+fn rust_docs_links() {
+    let mut resolver = Resolver::new(GodotVersion::Version35);
+    resolver.apply_user_config(&ConfigFile {
+        rust_docs: Some(HashMap::from([
+            (String::from("std"), String::new()),
+            (String::from("my-dep"), String::new()),
+        ])),
+        ..Default::default()
+    });
+    assert_eq!(
+        resolver.resolve("HashMap").as_deref(),
+        Some("https://doc.rust-lang.org/std/collections/struct.HashMap.html")
+    );
+    assert_eq!(
+        resolver.resolve("std::sync::Arc").as_deref(),
+        Some("https://doc.rust-lang.org/std/sync/struct.Arc.html")
+    );
+    assert_eq!(
+        resolver.resolve("my_dep::graph::Graph").as_deref(),
+        Some("https://docs.rs/my-dep/latest/my_dep/?search=graph::Graph")
+    );
+    assert_eq!(resolver.resolve("Graph"), None);
+    assert_eq!(resolver.resolve("serde::Serialize"), None);
+}
+
+#[test]
+fn godot_4_classes() {
+    let resolver = Resolver::new(GodotVersion::Version42);
+    assert_eq!(
+        resolver.resolve("EditorPlugin").as_deref(),
+        Some("https://docs.godotengine.org/en/4.2/classes/class_editorplugin.html")
+    );
+    assert_eq!(resolver.resolve("TileMapLayer"), None);
+    let resolver = Resolver::new(GodotVersion::Version43);
+    assert_eq!(
+        resolver.resolve("TileMapLayer").as_deref(),
+        Some("https://docs.godotengine.org/en/4.3/classes/class_tilemaplayer.html")
+    );
+    assert_eq!(
+        resolver.resolve("Parallax2D").as_deref(),
+        Some("https://docs.godotengine.org/en/4.3/classes/class_parallax2d.html")
+    );
+}
+
+#[test]
+fn resolve_hooks() {
+    let mut resolver = Resolver::new(GodotVersion::Version35);
+    resolver.hooks.push(Arc::new(|link: &str| {
+        Some(format!("https://wiki.example.com/{}", link))
+    }));
+    assert_eq!(
+        resolver.resolve("Node").as_deref(),
+        Some("https://docs.godotengine.org/en/3.5/classes/class_node.html")
+    );
+    assert_eq!(
+        resolver.resolve("struct@Grid").as_deref(),
+        Some("https://wiki.example.com/Grid")
+    );
+}
+
+#[test]
+fn godot_member_links() {
+    use crate::godot_classes::read_godot_classes;
+
+    let mut resolver = Resolver::new(GodotVersion::Version35);
+    let link = |resolver: &Resolver, link: &str| resolver.resolve(link).map(|url| url.into_owned());
+    let node = "https://docs.godotengine.org/en/3.5/classes/class_node.html";
+    assert_eq!(
+        link(&resolver, "Node.add_child"),
+        Some(format!("{}#class-node-method-add-child", node))
+    );
+    assert_eq!(
+        link(&resolver, "Rect2.size"),
+        Some(String::from(
+            "https://docs.godotengine.org/en/3.5/classes/class_rect2.html#class-rect2-property-size"
+        ))
+    );
+    assert_eq!(
+        link(&resolver, "Node::NOTIFICATION_READY"),
+        Some(format!("{}#class-node-constant-notification-ready", node))
+    );
+    assert_eq!(
+        link(&resolver, "Node.queue_free()"),
+        Some(format!("{}#class-node-method-queue-free", node))
+    );
+    assert_eq!(
+        link(&resolver, "property@Node.owner"),
+        Some(format!("{}#class-node-property-owner", node))
+    );
+    assert_eq!(link(&resolver, "struct@Node.add_child"), None);
+    assert_eq!(link(&resolver, "Graph.add_point"), None);
+
+    // the kinds of the members are read from class references
+    let directory = tempfile::tempdir().unwrap();
+    std::fs::write(
+        directory.path().join("Node.xml"),
+        r#"<class name="Node" inherits="Object">
+        <methods><method name="print_tree"><return type="void" /></method></methods>
+        <members><member name="name" type="String" setter="set_name" getter="get_name"></member></members>
+        <signals><signal name="ready"></signal></signals>
+        </class>"#,
+    )
+    .unwrap();
+    let classes = read_godot_classes(directory.path()).unwrap();
+    assert_eq!(classes.names, ["Node"]);
+    resolver.set_godot_classes(&classes.names, "stable");
+    resolver.godot_members = classes.members;
+    let node = "https://docs.godotengine.org/en/stable/classes/class_node.html";
+    assert_eq!(
+        link(&resolver, "Node.ready"),
+        Some(format!("{}#class-node-signal-ready", node))
+    );
+    assert_eq!(
+        link(&resolver, "Node.print_tree"),
+        Some(format!("{}#class-node-method-print-tree", node))
+    );
+}
+
+#[test]
+fn user_rust_to_godot() {
+    let mut documentation = documentation(vec![(
+        "MyClass",
+        vec![method(
+            "get_point",
+            &[("id", "PointId")],
+            Type::Named(String::from("i64")),
+        )],
+    )]);
+    let mut resolver = Resolver::new(GodotVersion::Version35);
+    resolver.apply_user_config(&ConfigFile {
+        rust_to_godot: Some(HashMap::from([
+            (String::from("PointId"), String::from("int")),
+            (String::from("i64"), String::from("float")),
+        ])),
+        ..Default::default()
+    });
+    resolver.rename_classes(&mut documentation);
+    let method = &documentation.classes["MyClass"].methods[0];
+    assert_eq!(method.parameters[0].1, Type::Named(String::from("int")));
+    assert_eq!(method.return_type, Type::Named(String::from("float")));
+    assert_eq!(
+        resolver.resolve("PointId").as_deref(),
+        Some("https://docs.godotengine.org/en/3.5/classes/class_int.html")
+    );
+}
+
+#[test]
+fn rename_members() {
+    let mut documentation = documentation(vec![
+        (
+            "MyClass",
+            vec![
+                method("get_speed", &[], Type::Unit),
+                method("set_terrain_for_point", &[], Type::Unit),
+            ],
+        ),
+        ("Other", vec![method("get_speed", &[], Type::Unit)]),
+    ]);
+    let class = documentation.classes.get_mut("MyClass").unwrap();
+    class.methods[0].accessor_of = Some(String::from("speed"));
+    class.properties.push(Property {
+        name: String::from("speed"),
+        typ: Type::Named(String::from("f32")),
+        default: None,
+        getter: Some(String::from("get_speed")),
+        setter: None,
+        documentation: String::new(),
+        summary: String::new(),
+        tags: Vec::new(),
+        deprecated: None,
+        line: 0,
+    });
+    let mut resolver = Resolver::new(GodotVersion::Version35);
+    resolver.apply_user_config(&ConfigFile {
+        rename_classes: Some(HashMap::from([(
+            String::from("MyClass"),
+            String::from("Graph"),
+        )])),
+        rename_methods: Some(HashMap::from([
+            (
+                String::from("set_terrain_for_point"),
+                String::from("set_terrain"),
+            ),
+            (
+                String::from("Graph::get_speed"),
+                String::from("get_velocity"),
+            ),
+        ])),
+        rename_properties: Some(HashMap::from([(
+            String::from("MyClass::speed"),
+            String::from("velocity"),
+        )])),
+        ..Default::default()
+    });
+    resolver.rename_classes(&mut documentation);
+    let class = &documentation.classes["Graph"];
+    assert_eq!(class.methods[0].name, "get_velocity");
+    assert_eq!(class.methods[0].accessor_of.as_deref(), Some("velocity"));
+    assert_eq!(class.methods[1].name, "set_terrain");
+    assert_eq!(class.methods[1].aliases, ["set_terrain_for_point"]);
+    assert_eq!(class.properties[0].name, "velocity");
+    assert_eq!(class.properties[0].getter.as_deref(), Some("get_velocity"));
+    assert_eq!(documentation.classes["Other"].methods[0].name, "get_speed");
+
+    resolver.add_aliases(&documentation);
+    resolver.add_documented_classes(&documentation, "md");
+    assert_eq!(
+        resolver.resolve("Graph::set_terrain_for_point").as_deref(),
+        Some("./Graph.md#func-set_terrain")
+    );
+    assert_eq!(
+        resolver.resolve("Other::get_speed").as_deref(),
+        Some("./Other.md#func-get_speed")
+    );
+}
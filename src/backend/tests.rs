@@ -0,0 +1,1754 @@
+use super::*;
+use crate::{
+    documentation::tests::{documentation, visit},
+    Builder, ConfigFile, Error, GodotVersion, HtmlConfig, HtmlTheme, MarkdownConfig,
+};
+use std::{fs, path::PathBuf};
+
+/// Files generated by `backend` for `documentation`.
+fn generate(
+    backend: BuiltinBackend,
+    documentation: Documentation,
+    config: ConfigFile,
+) -> HashMap<String, String> {
+    let mut files = Builder::new()
+        .user_config(config)
+        .with_documentation(documentation)
+        .add_backend(backend, PathBuf::from("doc"))
+        .build_to_memory()
+        .unwrap();
+    files.remove(Path::new("doc")).unwrap()
+}
+
+/// Documentation of a small crate, exercising most of the markdown features.
+fn graph() -> Documentation {
+    let mut documentation = visit(
+        r#"
+        /// A graph of [`Point`]s.
+        ///
+        /// Points are connected with [`connect_points`](Self::connect_points):
+        /// - **weighted** edges
+        /// - `one-way` edges
+        ///
+        /// ```gdscript
+        /// var graph = Graph.new()
+        /// assert_eq(graph.size, 0)
+        /// ```
+        #[derive(NativeClass)]
+        #[inherit(Reference)]
+        pub struct Graph {
+            /// Number of points in the graph.
+            #[property(default = 0)]
+            size: i32,
+        }
+
+        #[methods]
+        impl Graph {
+            /// Connect `from` to `to`.
+            ///
+            /// Returns `false` if one of the points does not exist, and increments
+            /// [`size`](Self::size) otherwise.
+            #[method]
+            pub fn connect_points(&self, from: i32, to: i32, weight: Option<f32>) -> bool {
+                true
+            }
+
+            /// Remove every point.
+            #[method]
+            pub fn clear(&mut self, #[base] owner: &Reference) {}
+        }
+
+        /// A point of a [`Graph`].
+        #[derive(NativeClass)]
+        #[inherit(Node2D)]
+        pub struct Point;
+        "#,
+    );
+    documentation.root_documentation =
+        String::from(" Graphs for godot.\n\n See [`Graph`] to get started.");
+    documentation
+}
+
+#[test]
+fn backend_options() {
+    let config = ConfigFile {
+        opening_comment: Some(false),
+        markdown: Some(MarkdownConfig {
+            opening_comment: Some(true),
+            heading_offset: Some(2),
+            table_of_contents: Some(true),
+            inline_links: Some(true),
+            ..Default::default()
+        }),
+        html: Some(HtmlConfig {
+            heading_offset: Some(4),
+            theme: Some(HtmlTheme::Light),
+            ..Default::default()
+        }),
+        absolute_links: Some(HashMap::from([(
+            String::from("html"),
+            String::from("https://example.com/{crate_name}/"),
+        )])),
+        ..Default::default()
+    };
+    let options = GeneratorOptions::from_config(&config).unwrap();
+
+    let markdown = options.for_backend(Some(BuiltinBackend::Markdown), &config);
+    assert!(markdown.opening_comment);
+    assert!(markdown.table_of_contents);
+    assert_eq!(markdown.heading_offset, 2);
+    assert_eq!(markdown.absolute_links, None);
+    assert!(markdown.markdown.inline_links);
+
+    let mut html = options.for_backend(Some(BuiltinBackend::Html), &config);
+    assert!(!html.opening_comment);
+    assert!(html.table_of_contents);
+    assert_eq!(html.heading_offset, 4);
+    assert_eq!(html.html.theme, HtmlTheme::Light);
+    html.replace_placeholders(&Placeholders::new("my-crate", None));
+    assert_eq!(
+        html.absolute_links.as_deref(),
+        Some("https://example.com/my-crate/")
+    );
+
+    // the options of the markdown table apply to all the markdown backends
+    let custom = options.for_backend(None, &config);
+    assert!(!custom.opening_comment);
+    assert_eq!(custom.heading_offset, 2);
+    assert!(custom.table_of_contents);
+    assert_eq!(custom.absolute_links, None);
+    assert!(custom.markdown.inline_links);
+}
+
+#[test]
+fn deprecated() {
+    let source = r#"
+        /// A graph.
+        #[derive(NativeClass)]
+        #[inherit(Reference)]
+        pub struct Graph {
+            /// ```gdscript
+            /// assert_eq(Graph.new().size, 0)
+            /// ```
+            #[property]
+            #[deprecated(note = "Use `len`.")]
+            size: i32,
+        }
+
+        #[methods]
+        impl Graph {
+            /// ```gdscript
+            /// assert_true(Graph.new().connect_points())
+            /// ```
+            #[method]
+            #[deprecated = "Use `connect`."]
+            pub fn connect_points(&self) -> bool {
+                true
+            }
+
+            /// ```gdscript
+            /// assert_true(Graph.new().connect())
+            /// ```
+            #[method]
+            pub fn connect(&self) -> bool {
+                true
+            }
+        }
+
+        /// ```gdscript
+        /// assert_not_null(OldGraph.new())
+        /// ```
+        #[derive(NativeClass)]
+        #[inherit(Reference)]
+        #[deprecated]
+        pub struct OldGraph;
+    "#;
+    let documentation = visit(source);
+    let graph = &documentation.classes["Graph"];
+    assert_eq!(graph.deprecated, None);
+    assert_eq!(
+        graph.properties[0].deprecated.as_deref(),
+        Some("Use `len`.")
+    );
+    assert_eq!(
+        graph.methods[0].deprecated.as_deref(),
+        Some("Use `connect`.")
+    );
+    assert_eq!(graph.methods[1].deprecated, None);
+    assert_eq!(
+        documentation.classes["OldGraph"].deprecated.as_deref(),
+        Some("")
+    );
+
+    // deprecated items are struck through, with a notice
+    let markdown = generate(
+        BuiltinBackend::Markdown,
+        documentation.clone(),
+        ConfigFile::default(),
+    );
+    let graph = &markdown["Graph.md"];
+    assert!(graph.contains("[~~size~~](#property-size \"size\")"));
+    assert!(graph.contains("> **Deprecated:** Use `len`."));
+    assert!(graph.contains("func ~~connect_points~~() -> [bool]"));
+    assert!(graph.contains("> **Deprecated:** Use `connect`."));
+    assert!(graph.contains("func connect() -> [bool]"));
+    assert!(markdown["OldGraph.md"].contains("> **Deprecated:**\n"));
+
+    // and their examples are not tested
+    let gut = generate(BuiltinBackend::Gut, documentation, ConfigFile::default());
+    assert_eq!(gut.keys().collect::<Vec<_>>(), ["Graph.gd"]);
+    let graph = &gut["Graph.gd"];
+    assert!(graph.contains("func test_connect():"));
+    assert!(!graph.contains("size"));
+    assert!(!graph.contains("connect_points"));
+}
+
+#[test]
+fn deprecation_note_links() {
+    use crate::diagnostics::Lint;
+
+    let documentation = visit(
+        r#"
+        /// A graph.
+        #[derive(NativeClass)]
+        #[inherit(Reference)]
+        pub struct Graph;
+
+        #[methods]
+        impl Graph {
+            #[method]
+            #[deprecated(note = "Use [`connect`](Self::connect) or [`Edge`].")]
+            pub fn connect_points(&self) {}
+
+            #[method]
+            pub fn connect(&self) {}
+        }
+        "#,
+    );
+    let markdown = generate(
+        BuiltinBackend::Markdown,
+        documentation.clone(),
+        ConfigFile::default(),
+    );
+    assert!(markdown["Graph.md"]
+        .contains("> **Deprecated:** Use [`connect`](#func-connect) or [`Edge`].\n"));
+    let xml = generate(
+        BuiltinBackend::GodotXml,
+        documentation.clone(),
+        ConfigFile::default(),
+    );
+    assert!(xml["Graph.xml"]
+        .contains("[b]Deprecated:[/b] Use [method Graph.connect] or [[code]Edge[/code]]."));
+
+    let report = Builder::new()
+        .with_documentation(documentation)
+        .add_backend(BuiltinBackend::Markdown, PathBuf::from("doc"))
+        .dry_run()
+        .build_with_report()
+        .unwrap();
+    let broken_links: Vec<_> = (report.diagnostics.iter())
+        .filter(|diagnostic| diagnostic.lint == Lint::BrokenLink)
+        .map(|diagnostic| diagnostic.message.as_str())
+        .collect();
+    assert_eq!(
+        broken_links,
+        ["unresolved link to 'Edge' in method 'Graph.connect_points' (lib.rs:11)"]
+    );
+}
+
+#[test]
+fn inheritance() {
+    use crate::diagnostics::Lint;
+
+    let documentation = visit(
+        r#"
+        /// A graph.
+        #[derive(NativeClass)]
+        #[inherit(Reference)]
+        pub struct Graph;
+
+        /// A graph with weighted edges.
+        #[derive(NativeClass)]
+        #[inherit(Graph)]
+        pub struct WeightedGraph;
+
+        /// A graph with one-way edges.
+        #[derive(NativeClass)]
+        #[inherit(Graph)]
+        pub struct DirectedGraph;
+        "#,
+    );
+    let markdown = generate(
+        BuiltinBackend::Markdown,
+        documentation,
+        ConfigFile::default(),
+    );
+    assert!(markdown["Graph.md"].contains(
+        "**Inherit:** [Reference]\n\n**Inherited By:** [DirectedGraph](./DirectedGraph.md), [WeightedGraph](./WeightedGraph.md)\n"
+    ));
+    let weighted_graph = &markdown["WeightedGraph.md"];
+    assert!(weighted_graph.contains("**Inherit:** [Graph](./Graph.md) < [Reference]\n"));
+    assert!(!weighted_graph.contains("Inherited By"));
+
+    // a cycle stops the chain, with a diagnostic
+    let documentation = visit(
+        r#"
+        /// A node.
+        #[derive(NativeClass)]
+        #[inherit(Edge)]
+        pub struct Node;
+
+        /// An edge.
+        #[derive(NativeClass)]
+        #[inherit(Node)]
+        pub struct Edge;
+        "#,
+    );
+    let report = Builder::new()
+        .with_documentation(documentation.clone())
+        .add_backend(BuiltinBackend::Markdown, PathBuf::from("doc"))
+        .dry_run()
+        .build_with_report()
+        .unwrap();
+    let mut cycles: Vec<_> = (report.diagnostics.iter())
+        .filter(|diagnostic| diagnostic.lint == Lint::InheritanceCycle)
+        .map(|diagnostic| diagnostic.message.as_str())
+        .collect();
+    cycles.sort_unstable();
+    assert_eq!(
+        cycles,
+        [
+            "inheritance cycle detected for class 'Edge'",
+            "inheritance cycle detected for class 'Node'",
+        ]
+    );
+    let markdown = generate(
+        BuiltinBackend::Markdown,
+        documentation,
+        ConfigFile::default(),
+    );
+    assert!(markdown["Node.md"]
+        .contains("**Inherit:** [Edge](./Edge.md)\n\n**Inherited By:** [Edge](./Edge.md)\n"));
+}
+
+#[test]
+fn source_links() {
+    let mut documentation = documentation(Vec::new());
+    documentation.root_file = PathBuf::from("src/lib.rs");
+    let resolver = Resolver::new(GodotVersion::Version35);
+    let mut options = GeneratorOptions {
+        repository_url: Some(String::from("https://github.com/user/repo/blob/v1.0/src/")),
+        ..Default::default()
+    };
+    let generator = Generator::new(&resolver, &documentation, options.clone());
+    assert_eq!(
+        generator
+            .source_link(Path::new("src/graph/mod.rs"), 12)
+            .as_deref(),
+        Some("https://github.com/user/repo/blob/v1.0/src/graph/mod.rs#L12")
+    );
+    // unknown lines, and files outside of the crate, are not linked
+    assert_eq!(generator.source_link(Path::new("src/lib.rs"), 0), None);
+    assert_eq!(generator.source_link(Path::new("build.rs"), 3), None);
+
+    options.repository_url = None;
+    let generator = Generator::new(&resolver, &documentation, options);
+    assert_eq!(generator.source_link(Path::new("src/lib.rs"), 3), None);
+
+    let source = r#"
+        /// A graph.
+        #[derive(NativeClass)]
+        #[inherit(Reference)]
+        pub struct Graph;
+        "#;
+    let config = ConfigFile {
+        repository_url: Some(String::from("https://github.com/user/repo/blob/main")),
+        ..Default::default()
+    };
+    let markdown = generate(BuiltinBackend::Markdown, visit(source), config);
+    assert!(markdown["Graph.md"].contains(
+        "**Inherit:** [Reference]\n\n[source](https://github.com/user/repo/blob/main/lib.rs#L5)\n"
+    ));
+    let markdown = generate(
+        BuiltinBackend::Markdown,
+        visit(source),
+        ConfigFile::default(),
+    );
+    assert!(!markdown["Graph.md"].contains("[source]"));
+}
+
+#[test]
+fn referenced_by() {
+    let documentation = visit(
+        r#"
+        /// A graph.
+        #[derive(NativeClass)]
+        #[inherit(Reference)]
+        pub struct Graph;
+
+        #[methods]
+        impl Graph {
+            #[method]
+            pub fn add_point(&mut self, point: Point) {}
+
+            #[method]
+            pub fn center(&self) -> Option<Point> {
+                None
+            }
+
+            #[method]
+            pub fn clear(&mut self) {}
+        }
+
+        /// A point of a graph.
+        #[derive(NativeClass)]
+        #[inherit(Reference)]
+        pub struct Point;
+        "#,
+    );
+    let references: Vec<_> = (documentation.referenced_by("Point").into_iter())
+        .map(|(class, method)| (class, method.name.as_str()))
+        .collect();
+    assert_eq!(references, [("Graph", "add_point"), ("Graph", "center")]);
+    assert!(documentation.referenced_by("Graph").is_empty());
+
+    let markdown = generate(
+        BuiltinBackend::Markdown,
+        documentation,
+        ConfigFile::default(),
+    );
+    assert!(markdown["Point.md"].contains(
+        "## Referenced By\n- [Graph](./Graph.md): [add_point](./Graph.md#func-add_point)\n- [Graph](./Graph.md): [center](./Graph.md#func-center)\n"
+    ));
+    assert!(!markdown["Graph.md"].contains("Referenced By"));
+}
+
+#[test]
+fn method_tags() {
+    let documentation = visit(
+        r#"
+        /// A graph.
+        #[derive(NativeClass)]
+        #[inherit(Reference)]
+        pub struct Graph;
+
+        #[methods]
+        impl Graph {
+            /// Find the shortest path between `from` and `to`.
+            ///
+            /// @complexity `O(E + V log V)`, with a binary heap.
+            /// @requires [`add_point`](Self::add_point) for both points.
+            /// @since 1.2
+            #[method]
+            pub fn shortest_path(&self, from: i32, to: i32) -> bool {
+                true
+            }
+
+            /// Add a point.
+            #[method]
+            pub fn add_point(&mut self, id: i32) {}
+        }
+        "#,
+    );
+    let markdown = generate(
+        BuiltinBackend::Markdown,
+        documentation.clone(),
+        ConfigFile::default(),
+    );
+    insta::assert_snapshot!("method_tags_markdown", markdown["Graph.md"]);
+    let html = generate(BuiltinBackend::Html, documentation, ConfigFile::default());
+    let html = &html["Graph.html"];
+    // the description of the method, without the rest of the page
+    let start = html.find("<h3><a id=\"func-shortest_path\">").unwrap();
+    let end = start + html[start..].find("<h3><a id=\"func-add_point\">").unwrap();
+    insta::assert_snapshot!("method_tags_html", &html[start..end]);
+}
+
+#[test]
+fn tool_badge() {
+    let documentation = visit(
+        r#"
+        /// An editor plugin.
+        #[derive(NativeClass)]
+        #[inherit(EditorPlugin)]
+        pub struct Plugin;
+
+        /// A runtime node.
+        #[derive(NativeClass)]
+        #[inherit(Node)]
+        pub struct Runtime;
+        "#,
+    );
+    let markdown = generate(
+        BuiltinBackend::Markdown,
+        documentation.clone(),
+        ConfigFile::default(),
+    );
+    assert!(markdown["Plugin.md"].contains("\n# Plugin `tool`\n"));
+    assert!(markdown["Runtime.md"].contains("\n# Runtime\n"));
+
+    let html = generate(BuiltinBackend::Html, documentation, ConfigFile::default());
+    assert!(html["Plugin.html"].contains("Plugin <code>tool</code></h1>"));
+    assert!(!html["Runtime.html"].contains("<code>tool</code>"));
+}
+
+#[test]
+fn editor_metadata() {
+    let documentation = visit(
+        r#"
+        /// A graph.
+        #[derive(NativeClass)]
+        #[inherit(Reference)]
+        pub struct Graph;
+
+        /// A point.
+        #[derive(NativeClass)]
+        #[inherit(Reference)]
+        pub struct Point;
+        "#,
+    );
+    let config = ConfigFile {
+        rename_classes: Some(HashMap::from([(
+            String::from("Graph"),
+            String::from("GraphGd"),
+        )])),
+        editor_metadata: Some(true),
+        class_icons: Some(HashMap::from([(
+            String::from("Graph"),
+            String::from("res://icons/graph.svg"),
+        )])),
+        ..Default::default()
+    };
+    let markdown = generate(
+        BuiltinBackend::Markdown,
+        documentation.clone(),
+        config.clone(),
+    );
+    assert!(markdown["GraphGd.md"].contains(
+        "## Editor Registration\n\nAdd these lines to the `[resource]` section of the class's `.gdns` file:\n```ini\nclass_name = \"Graph\"\nscript_class_name = \"GraphGd\"\nscript_class_icon_path = \"res://icons/graph.svg\"\n```\n"
+    ));
+    // `Point` is not renamed, so it has no `script_class_name`
+    assert!(!markdown["Point.md"].contains("Editor Registration"));
+
+    let config = ConfigFile {
+        editor_metadata: Some(false),
+        ..config
+    };
+    let markdown = generate(BuiltinBackend::Markdown, documentation, config);
+    assert!(!markdown["GraphGd.md"].contains("Editor Registration"));
+}
+
+#[test]
+fn table_of_contents() {
+    let documentation = visit(
+        r#"
+        /// A graph.
+        #[derive(NativeClass)]
+        #[inherit(Reference)]
+        pub struct Graph {
+            /// Number of points in the graph.
+            #[property]
+            size: i32,
+        }
+
+        #[methods]
+        impl Graph {
+            /// Build a graph from a list of points.
+            #[method]
+            pub fn from_points() {}
+
+            /// Connect `from` to `to`.
+            #[method]
+            pub fn connect_points(&self, from: i32, to: i32) -> bool {
+                true
+            }
+
+            /// Remove every point.
+            #[method]
+            pub fn clear(&mut self) {}
+        }
+        "#,
+    );
+    let config = || ConfigFile {
+        markdown: Some(MarkdownConfig {
+            table_of_contents: Some(true),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let markdown = generate(BuiltinBackend::Markdown, documentation.clone(), config());
+    let graph = &markdown["Graph.md"];
+    assert!(graph.contains(
+        "**Inherit:** [Reference]\n\
+         - [Description](#description)\n\
+         - [Properties](#properties)\n    - [size](#property-size)\n\
+         - [Constructors](#constructors)\n    - [from_points](#func-from_points)\n\
+         - [Methods](#methods)\n    - [connect_points](#func-connect_points)\n    - [clear](#func-clear)\n\
+         ## <a id=\"description\"></a>Description\n"
+    ));
+    for anchor in [
+        "## <a id=\"properties\"></a>Properties\n",
+        "## <a id=\"constructors\"></a>Constructors\n",
+        "## <a id=\"methods\"></a>Methods\n",
+        "### <a id=\"property-size\"></a> size: [int]\n",
+        "### <a id=\"func-from_points\"></a>func from_points() -> void\n",
+        "### <a id=\"func-connect_points\"></a>func connect_points(",
+        "### <a id=\"func-clear\"></a>func clear() -> void\n",
+    ] {
+        assert!(graph.contains(anchor), "missing {anchor:?}");
+    }
+
+    // the html sidebar already lists the members, so there is no table of contents
+    let html = generate(BuiltinBackend::Html, documentation, config());
+    let graph = &html["Graph.html"];
+    assert!(!graph.contains("href=\"#description\""));
+    assert!(graph.contains("<h2>Description</h2>"));
+    assert!(graph.contains(
+        "<ul class=\"members\">\n\
+         <li><a href=\"#property-size\">size</a></li>\n\
+         <li><a href=\"#func-from_points\">from_points</a>()</li>\n\
+         <li><a href=\"#func-connect_points\">connect_points</a>()</li>\n\
+         <li><a href=\"#func-clear\">clear</a>()</li>\n\
+         </ul>"
+    ));
+    for id in [
+        "property-size",
+        "func-from_points",
+        "func-connect_points",
+        "func-clear",
+    ] {
+        assert!(graph.contains(&format!("<h3><a id=\"{id}\"></a>")));
+    }
+}
+
+#[test]
+fn slugs() {
+    assert_eq!(slugify("Getting started"), "getting-started");
+    assert_eq!(slugify("  The `Graph` type!  "), "the-graph-type");
+    assert_eq!(slugify("snake_case & Co."), "snake_case-co");
+    assert_eq!(slugify("Énergie"), "énergie");
+}
+
+#[test]
+fn escaping() {
+    let escaped = |text: &str, special: &[char]| {
+        let mut s = String::new();
+        push_escaped(&mut s, text, special);
+        s
+    };
+    assert_eq!(escaped(r"[a] \ b", &['[', ']']), r"\[a\] \\ b");
+    assert_eq!(escaped(r#"say "hi" \o/"#, &['"']), r#"say \"hi\" \\o/"#);
+    assert_eq!(escaped("it's", &['\'']), r"it\'s");
+}
+
+#[test]
+fn guides() {
+    let mut documentation = documentation(vec![("DijkstraMap", Vec::new())]);
+    documentation.root_documentation = String::from(
+        "Introduction.\n\
+         # Getting started\nFirst steps.\n## Installing\nCopy it.\n\
+         # DijkstraMap\nThe class.\n\
+         # Index\n\
+         # Getting started\nAgain.\n",
+    );
+    let (index, guides) = split_guides(&documentation, MarkdownOptions::empty());
+    assert_eq!(index, "Introduction.\n");
+    let guides: Vec<_> = guides
+        .iter()
+        .map(|guide| {
+            (
+                guide.title.as_str(),
+                guide.file_stem.as_str(),
+                guide.content,
+            )
+        })
+        .collect();
+    assert_eq!(
+        guides,
+        [
+            (
+                "Getting started",
+                "getting-started",
+                "First steps.\n## Installing\nCopy it.\n"
+            ),
+            // pages do not collide, even on case-insensitive file systems
+            ("DijkstraMap", "dijkstramap-1", "The class.\n"),
+            ("Index", "index-1", ""),
+            ("Getting started", "getting-started-1", "Again.\n"),
+        ]
+    );
+
+    let files = generate(
+        BuiltinBackend::Markdown,
+        documentation,
+        ConfigFile {
+            guide_pages: Some(true),
+            opening_comment: Some(false),
+            ..Default::default()
+        },
+    );
+    assert!(files["index.md"].contains("Introduction."));
+    assert!(!files["index.md"].contains("First steps."));
+    let first = &files["getting-started.md"];
+    assert!(first.starts_with("# Getting started\n"));
+    assert!(first.contains("First steps."));
+    assert!(first.contains("[Index](./index.md) | [Next: DijkstraMap](./dijkstramap-1.md)"));
+    assert!(!first.contains("Previous"));
+    assert!(files["dijkstramap-1.md"].contains(
+        "[Previous: Getting started](./getting-started.md) | [Index](./index.md) | [Next: Index](./index-1.md)"
+    ));
+    let last = &files["getting-started-1.md"];
+    assert!(last.contains("[Previous: Index](./index-1.md) | [Index](./index.md)"));
+    assert!(!last.contains("Next"));
+}
+
+#[test]
+fn godot_xml() {
+    let files = generate(BuiltinBackend::GodotXml, graph(), ConfigFile::default());
+    let mut names: Vec<_> = files.keys().collect();
+    names.sort();
+    assert_eq!(names, ["Graph.xml", "Point.xml"]);
+    insta::assert_snapshot!("godot_xml_graph", files["Graph.xml"]);
+    insta::assert_snapshot!("godot_xml_point", files["Point.xml"]);
+}
+
+#[test]
+fn json() {
+    let files = generate(BuiltinBackend::Json, graph(), ConfigFile::default());
+    let mut names: Vec<_> = files.keys().collect();
+    names.sort();
+    assert_eq!(names, ["Graph.json", "Point.json", "index.json"]);
+    // the files are valid JSON, pretty-printed to be readable
+    let pretty = |name: &str| {
+        let json: serde_json::Value = serde_json::from_str(&files[name]).unwrap();
+        assert_eq!(json["schema_version"], json::JSON_SCHEMA_VERSION);
+        serde_json::to_string_pretty(&json).unwrap()
+    };
+    insta::assert_snapshot!("json_index", pretty("index.json"));
+    insta::assert_snapshot!("json_graph", pretty("Graph.json"));
+}
+
+#[test]
+fn mdbook() {
+    let mut documentation = graph();
+    documentation.name = String::from("my \"graph\" crate");
+    documentation
+        .root_documentation
+        .push_str("\n# Getting [started]\n Hello.");
+    documentation.modules.push(documentation::Module {
+        path: String::from("graph"),
+        documentation: String::from(" Graphs."),
+        file: PathBuf::from("graph.rs"),
+    });
+    let config = || ConfigFile {
+        guide_pages: Some(true),
+        module_pages: Some(true),
+        ..Default::default()
+    };
+    let files = generate(BuiltinBackend::MdBook, documentation.clone(), config());
+    assert_eq!(
+        files["book.toml"],
+        "[book]\ntitle = \"my \\\"graph\\\" crate\"\nsrc = \"src\"\n"
+    );
+    assert_eq!(
+        files["src/SUMMARY.md"],
+        "# Summary\n\n\
+         [my \"graph\" crate](index.md)\n\n\
+         - [Getting \\[started\\]](getting-started.md)\n\n\
+         # Modules\n\n\
+         - [graph](module-graph.md)\n\n\
+         # Classes\n\n\
+         - [Graph](Graph.md)\n\
+         - [Point](Point.md)\n"
+    );
+    // every chapter is generated
+    for chapter in ["index", "getting-started", "module-graph", "Graph", "Point"] {
+        assert!(
+            files.contains_key(&format!("src/{}.md", chapter)),
+            "{}",
+            chapter
+        );
+    }
+
+    // categories are parts of the book
+    let config = ConfigFile {
+        categories: Some(HashMap::from([(
+            String::from("Graph"),
+            String::from("Graphs"),
+        )])),
+        ..config()
+    };
+    let files = generate(BuiltinBackend::MdBook, documentation, config);
+    assert!(files["src/SUMMARY.md"].ends_with(
+        "# Graphs\n\n\
+         - [Graph](Graph.md)\n\n\
+         # Other\n\n\
+         - [Point](Point.md)\n"
+    ));
+}
+
+/// Tests generated by the gut backend for `documentation`, without opening
+/// comment.
+fn gut(documentation: Documentation, config: ConfigFile) -> HashMap<String, String> {
+    let config = ConfigFile {
+        opening_comment: Some(false),
+        ..config
+    };
+    generate(BuiltinBackend::Gut, documentation, config)
+}
+
+#[test]
+fn gut_fixtures() {
+    let mut documentation = visit(
+        r#"
+        /// ```gdscript,before_each
+        /// var graph = Graph.new()
+        /// graph.add_point(1)
+        /// ```
+        /// ```gdscript
+        /// assert_eq(graph.size, 1)
+        /// ```
+        #[derive(NativeClass)]
+        #[inherit(Reference)]
+        pub struct Graph;
+
+        #[derive(NativeClass)]
+        #[inherit(Reference)]
+        pub struct Point;
+        "#,
+    );
+    // fixtures of the crate documentation are shared by all scripts
+    documentation.root_documentation =
+        String::from("```gdscript,after_each\nassert_no_new_orphans()\n```");
+    let files = gut(documentation, ConfigFile::default());
+    assert_eq!(
+        files["Graph.gd"],
+        "extends \"res://addons/gut/test.gd\"\n\n\
+         var graph\n\n\
+         func before_each():\n    graph = Graph.new()\n    graph.add_point(1)\n\n\
+         func after_each():\n    assert_no_new_orphans()\n\n\
+         func test_class_Graph():\n    assert_eq(graph.size, 1)\n\n"
+    );
+    assert_eq!(
+        files["Point.gd"],
+        "extends \"res://addons/gut/test.gd\"\n\n\
+         func after_each():\n    assert_no_new_orphans()\n\n"
+    );
+}
+
+#[test]
+fn gut_no_run() {
+    let documentation = visit(
+        r#"
+        #[derive(NativeClass)]
+        #[inherit(Reference)]
+        pub struct Graph;
+
+        #[methods]
+        impl Graph {
+            /// ```gdscript,no_run
+            /// $Graph.connect_points(1, 2)
+            /// ```
+            /// ```gdscript
+            /// assert_true(Graph.new().connect_points(1, 2))
+            /// ```
+            #[method]
+            pub fn connect_points(&self, from: i32, to: i32) -> bool {
+                true
+            }
+        }
+        "#,
+    );
+    let files = gut(documentation, ConfigFile::default());
+    assert_eq!(
+        files["Graph.gd"],
+        "extends \"res://addons/gut/test.gd\"\n\n\
+         func test_connect_points():\n    assert_true(Graph.new().connect_points(1, 2))\n\n"
+    );
+}
+
+#[test]
+fn gut_class_and_property_examples() {
+    let documentation = visit(
+        r#"
+        /// ```gdscript
+        /// assert_not_null(Graph.new())
+        /// ```
+        /// ```gdscript
+        /// assert_true(Graph.new() is Reference)
+        /// ```
+        #[derive(NativeClass)]
+        #[inherit(Reference)]
+        pub struct Graph {
+            /// ```gdscript
+            /// assert_eq(Graph.new().size, 0)
+            /// ```
+            #[property]
+            size: i32,
+        }
+        "#,
+    );
+    let files = gut(documentation, ConfigFile::default());
+    assert_eq!(
+        files["Graph.gd"],
+        "extends \"res://addons/gut/test.gd\"\n\n\
+         func test_class_Graph():\n    assert_not_null(Graph.new())\n\n\
+         func test_class_Graph_1():\n    assert_true(Graph.new() is Reference)\n\n\
+         func test_property_size():\n    assert_eq(Graph.new().size, 0)\n\n"
+    );
+}
+
+/// Two classes with tests, one of them in the `nodes` module.
+fn gut_classes() -> Documentation {
+    visit(
+        r#"
+        /// ```gdscript
+        /// assert_not_null(Graph.new())
+        /// ```
+        #[derive(NativeClass)]
+        #[inherit(Reference)]
+        pub struct Graph;
+
+        pub mod nodes {
+            /// ```gdscript
+            /// assert_not_null(Waypoint.new())
+            /// ```
+            #[derive(NativeClass)]
+            #[inherit(Node)]
+            pub struct Waypoint;
+        }
+        "#,
+    )
+}
+
+#[test]
+fn gut_suite() {
+    use crate::{GutConfig, GutSuite};
+
+    // nothing besides the tests by default
+    let files = gut(gut_classes(), ConfigFile::default());
+    let mut names: Vec<_> = files.keys().collect();
+    names.sort();
+    assert_eq!(names, ["Graph.gd", "Waypoint.gd"]);
+
+    let files = gut(
+        gut_classes(),
+        ConfigFile {
+            gut: Some(GutConfig {
+                suite: Some(GutSuite {
+                    directory: Some(String::from("res://test/generated/")),
+                    dirs: Some(vec![String::from("res://test/unit")]),
+                    exit_on_failure: Some(false),
+                    runner_scene: None,
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+    );
+    assert!(!files.contains_key("run_tests.tscn"));
+    assert_eq!(
+        files[".gutconfig.json"],
+        r#"{
+  "dirs": [
+    "res://test/unit"
+  ],
+  "tests": [
+    "res://test/generated/Graph.gd",
+    "res://test/generated/Waypoint.gd"
+  ],
+  "should_exit": false,
+  "should_exit_on_success": true
+}
+"#
+    );
+}
+
+#[test]
+fn gut_runner_scene() {
+    use crate::{GutConfig, GutSuite};
+
+    let config = |godot_version: &str| ConfigFile {
+        godot_version: Some(String::from(godot_version)),
+        gut: Some(GutConfig {
+            suite: Some(GutSuite {
+                runner_scene: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let files = gut(gut_classes(), config("3.5"));
+    assert!(files.contains_key(".gutconfig.json"));
+    assert_eq!(
+        files["run_tests.gd"],
+        "extends Node\n\n\
+         # Runs the generated tests when the scene is played.\n\
+         func _ready():\n    \
+         var gut = load(\"res://addons/gut/gut.gd\").new()\n    \
+         add_child(gut)\n    \
+         gut.add_script(\"res://test/Graph.gd\")\n    \
+         gut.add_script(\"res://test/Waypoint.gd\")\n    \
+         gut.test_scripts()\n"
+    );
+    assert_eq!(
+        files["run_tests.tscn"],
+        "[gd_scene load_steps=2 format=2]\n\n\
+         [ext_resource path=\"res://test/run_tests.gd\" type=\"Script\" id=1]\n\n\
+         [node name=\"RunTests\" type=\"Node\"]\nscript = ExtResource( 1 )\n"
+    );
+
+    // godot 4 uses the format 3 of scenes
+    let files = gut(gut_classes(), config("4.0"));
+    assert_eq!(
+        files["run_tests.tscn"],
+        "[gd_scene load_steps=2 format=3]\n\n\
+         [ext_resource type=\"Script\" path=\"res://test/run_tests.gd\" id=\"1\"]\n\n\
+         [node name=\"RunTests\" type=\"Node\"]\nscript = ExtResource(\"1\")\n"
+    );
+}
+
+#[test]
+fn gut_test_base() {
+    use crate::GutConfig;
+
+    let extends = |test_base: Option<&str>| {
+        let config = ConfigFile {
+            gut: Some(GutConfig {
+                test_base: test_base.map(String::from),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let files = gut(gut_classes(), config);
+        files["Graph.gd"].lines().next().unwrap().to_string()
+    };
+    assert_eq!(extends(None), "extends \"res://addons/gut/test.gd\"");
+    assert_eq!(
+        extends(Some("res://vendor/gut/test.gd")),
+        "extends \"res://vendor/gut/test.gd\""
+    );
+    assert_eq!(extends(Some("MyTestBase")), "extends MyTestBase");
+}
+
+#[test]
+fn gut_pending_tests() {
+    use crate::GutConfig;
+
+    let source = r#"
+        #[derive(NativeClass)]
+        #[inherit(Reference)]
+        pub struct Graph;
+
+        #[methods]
+        impl Graph {
+            /// ```gdscript
+            /// assert_true(Graph.new().connect_points(1, 2))
+            /// ```
+            #[method]
+            pub fn connect_points(&self, from: i32, to: i32) -> bool {
+                true
+            }
+
+            /// No example.
+            #[method]
+            pub fn clear(&self) {}
+
+            /// ```gdscript
+            /// assert_true(Graph.new().is_empty())
+            /// ```
+            #[method]
+            #[deprecated]
+            pub fn empty(&self) -> bool {
+                true
+            }
+        }
+    "#;
+    // not generated by default
+    let files = gut(visit(source), ConfigFile::default());
+    assert!(!files["Graph.gd"].contains("pending"));
+
+    let config = ConfigFile {
+        gut: Some(GutConfig {
+            pending_tests: Some(true),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let files = gut(visit(source), config);
+    // deprecated methods have no test, even a pending one
+    assert_eq!(
+        files["Graph.gd"],
+        "extends \"res://addons/gut/test.gd\"\n\n\
+         func test_connect_points():\n    assert_true(Graph.new().connect_points(1, 2))\n\n\
+         func test_clear():\n    pending(\"no example\")\n\n"
+    );
+}
+
+#[test]
+fn gut_tests_without_assertion() {
+    use crate::diagnostics::{self, Lint, LintLevel};
+
+    let documentation = || {
+        visit(
+            r#"
+            #[derive(NativeClass)]
+            #[inherit(Reference)]
+            pub struct Graph;
+
+            #[methods]
+            impl Graph {
+                /// ```gdscript
+                /// Graph.new().connect_points(1, 2)
+                /// ```
+                /// ```gdscript
+                /// assert(Graph.new().connect_points(1, 2))
+                /// ```
+                #[method]
+                pub fn connect_points(&self, from: i32, to: i32) -> bool {
+                    true
+                }
+
+                /// ```gdscript
+                /// Graph.new().clear()
+                /// ```
+                #[method]
+                pub fn clear(&self) {}
+
+                /// ```gdscript
+                /// assert_true(Graph.new().is_empty())
+                /// ```
+                #[method]
+                pub fn is_empty(&self) -> bool {
+                    true
+                }
+            }
+            "#,
+        )
+    };
+    gut(documentation(), ConfigFile::default());
+    let diagnostic = diagnostics::emitted()
+        .into_iter()
+        .find(|diagnostic| diagnostic.lint == Lint::TestWithoutAssertion)
+        .unwrap();
+    assert_eq!(diagnostic.level, LintLevel::Warn);
+    assert_eq!(
+        diagnostic.message,
+        "tests of 'Graph' without assertion: test_connect_points, test_clear"
+    );
+
+    // the lint can fail the build
+    let result = Builder::new()
+        .user_config(ConfigFile {
+            lints: Some(HashMap::from([(
+                String::from("test_without_assertion"),
+                LintLevel::Deny,
+            )])),
+            ..Default::default()
+        })
+        .with_documentation(documentation())
+        .add_backend(BuiltinBackend::Gut, PathBuf::from("gut"))
+        .build_to_memory();
+    assert!(matches!(result, Err(Error::DeniedDiagnostics(1))));
+}
+
+#[test]
+fn gut_parameters() {
+    let documentation = visit(
+        r#"
+        #[derive(NativeClass)]
+        #[inherit(Reference)]
+        pub struct Graph;
+
+        #[methods]
+        impl Graph {
+            /// ```gdscript,params
+            /// [1, 1]
+            /// [2, 4]
+            /// ```
+            /// ```gdscript
+            /// assert_eq(Graph.new().square(params[0]), params[1])
+            /// ```
+            /// Without parameters:
+            /// ```gdscript
+            /// assert_eq(Graph.new().square(0), 0)
+            /// ```
+            #[method]
+            pub fn square(&self, x: i32) -> i32 {
+                x * x
+            }
+        }
+        "#,
+    );
+    let files = gut(documentation, ConfigFile::default());
+    assert_eq!(
+        files["Graph.gd"],
+        "extends \"res://addons/gut/test.gd\"\n\n\
+         var params_test_square = [\n    [1, 1],\n    [2, 4]\n]\n\n\
+         func test_square(p = use_parameters(params_test_square)):\n    \
+         assert_eq(Graph.new().square(params[0]), params[1])\n\n\
+         func test_square_1():\n    assert_eq(Graph.new().square(0), 0)\n\n"
+    );
+}
+
+#[test]
+fn gut_module_directories() {
+    use crate::{GutConfig, GutSuite};
+
+    let files = gut(
+        gut_classes(),
+        ConfigFile {
+            gut: Some(GutConfig {
+                suite: Some(GutSuite::default()),
+                module_directories: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+    );
+    let mut names: Vec<_> = files.keys().collect();
+    names.sort();
+    assert_eq!(names, [".gutconfig.json", "Graph.gd", "nodes/Waypoint.gd"]);
+    assert!(files[".gutconfig.json"].contains("\"res://test/nodes/Waypoint.gd\""));
+
+    // renamed classes have the same file name with or without module directories
+    let rename_classes = || {
+        Some(HashMap::from([(
+            String::from("Waypoint"),
+            String::from("Beacon"),
+        )]))
+    };
+    let files = gut(
+        gut_classes(),
+        ConfigFile {
+            rename_classes: rename_classes(),
+            gut: Some(GutConfig {
+                module_directories: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+    );
+    let mut names: Vec<_> = files.keys().collect();
+    names.sort();
+    assert_eq!(names, ["Graph.gd", "nodes/Beacon.gd"]);
+    let files = gut(
+        gut_classes(),
+        ConfigFile {
+            rename_classes: rename_classes(),
+            ..Default::default()
+        },
+    );
+    let mut names: Vec<_> = files.keys().collect();
+    names.sort();
+    assert_eq!(names, ["Beacon.gd", "Graph.gd"]);
+}
+
+#[test]
+fn html_search_index() {
+    let mut documentation = graph();
+    documentation.classes.extend(
+        visit(
+            r#"
+            /// A weighted edge.
+            #[derive(NativeClass)]
+            #[doc(alias = "Link")]
+            pub struct Edge {
+                /// Weight of the edge.
+                #[property(get = "Self::get_weight")]
+                weight: f32,
+            }
+
+            #[methods]
+            impl Edge {
+                /// Get the weight.
+                #[method]
+                pub fn get_weight(&self) -> f32 { self.weight }
+
+                /// Swap the ends of the edge.
+                #[method]
+                #[doc(alias = "flip")]
+                #[doc(alias = "invert")]
+                pub fn reverse(&mut self) {}
+            }
+            "#,
+        )
+        .classes,
+    );
+    let files = generate(BuiltinBackend::Html, documentation, ConfigFile::default());
+    let index: serde_json::Value = serde_json::from_str(&files["search_index.json"]).unwrap();
+    // accessors are found through their property
+    assert_eq!(
+        index,
+        serde_json::json!([
+            {
+                "name": "Edge",
+                "kind": "class",
+                "class": null,
+                "aliases": ["Link"],
+                "summary": "A weighted edge.",
+                "url": "Edge.html"
+            },
+            {
+                "name": "weight",
+                "kind": "property",
+                "class": "Edge",
+                "aliases": [],
+                "summary": "Weight of the edge.",
+                "url": "Edge.html#property-weight"
+            },
+            {
+                "name": "reverse",
+                "kind": "method",
+                "class": "Edge",
+                "aliases": ["flip", "invert"],
+                "summary": "Swap the ends of the edge.",
+                "url": "Edge.html#func-reverse"
+            },
+            {
+                "name": "Graph",
+                "kind": "class",
+                "class": null,
+                "aliases": [],
+                "summary": "A graph of [`Point`]s.",
+                "url": "Graph.html"
+            },
+            {
+                "name": "size",
+                "kind": "property",
+                "class": "Graph",
+                "aliases": [],
+                "summary": "Number of points in the graph.",
+                "url": "Graph.html#property-size"
+            },
+            {
+                "name": "connect_points",
+                "kind": "method",
+                "class": "Graph",
+                "aliases": [],
+                "summary": "Connect `from` to `to`.",
+                "url": "Graph.html#func-connect_points"
+            },
+            {
+                "name": "clear",
+                "kind": "method",
+                "class": "Graph",
+                "aliases": [],
+                "summary": "Remove every point.",
+                "url": "Graph.html#func-clear"
+            },
+            {
+                "name": "Point",
+                "kind": "class",
+                "class": null,
+                "aliases": [],
+                "summary": "A point of a [`Graph`].",
+                "url": "Point.html"
+            }
+        ])
+    );
+    assert!(files["search.js"].contains("search_index.json"));
+    assert!(files["search.js"].contains("item.aliases"));
+}
+
+#[test]
+fn html_sidebar() {
+    let mut documentation = graph();
+    documentation
+        .root_documentation
+        .push_str("\n# Getting started\n Hello.");
+    documentation.modules.push(documentation::Module {
+        path: String::from("graph"),
+        documentation: String::from(" Graphs."),
+        file: PathBuf::from("graph.rs"),
+    });
+    let config = || ConfigFile {
+        guide_pages: Some(true),
+        module_pages: Some(true),
+        ..Default::default()
+    };
+    let sidebar = |page: &str| {
+        let start = page.find("<nav class=\"sidebar\">").unwrap();
+        let end = page.find("</nav>").unwrap();
+        page[start..end].to_string()
+    };
+    let files = generate(BuiltinBackend::Html, documentation.clone(), config());
+
+    // the members of the current class are listed below it
+    assert!(sidebar(&files["Graph.html"]).ends_with(
+        "<p class=\"sidebar-title\"><a href=\"./index.html\">my-crate</a></p>\n\
+         <h3>Guides</h3>\n\
+         <ul>\n\
+         <li><a href=\"./getting-started.html\">Getting started</a></li>\n\
+         </ul>\n\
+         <h3>Modules</h3>\n\
+         <ul>\n\
+         <li><a href=\"./module-graph.html\">graph</a></li>\n\
+         </ul>\n\
+         <h3>Classes</h3>\n\
+         <ul>\n\
+         <li><a href=\"./Graph.html\" class=\"current\">Graph</a>\n\
+         <ul class=\"members\">\n\
+         <li><a href=\"#property-size\">size</a></li>\n\
+         <li><a href=\"#func-connect_points\">connect_points</a>()</li>\n\
+         <li><a href=\"#func-clear\">clear</a>()</li>\n\
+         </ul>\n\
+         </li>\n\
+         <li><a href=\"./Point.html\">Point</a></li>\n\
+         </ul>\n"
+    ));
+    // every page has the sidebar
+    for page in ["index.html", "getting-started.html", "module-graph.html"] {
+        let sidebar = sidebar(&files[page]);
+        assert!(sidebar.contains("<li><a href=\"./Graph.html\">Graph</a></li>\n"));
+        assert!(!sidebar.contains("class=\"current\""), "{}", page);
+    }
+
+    // classes are grouped by category
+    let config = ConfigFile {
+        guide_pages: Some(false),
+        module_pages: Some(false),
+        categories: Some(HashMap::from([(
+            String::from("Graph"),
+            String::from("Graphs"),
+        )])),
+        ..config()
+    };
+    let files = generate(BuiltinBackend::Html, documentation, config);
+    let sidebar = sidebar(&files["Point.html"]);
+    assert!(!sidebar.contains("Guides"));
+    assert!(!sidebar.contains("Modules"));
+    assert!(sidebar.ends_with(
+        "<h3>Graphs</h3>\n\
+         <ul>\n\
+         <li><a href=\"./Graph.html\">Graph</a></li>\n\
+         </ul>\n\
+         <h3>Other</h3>\n\
+         <ul>\n\
+         <li><a href=\"./Point.html\" class=\"current\">Point</a>\n\
+         <ul class=\"members\">\n\
+         </ul>\n\
+         </li>\n\
+         </ul>\n"
+    ));
+}
+
+#[test]
+fn html_theme() {
+    let files = generate(BuiltinBackend::Html, graph(), ConfigFile::default());
+    assert!(files["index.html"].contains("<html data-default-theme=\"dark\">"));
+    assert!(files.contains_key("theme.js"));
+    assert!(!files.contains_key("extra.css"));
+    assert!(!files["index.html"].contains("extra.css"));
+
+    let directory = tempfile::tempdir().unwrap();
+    let extra_css = directory.path().join("graph.css");
+    fs::write(
+        &extra_css,
+        "[data-theme=\"dark\"] { --link-color: #f90; }\n",
+    )
+    .unwrap();
+    let config = || ConfigFile {
+        html: Some(HtmlConfig {
+            theme: Some(HtmlTheme::Auto),
+            extra_css: Some(extra_css.clone()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let files = generate(BuiltinBackend::Html, graph(), config());
+    assert_eq!(
+        files["extra.css"],
+        "[data-theme=\"dark\"] { --link-color: #f90; }\n"
+    );
+    for page in ["index.html", "Graph.html", "inheritance.html"] {
+        let page = &files[page];
+        assert!(page.contains("<html data-default-theme=\"auto\">"));
+        assert!(page.contains("<link rel=\"stylesheet\" href=\"./extra.css\"/>"));
+    }
+
+    // a missing stylesheet is an error
+    fs::remove_file(&extra_css).unwrap();
+    let result = Builder::new()
+        .user_config(config())
+        .with_documentation(graph())
+        .add_backend(BuiltinBackend::Html, PathBuf::from("doc"))
+        .build_to_memory();
+    assert!(matches!(result, Err(Error::Io(path, _)) if path == extra_css));
+}
+
+#[test]
+fn html_templates() {
+    let directory = tempfile::tempdir().unwrap();
+    let directory = directory.path();
+    fs::write(
+        directory.join("footer.hbs"),
+        "<footer>{{crate_name}}</footer>\n",
+    )
+    .unwrap();
+    fs::write(
+        directory.join("class.hbs"),
+        "<p class=\"class-name\">{{class}}</p>\n{{{content}}}",
+    )
+    .unwrap();
+    // ignored: not a template, and unknown template
+    fs::write(directory.join("notes.txt"), "{{#if}}").unwrap();
+    fs::write(directory.join("sidebar.hbs"), "{{#if}}").unwrap();
+    let config = || ConfigFile {
+        html: Some(HtmlConfig {
+            templates: Some(directory.to_path_buf()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let files = generate(BuiltinBackend::Html, graph(), config());
+    for page in ["index.html", "Graph.html", "Point.html"] {
+        let page = &files[page];
+        assert!(page.contains("<footer>my-crate</footer>\n"));
+        assert!(!page.contains("search.js"));
+        // the other templates are the default ones
+        assert!(page.contains("<nav class=\"sidebar\">"));
+    }
+    assert!(
+        files["Graph.html"].contains("<main>\n<p class=\"class-name\">Graph</p>\n<h1>Graph</h1>")
+    );
+    assert!(!files["index.html"].contains("class-name"));
+
+    // invalid templates are an error
+    let invalid = directory.join("header.hbs");
+    fs::write(&invalid, "{{#if sidebar}}").unwrap();
+    let result = Builder::new()
+        .user_config(config())
+        .with_documentation(graph())
+        .add_backend(BuiltinBackend::Html, PathBuf::from("doc"))
+        .build_to_memory();
+    assert!(matches!(result, Err(Error::Template(path, _)) if path == invalid));
+}
+
+#[test]
+fn html_highlighting() {
+    let mut documentation = graph();
+    documentation
+        .classes
+        .get_mut("Point")
+        .unwrap()
+        .documentation = String::from(" ```rust\n let x = 1;\n ```\n\n ```text\n <a>\n ```\n");
+    let files = generate(
+        BuiltinBackend::Html,
+        documentation.clone(),
+        ConfigFile::default(),
+    );
+    assert!(files["highlight.css"].contains(".hl-"));
+    assert!(files["Graph.html"].contains(
+        "<pre><code class=\"language-gdscript\">\
+         <span class=\"hl-source hl-gdscript\">\
+         <span class=\"hl-storage hl-type hl-gdscript\">var</span> graph \
+         <span class=\"hl-keyword hl-operator hl-gdscript\">=</span> \
+         <span class=\"hl-support hl-class hl-gdscript\">Graph</span>.\
+         <span class=\"hl-variable hl-function hl-gdscript\">new</span>()\n\
+         <span class=\"hl-variable hl-function hl-gdscript\">assert_eq</span>(graph.size, \
+         <span class=\"hl-constant hl-numeric hl-gdscript\">0</span>)\n\
+         </span></code></pre>"
+    ));
+    let point = &files["Point.html"];
+    assert!(point.contains("<span class=\"hl-storage hl-type hl-rust\">let</span>"));
+    // unknown languages are only escaped
+    assert!(point.contains("<pre><code class=\"language-text\">&lt;a&gt;\n</code></pre>"));
+    assert!(!point.contains("line-numbers"));
+
+    let config = ConfigFile {
+        html: Some(HtmlConfig {
+            line_numbers: Some(true),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let files = generate(BuiltinBackend::Html, documentation, config);
+    assert!(files["Graph.html"].contains(
+        "<pre class=\"line-numbers\" aria-hidden=\"true\"><span>1</span><span>2</span></pre>\n\
+         <pre><code class=\"language-gdscript\">"
+    ));
+    assert!(files["Point.html"].contains(
+        "<pre class=\"line-numbers\" aria-hidden=\"true\"><span>1</span></pre>\n\
+         <pre><code class=\"language-text\">"
+    ));
+}
+
+#[test]
+fn html_copy_buttons() {
+    let files = generate(BuiltinBackend::Html, graph(), ConfigFile::default());
+    assert!(files["copy.js"].contains(".code-block > .copy-button"));
+    let graph = &files["Graph.html"];
+    // only the code block has a button, not the inline code
+    assert_eq!(graph.matches("class=\"copy-button\"").count(), 1);
+    assert!(graph.contains(
+        "<div class=\"code-block\">\n\
+         <button type=\"button\" class=\"copy-button\" title=\"Copy to clipboard\">Copy</button>\n\
+         <pre><code class=\"language-gdscript\">"
+    ));
+    assert!(graph.contains("</code></pre>\n</div>\n"));
+    for page in ["index.html", "Graph.html", "Point.html", "inheritance.html"] {
+        assert!(files[page].contains("<script src=\"./copy.js\"></script>"));
+    }
+}
+
+#[test]
+fn html_headings() {
+    let mut documentation = graph();
+    documentation.root_documentation.push_str(
+        "\n\n ## Install `it`\n\n ## Install `it`\n\n # Getting started\n\n ## Install `it`\n",
+    );
+    documentation
+        .classes
+        .get_mut("Point")
+        .unwrap()
+        .documentation = String::from(" A point.\n\n # Usage\n");
+    let config = ConfigFile {
+        guide_pages: Some(true),
+        ..Default::default()
+    };
+    let files = generate(BuiltinBackend::Html, documentation, config);
+    // ids are unique in each page
+    assert!(files["index.html"].contains(
+        "<h5 id=\"install-it\">Install <code>it</code> \
+         <a class=\"permalink\" href=\"#install-it\" aria-label=\"Permalink\">¶</a></h5>\n\
+         <h5 id=\"install-it-1\">Install <code>it</code> \
+         <a class=\"permalink\" href=\"#install-it-1\" aria-label=\"Permalink\">¶</a></h5>\n"
+    ));
+    assert!(files["getting-started.html"].contains(
+        "<h5 id=\"install-it\">Install <code>it</code> \
+         <a class=\"permalink\" href=\"#install-it\" aria-label=\"Permalink\">¶</a></h5>\n"
+    ));
+    // headings in the documentation of classes are prefixed by their section
+    assert!(files["Point.html"].contains(
+        "<h4 id=\"description-usage\">Usage \
+         <a class=\"permalink\" href=\"#description-usage\" aria-label=\"Permalink\">¶</a></h4>\n"
+    ));
+    // properties and methods link to their anchor
+    let graph = &files["Graph.html"];
+    assert!(graph.contains(
+        "<h3><a id=\"property-size\"></a> size: \
+         <a href=\"https://docs.godotengine.org/en/3.5/classes/class_int.html\">int</a> \
+         <a class=\"permalink\" href=\"#property-size\" aria-label=\"Permalink\">¶</a></h3>\n"
+    ));
+    assert!(graph.contains(
+        "<h3><a id=\"func-clear\"></a>func clear() -&gt; void \
+         <a class=\"permalink\" href=\"#func-clear\" aria-label=\"Permalink\">¶</a></h3>\n"
+    ));
+}
+
+#[test]
+fn html_base_url() {
+    let files = generate(BuiltinBackend::Html, graph(), ConfigFile::default());
+    assert!(!files.contains_key("sitemap.xml"));
+    assert!(!files.contains_key("robots.txt"));
+    assert!(!files["Graph.html"].contains("canonical"));
+
+    let config = ConfigFile {
+        html: Some(HtmlConfig {
+            base_url: Some(String::from("https://example.com/docs")),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let files = generate(BuiltinBackend::Html, graph(), config);
+    assert!(files["Graph.html"]
+        .contains("<link rel=\"canonical\" href=\"https://example.com/docs/Graph.html\"/>"));
+    assert!(files["index.html"]
+        .contains("<link rel=\"canonical\" href=\"https://example.com/docs/index.html\"/>"));
+    assert_eq!(
+        files["sitemap.xml"],
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n\
+         <url><loc>https://example.com/docs/Graph.html</loc></url>\n\
+         <url><loc>https://example.com/docs/Point.html</loc></url>\n\
+         <url><loc>https://example.com/docs/index.html</loc></url>\n\
+         <url><loc>https://example.com/docs/inheritance.html</loc></url>\n\
+         </urlset>\n"
+    );
+    assert_eq!(
+        files["robots.txt"],
+        "User-agent: *\nAllow: /\nSitemap: https://example.com/docs/sitemap.xml\n"
+    );
+}
+
+#[test]
+fn html_meta_tags() {
+    let mut documentation = graph();
+    documentation.classes.get_mut("Point").unwrap().summary =
+        String::from("A point, with \"coordinates\".");
+    let files = generate(
+        BuiltinBackend::Html,
+        documentation.clone(),
+        ConfigFile::default(),
+    );
+    let index = &files["index.html"];
+    assert!(index.contains(
+        "<title>my-crate</title>\n\
+         <meta property=\"og:type\" content=\"website\"/>\n\
+         <meta property=\"og:site_name\" content=\"my-crate\"/>\n\
+         <meta property=\"og:title\" content=\"my-crate\"/>\n\
+         <meta name=\"twitter:card\" content=\"summary\"/>\n"
+    ));
+    // classes are described by their summary
+    assert!(files["Point.html"].contains(
+        "<title>Point - my-crate</title>\n\
+         <meta name=\"description\" content=\"A point, with &quot;coordinates&quot;.\"/>\n"
+    ));
+
+    let config = ConfigFile {
+        html: Some(HtmlConfig {
+            description: Some(String::from("Graphs for godot.")),
+            author: Some(String::from("Jane Doe")),
+            logo: Some(String::from("https://example.com/logo.png")),
+            twitter_site: Some(String::from("@example")),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let files = generate(BuiltinBackend::Html, documentation, config);
+    assert!(files["index.html"].contains(
+        "<title>my-crate</title>\n\
+         <meta name=\"description\" content=\"Graphs for godot.\"/>\n\
+         <meta name=\"author\" content=\"Jane Doe\"/>\n\
+         <meta property=\"og:type\" content=\"website\"/>\n\
+         <meta property=\"og:site_name\" content=\"my-crate\"/>\n\
+         <meta property=\"og:title\" content=\"my-crate\"/>\n\
+         <meta property=\"og:description\" content=\"Graphs for godot.\"/>\n\
+         <meta property=\"og:image\" content=\"https://example.com/logo.png\"/>\n\
+         <meta name=\"twitter:card\" content=\"summary\"/>\n\
+         <meta name=\"twitter:site\" content=\"@example\"/>\n"
+    ));
+    assert!(files["Point.html"].contains(
+        "<meta property=\"og:description\" content=\"A point, with &quot;coordinates&quot;.\"/>\n"
+    ));
+}
+
+#[test]
+fn html_github_pages() {
+    let files = generate(BuiltinBackend::Html, graph(), ConfigFile::default());
+    assert!(!files.contains_key(".nojekyll"));
+
+    let config = ConfigFile {
+        html: Some(HtmlConfig {
+            github_pages: Some(true),
+            path_prefix: Some(String::from("/my repo/")),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let files = generate(BuiltinBackend::Html, graph(), config);
+    assert_eq!(files[".nojekyll"], "");
+    let graph = &files["Graph.html"];
+    assert!(!graph.contains("\"./"));
+    assert!(graph.contains("<link rel=\"stylesheet\" href=\"/my%20repo/style.css\"/>"));
+    assert!(graph.contains("<script src=\"/my%20repo/search.js\"></script>"));
+    assert!(graph.contains("<a href=\"/my%20repo/Graph.html\" class=\"current\">Graph</a>"));
+    assert!(graph.contains("<a href=\"/my%20repo/Point.html\"><code>Point</code></a>"));
+    // anchors and external links are unchanged
+    assert!(graph.contains("<a href=\"#property-size\">size</a>"));
+    assert!(graph.contains(
+        "<a href=\"https://docs.godotengine.org/en/3.5/classes/class_int.html\">int</a>"
+    ));
+}
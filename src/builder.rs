@@ -1,9 +1,25 @@
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "serde")]
+use crate::cache::{DocumentationCache, PageCache};
 use crate::{
-    backend::{self, BuiltinBackend, Callbacks, Resolver},
-    documentation::Documentation,
-    ConfigFile, Error, GodotVersion,
+    backend::{self, BuiltinBackend, Callbacks, ResolveHook, Resolver},
+    checkout::Checkout,
+    config::Placeholders,
+    diagnostics,
+    documentation::{
+        CrateMetadata, Documentation, Images, ParsedFiles, TagRegistry, IMAGES_DIRECTORY,
+    },
+    gdns, godot_classes, godot_version, CleanMode, ConfigFile, Error,
+};
+use cargo_metadata::CargoOpt;
+use std::{
+    collections::HashMap,
+    fs, mem,
+    path::{Component, Path, PathBuf},
+    sync::Arc,
 };
-use std::{fs, path::PathBuf};
 
 /// Used to specify a crate in [`Builder::package`].
 #[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -14,6 +30,38 @@ pub enum Package {
     Root(PathBuf),
 }
 
+/// Result of [`Builder::build_with_report`].
+#[derive(Clone, Debug)]
+pub struct BuildReport {
+    /// Documentation of the crate, as given to the backends.
+    pub documentation: Documentation,
+    /// What each backend generated, in the order in which they were added.
+    pub backends: Vec<BackendReport>,
+    /// Diagnostics emitted while building, in order.
+    ///
+    /// This includes the diagnostics of allowed lints.
+    pub diagnostics: Vec<diagnostics::Diagnostic>,
+}
+
+/// Files generated by a backend, in a [`BuildReport`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BackendReport {
+    /// Kind of the backend, or `None` if it was added with
+    /// [`Builder::add_backend_with_callbacks`].
+    pub backend: Option<BuiltinBackend>,
+    /// Output directory of the backend.
+    pub output_dir: PathBuf,
+    /// Paths of the files written, including the images copied in the output
+    /// directory.
+    ///
+    /// Files whose content did not change are not written, and are not listed.
+    pub files: Vec<PathBuf>,
+    /// Paths of the stale files removed, according to [`ConfigFile::clean`].
+    ///
+    /// With [`CleanMode::All`], this is the output directory itself.
+    pub removed: Vec<PathBuf>,
+}
+
 #[derive(Debug)]
 /// A builder for generating godot documentation in various formats.
 ///
@@ -23,12 +71,41 @@ pub enum Package {
 /// [`add_backend`]: Builder::add_backend
 /// [`add_backend_with_callbacks`]: Builder::add_backend_with_callbacks
 pub struct Builder {
-    /// List of backends with their output directory
-    backends: Vec<(Box<dyn Callbacks>, PathBuf)>,
+    /// List of backends with their output directory, and their kind if they are
+    /// builtin.
+    backends: Vec<(Box<dyn Callbacks>, PathBuf, Option<BuiltinBackend>)>,
     /// Configuration file
     user_config: ConfigFile,
     /// Used to disambiguate which crate to use.
     package: Option<Package>,
+    /// Manifest of the crate or workspace, instead of the one in the current
+    /// directory.
+    manifest_path: Option<PathBuf>,
+    /// Features of the crate to activate.
+    features: Vec<String>,
+    /// Do not activate the `default` feature of the crate.
+    no_default_features: bool,
+    /// Activate all the features of the crate.
+    all_features: bool,
+    /// Tags extracted from the documentation comments.
+    tags: TagRegistry,
+    /// Git revision at which to document the crate.
+    revision: Option<String>,
+    /// Custom link resolution.
+    resolve_hooks: Vec<Arc<dyn ResolveHook + Send + Sync>>,
+    /// Documentation to use instead of parsing the crate.
+    documentation: Option<Documentation>,
+    /// Only log the files that would be written.
+    dry_run: bool,
+    /// Cache the parsed module files.
+    #[cfg(feature = "serde")]
+    incremental: bool,
+    /// Directory of the cache, instead of `target/gdnative-doc`.
+    #[cfg(feature = "serde")]
+    cache_directory: Option<PathBuf>,
+    /// `target/gdnative-doc`, found the first time the cache is used.
+    #[cfg(feature = "serde")]
+    target_cache_directory: std::sync::OnceLock<Option<PathBuf>>,
 }
 
 impl Default for Builder {
@@ -44,6 +121,21 @@ impl Builder {
             backends: Vec::new(),
             user_config: ConfigFile::default(),
             package: None,
+            manifest_path: None,
+            features: Vec::new(),
+            no_default_features: false,
+            all_features: false,
+            tags: TagRegistry::default(),
+            revision: None,
+            resolve_hooks: Vec::new(),
+            documentation: None,
+            dry_run: false,
+            #[cfg(feature = "serde")]
+            incremental: false,
+            #[cfg(feature = "serde")]
+            cache_directory: None,
+            #[cfg(feature = "serde")]
+            target_cache_directory: std::sync::OnceLock::new(),
         }
     }
 
@@ -75,8 +167,155 @@ impl Builder {
         self
     }
 
+    /// Use the `Cargo.toml` at `manifest_path` to find the crate to document,
+    /// instead of the one in the current directory.
+    ///
+    /// This allows to document a crate from another directory, like a separate
+    /// documentation repository. It can be the manifest of a workspace, in which
+    /// case the crate is chosen as usual (see [`package`](Self::package)).
+    ///
+    /// # Example
+    /// ```
+    /// # use gdnative_doc::Builder;
+    /// # use std::path::PathBuf;
+    /// let builder = Builder::new().manifest_path(PathBuf::from("../my-crate/Cargo.toml"));
+    /// ```
+    pub fn manifest_path(mut self, manifest_path: PathBuf) -> Self {
+        self.manifest_path = Some(manifest_path);
+        self
+    }
+
+    /// Activate `features` of the crate when looking for it with `cargo metadata`,
+    /// like `cargo build --features`.
+    ///
+    /// Calls to this function accumulate.
+    ///
+    /// # Example
+    /// ```
+    /// # use gdnative_doc::Builder;
+    /// let builder = Builder::new().features(&["serde", "editor"]);
+    /// ```
+    pub fn features(mut self, features: &[&str]) -> Self {
+        self.features
+            .extend(features.iter().map(|feature| feature.to_string()));
+        self
+    }
+
+    /// Do not activate the `default` feature of the crate, like
+    /// `cargo build --no-default-features`.
+    ///
+    /// # Example
+    /// ```
+    /// # use gdnative_doc::Builder;
+    /// let builder = Builder::new().no_default_features().features(&["editor"]);
+    /// ```
+    pub fn no_default_features(mut self) -> Self {
+        self.no_default_features = true;
+        self
+    }
+
+    /// Activate all the features of the crate, like `cargo build --all-features`.
+    ///
+    /// # Example
+    /// ```
+    /// # use gdnative_doc::Builder;
+    /// let builder = Builder::new().all_features();
+    /// ```
+    pub fn all_features(mut self) -> Self {
+        self.all_features = true;
+        self
+    }
+
+    /// Document the crate as it was at a git `revision` (commit, branch, tag...).
+    ///
+    /// The crate is still found as described in [`package`](Self::package), but
+    /// its sources are then checked out at `revision` in a temporary directory,
+    /// which is removed once the documentation has been extracted. This requires
+    /// `git` to be installed, and the crate to be in a git repository.
+    ///
+    /// # Example
+    /// ```
+    /// # use gdnative_doc::Builder;
+    /// let builder = Builder::new().at_revision("v0.3.0");
+    /// ```
+    pub fn at_revision(mut self, revision: &str) -> Self {
+        self.revision = Some(revision.to_string());
+        self
+    }
+
+    /// Generate the backends from `documentation`, instead of parsing a crate.
+    ///
+    /// This is useful for tools that build or post-process the documentation
+    /// themselves, for example by [merging](Documentation::merge) several crates
+    /// or adding synthetic classes. [`package`](Self::package),
+    /// [`at_revision`](Self::at_revision) and
+    /// [`register_tag`](Self::register_tag) are then ignored, but the
+    /// configuration (renaming, filters...) still applies.
+    ///
+    /// # Example
+    /// ```
+    /// # use gdnative_doc::{backend::BuiltinBackend, documentation::*, Builder};
+    /// # use std::{collections::HashMap, path::PathBuf};
+    /// let documentation = Documentation {
+    ///     schema_version: SCHEMA_VERSION,
+    ///     name: String::from("my-crate"),
+    ///     metadata: CrateMetadata::default(),
+    ///     root_file: PathBuf::from("src/lib.rs"),
+    ///     root_documentation: String::from("Classes generated by a script."),
+    ///     modules: Vec::new(),
+    ///     classes: HashMap::new(),
+    /// };
+    /// let builder = Builder::new()
+    ///     .with_documentation(documentation)
+    ///     .add_backend(BuiltinBackend::Markdown, PathBuf::from("doc"));
+    /// ```
+    pub fn with_documentation(mut self, documentation: Documentation) -> Self {
+        self.documentation = Some(documentation);
+        self
+    }
+
+    /// Register a custom documentation tag.
+    ///
+    /// Lines of the form `@name value` in documentation comments are then removed
+    /// from the documentation text, and stored in the `tags` field of the
+    /// documented item, for use in custom backends. The
+    /// [builtin tags](crate::documentation::BUILTIN_TAGS) are always registered.
+    ///
+    /// # Example
+    /// ```
+    /// # use gdnative_doc::Builder;
+    /// let builder = Builder::new().register_tag("author");
+    /// ```
+    pub fn register_tag(mut self, name: &str) -> Self {
+        self.tags.register(name);
+        self
+    }
+
+    /// Add a custom resolution of links, for names that the built-in tables (godot
+    /// classes, documented items, [`url_overrides`](ConfigFile::url_overrides)...)
+    /// do not know.
+    ///
+    /// Hooks are tried in the order they were added. With the `rayon` feature,
+    /// they may be called from several threads at once.
+    ///
+    /// # Example
+    /// ```
+    /// # use gdnative_doc::Builder;
+    /// let builder = Builder::new().resolve_hook(|link: &str| {
+    ///     let name = link.strip_prefix("wiki::")?;
+    ///     Some(format!("https://wiki.example.com/{}", name))
+    /// });
+    /// ```
+    pub fn resolve_hook(mut self, hook: impl ResolveHook + Send + Sync + 'static) -> Self {
+        self.resolve_hooks.push(Arc::new(hook));
+        self
+    }
+
     /// Add a new builtin backend to the builder.
     ///
+    /// `{crate_name}` and `{crate_version}` in `output_dir` are replaced by the name
+    /// and version of the documented crate.
+    ///
     /// # Example
     /// ```
     /// # use gdnative_doc::{Builder, backend::BuiltinBackend};
@@ -88,8 +327,13 @@ impl Builder {
             BuiltinBackend::Markdown => Box::new(backend::MarkdownCallbacks::default()),
             BuiltinBackend::Html => Box::new(backend::HtmlCallbacks::default()),
             BuiltinBackend::Gut => Box::new(backend::GutCallbacks::default()),
+            BuiltinBackend::GodotXml => Box::new(backend::GodotXmlCallbacks::default()),
+            BuiltinBackend::Json => Box::new(backend::JsonCallbacks::default()),
+            BuiltinBackend::MdBook => Box::new(backend::MdBookCallbacks::default()),
+            BuiltinBackend::Docusaurus => Box::new(backend::DocusaurusCallbacks::default()),
+            BuiltinBackend::GithubWiki => Box::new(backend::GithubWikiCallbacks::default()),
         };
-        self.backends.push((callbacks, output_dir));
+        self.backends.push((callbacks, output_dir, Some(backend)));
         self
     }
 
@@ -102,7 +346,74 @@ impl Builder {
         callbacks: Box<dyn Callbacks>,
         output_dir: PathBuf,
     ) -> Self {
-        self.backends.push((callbacks, output_dir));
+        self.backends.push((callbacks, output_dir, None));
+        self
+    }
+
+    /// Run the whole pipeline when building, but only log the files that would
+    /// be written, with their size and whether they would change, instead of
+    /// writing them.
+    ///
+    /// The [report](Self::build_with_report) then lists the files that would be
+    /// written.
+    ///
+    /// # Example
+    /// ```
+    /// # use gdnative_doc::Builder;
+    /// let builder = Builder::new().dry_run();
+    /// ```
+    pub fn dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+
+    /// Cache the parsed module files and the generated class pages in
+    /// `target/gdnative-doc`, and only parse again the files that changed.
+    ///
+    /// Build scripts run again whenever a file of the crate changes, even if its
+    /// documentation did not: this skips parsing the unchanged files in this
+    /// case. Likewise, only the pages of the classes that changed, and of the
+    /// classes that depend on them (by linking to them, inheriting them, or
+    /// being referenced by them) are generated again. Other changes, like the
+    /// configuration or the crate documentation, generate every page again.
+    /// Only the files whose content changed are written.
+    ///
+    /// The cache of the parsed files is not used with
+    /// [`at_revision`](Self::at_revision), and files whose parsing emitted
+    /// diagnostics are not cached, so that they are reported on every build.
+    /// [Resolve hooks](Self::resolve_hook) are assumed to always resolve a link
+    /// the same way.
+    ///
+    /// Requires the `serde` feature.
+    ///
+    /// # Example
+    /// ```
+    /// # use gdnative_doc::Builder;
+    /// let builder = Builder::new().incremental();
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn incremental(mut self) -> Self {
+        self.incremental = true;
+        self
+    }
+
+    /// Store the cache of [`incremental`](Self::incremental) in `directory`,
+    /// instead of `target/gdnative-doc`.
+    ///
+    /// Requires the `serde` feature.
+    ///
+    /// # Example
+    /// ```
+    /// # use gdnative_doc::Builder;
+    /// # use std::path::PathBuf;
+    /// let out_dir = PathBuf::from(std::env::var("OUT_DIR").unwrap_or_default());
+    /// let builder = Builder::new()
+    ///     .incremental()
+    ///     .cache_directory(out_dir.join("gdnative-doc"));
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn cache_directory(mut self, directory: PathBuf) -> Self {
+        self.cache_directory = Some(directory);
         self
     }
 
@@ -110,79 +421,660 @@ impl Builder {
     ///
     /// This will generate the documentation for each
     /// [specified backend](Self::add_backend), creating the ouput directories if
-    /// needed.
-    #[allow(clippy::or_fun_call)]
-    pub fn build(mut self) -> Result<(), Error> {
-        let mut resolver = Resolver::new(match &self.user_config.godot_version {
-            Some(s) => GodotVersion::try_from(s.as_str())?,
-            None => GodotVersion::Version35,
-        });
-
-        let (markdown_options, opening_comment) = {
-            let opening_comment = self.user_config.opening_comment.unwrap_or(true);
-            let markdown_options = self
-                .user_config
-                .markdown_options()
-                .unwrap_or(pulldown_cmark::Options::empty());
-            resolver.apply_user_config(&self.user_config);
-            (markdown_options, opening_comment)
-        };
-
-        let documentation = self.build_documentation(&resolver)?;
-        for (mut callbacks, output_dir) in self.backends {
-            let generator = backend::Generator::new(
-                &resolver,
-                &documentation,
-                markdown_options,
-                opening_comment,
-            );
-
-            let files = callbacks.generate_files(generator);
+    /// needed. Files whose content did not change are left untouched.
+    pub fn build(self) -> Result<(), Error> {
+        self.build_with_report().map(|_| ())
+    }
 
+    /// Build the documentation like [`build`](Self::build), and return what was
+    /// generated.
+    ///
+    /// If a diagnostic [denied](ConfigFile::lints) in the configuration is
+    /// emitted, this returns [`Error::DeniedDiagnostics`] instead of the report.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use gdnative_doc::{backend::BuiltinBackend, Builder, Error};
+    /// # use std::path::PathBuf;
+    /// # fn main() -> Result<(), Error> {
+    /// let report = Builder::new()
+    ///     .add_backend(BuiltinBackend::Markdown, PathBuf::from("doc"))
+    ///     .build_with_report()?;
+    /// assert!(report.documentation.classes.contains_key("DijkstraMap"));
+    /// assert!(report.diagnostics.is_empty());
+    /// # Ok(()) }
+    /// ```
+    pub fn build_with_report(self) -> Result<BuildReport, Error> {
+        let dry_run = self.dry_run;
+        let clean = self.user_config.clean.unwrap_or_default();
+        let generated = self.generate()?;
+        let mut backends = Vec::new();
+        for backend in generated.backends {
+            let output_dir = backend.output_dir;
+            let mut generated_paths: Vec<_> = backend.files.keys().map(PathBuf::from).collect();
+            if let Some(pages_directory) = backend.pages_directory {
+                let images = generated.images.iter();
+                generated_paths
+                    .extend(images.map(|(_, path)| Path::new(pages_directory).join(path)));
+            }
+            let removed = clean_output(&output_dir, clean, &generated_paths, dry_run)?;
+            if dry_run {
+                backends.push(BackendReport {
+                    backend: backend.backend,
+                    files: log_dry_run(
+                        &output_dir,
+                        backend.files,
+                        backend.pages_directory,
+                        &generated.images,
+                    ),
+                    output_dir,
+                    removed,
+                });
+                continue;
+            }
             if let Err(err) = fs::create_dir_all(&output_dir) {
                 return Err(Error::Io(output_dir, err));
             }
-            for (file_name, content) in files {
+            let mut written = Vec::new();
+            for (file_name, content) in backend.files {
                 let out_file = output_dir.join(file_name);
-                if let Err(err) = fs::write(&out_file, content) {
-                    return Err(Error::Io(out_file, err));
+                if let Some(parent) = out_file.parent() {
+                    if let Err(err) = fs::create_dir_all(parent) {
+                        return Err(Error::Io(parent.to_path_buf(), err));
+                    }
+                }
+                if write_if_changed(&out_file, content.as_bytes())? {
+                    written.push(out_file);
+                }
+            }
+            if let Some(pages_directory) = backend.pages_directory {
+                let pages_directory = output_dir.join(pages_directory);
+                written.extend(copy_images(&generated.images, &pages_directory)?);
+            }
+            write_manifest(&output_dir, &generated_paths)?;
+            backends.push(BackendReport {
+                backend: backend.backend,
+                output_dir,
+                files: written,
+                removed,
+            });
+        }
+
+        match diagnostics::denied_count() {
+            0 => Ok(BuildReport {
+                documentation: generated.documentation,
+                backends,
+                diagnostics: diagnostics::emitted(),
+            }),
+            denied => Err(Error::DeniedDiagnostics(denied)),
+        }
+    }
+
+    /// Generate the documentation like [`build`](Self::build), but return the
+    /// files instead of writing them.
+    ///
+    /// The result maps the output directory of each backend (as given to
+    /// [`add_backend`](Self::add_backend) or
+    /// [`add_backend_with_callbacks`](Self::add_backend_with_callbacks)) to its
+    /// files, by path relative to this directory. Local images referenced by the
+    /// documentation are not included.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use gdnative_doc::{backend::BuiltinBackend, Builder, Error};
+    /// # use std::path::{Path, PathBuf};
+    /// # fn main() -> Result<(), Error> {
+    /// let files = Builder::new()
+    ///     .add_backend(BuiltinBackend::Markdown, PathBuf::from("doc"))
+    ///     .build_to_memory()?;
+    /// println!("{}", files[Path::new("doc")]["index.md"]);
+    /// # Ok(()) }
+    /// ```
+    pub fn build_to_memory(self) -> Result<HashMap<PathBuf, HashMap<String, String>>, Error> {
+        let generated = self.generate()?;
+        match diagnostics::denied_count() {
+            0 => Ok(generated
+                .backends
+                .into_iter()
+                .map(|backend| (backend.output_dir, backend.files))
+                .collect()),
+            denied => Err(Error::DeniedDiagnostics(denied)),
+        }
+    }
+
+    /// Generate the documentation like [`build`](Self::build), and compare it to
+    /// the files in the output directories instead of writing it.
+    ///
+    /// This returns [`Error::OutdatedFiles`] with the list of differences if the
+    /// files are missing or out of date, so that CI can check that the committed
+    /// documentation was regenerated. Other files in the output directories are
+    /// ignored.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use gdnative_doc::{backend::BuiltinBackend, Builder, Error};
+    /// # use std::path::PathBuf;
+    /// let result = Builder::new()
+    ///     .add_backend(BuiltinBackend::Markdown, PathBuf::from("doc"))
+    ///     .check();
+    /// if let Err(Error::OutdatedFiles(diffs)) = result {
+    ///     for diff in diffs {
+    ///         eprintln!("{}", diff);
+    ///     }
+    /// }
+    /// ```
+    pub fn check(self) -> Result<(), Error> {
+        let generated = self.generate()?;
+        let mut diffs = Vec::new();
+        for backend in generated.backends {
+            let mut files: Vec<_> = backend.files.into_iter().collect();
+            files.sort_unstable();
+            for (file_name, content) in files {
+                let path = backend.output_dir.join(file_name);
+                diffs.extend(FileDiff::new(path, content.as_bytes()));
+            }
+            if let Some(pages_directory) = backend.pages_directory {
+                for (source, path) in generated.images.iter() {
+                    let content = fs::read(source).map_err(|err| Error::Io(source.into(), err))?;
+                    let path = backend.output_dir.join(pages_directory).join(path);
+                    diffs.extend(FileDiff::new(path, &content));
                 }
             }
         }
+        for diff in &diffs {
+            log::error!("{}", diff);
+        }
 
-        Ok(())
+        match diagnostics::denied_count() {
+            0 if diffs.is_empty() => Ok(()),
+            0 => Err(Error::OutdatedFiles(diffs)),
+            denied => Err(Error::DeniedDiagnostics(denied)),
+        }
     }
 
-    /// Build documentation from a root file.
+    /// Run the whole pipeline, up to the files generated by each backend.
+    fn generate(mut self) -> Result<Generated, Error> {
+        diagnostics::configure(&self.user_config.lints.take().unwrap_or_default());
+        let (godot_version, documentation_version) = godot_version::select(&self.user_config)?;
+        let mut resolver = Resolver::new(godot_version);
+        if let Some(path) = &self.user_config.godot_classes_file {
+            let classes = godot_classes::read_godot_classes(path)?;
+            resolver.set_godot_classes(&classes.names, &documentation_version);
+            resolver.godot_members = classes.members;
+        } else if documentation_version != godot_version.as_str() {
+            resolver.set_godot_classes(
+                Resolver::bundled_classes(godot_version),
+                &documentation_version,
+            );
+        }
+
+        resolver.apply_user_config(&self.user_config);
+        resolver.hooks = self.resolve_hooks.clone();
+        if let Some(godot_project) = &self.user_config.godot_project {
+            for (class, name) in gdns::script_class_names(godot_project)? {
+                resolver.rename_classes.entry(class).or_insert(name);
+            }
+        }
+
+        let options = backend::GeneratorOptions::from_config(&self.user_config)?;
+        let constructors =
+            (self.user_config.constructors.take()).unwrap_or_else(|| vec![String::from("from_*")]);
+
+        let mut documentation = self.build_documentation(&resolver)?;
+        // the placeholders are known once the crate is found
+        let placeholders = Placeholders::new(
+            &documentation.name,
+            documentation.metadata.version.as_deref(),
+        );
+        resolver.replace_placeholders(&placeholders);
+        documentation.mark_constructors(&constructors);
+        documentation.sort_members(self.user_config.member_sort.unwrap_or_default());
+        let images = documentation.relocate_images();
+        resolver.add_aliases(&documentation);
+        diagnostics::check_documentation(&documentation);
+        // links to the documented classes do not depend on the backend
+        let mut link_resolver = resolver.clone();
+        link_resolver.add_documented_classes(&documentation, "md");
+        diagnostics::check_links(&documentation, &link_resolver);
+        let mut backends = Vec::new();
+        for (mut callbacks, output_dir, builtin) in mem::take(&mut self.backends) {
+            let output_dir = match output_dir.to_str() {
+                Some(path) => PathBuf::from(placeholders.replace(path)),
+                None => output_dir,
+            };
+            let mut resolver = resolver.clone();
+            if callbacks.links_to_documented_classes() {
+                resolver.add_documented_classes(&documentation, callbacks.extension());
+            }
+            let mut options = options.for_backend(builtin, &self.user_config);
+            options.replace_placeholders(&placeholders);
+            #[allow(unused_mut)]
+            let mut generator = backend::Generator::new(&resolver, &documentation, options);
+            #[cfg(feature = "serde")]
+            let page_cache = self.incremental_cache_directory().map(|directory| {
+                PageCache::new(
+                    directory,
+                    &output_dir,
+                    builtin,
+                    &generator,
+                    &self.user_config,
+                )
+            });
+            #[cfg(feature = "serde")]
+            {
+                generator.page_cache = page_cache.as_ref();
+            }
+
+            let files = callbacks.generate_files(generator);
+            #[cfg(feature = "serde")]
+            if let Some(page_cache) = page_cache {
+                page_cache.store();
+            }
+            backends.push(GeneratedBackend {
+                backend: builtin,
+                output_dir,
+                pages_directory: callbacks.pages_directory(),
+                files,
+            });
+        }
+
+        Ok(Generated {
+            documentation,
+            images,
+            backends,
+        })
+    }
+
+    /// Get the documentation given to [`with_documentation`](Self::with_documentation),
+    /// or else [read it](Self::read_documentation) from the crate, and apply the
+    /// configuration to it.
+    fn build_documentation(&mut self, resolver: &Resolver) -> Result<Documentation, Error> {
+        let mut documentation = match self.documentation.take() {
+            Some(documentation) => documentation,
+            None => self.read_documentation()?,
+        };
+        documentation.filter_classes(
+            self.user_config
+                .include_classes
+                .as_deref()
+                .unwrap_or_default(),
+            self.user_config
+                .exclude_classes
+                .as_deref()
+                .unwrap_or_default(),
+        );
+        documentation.filter_members(
+            self.user_config
+                .exclude_methods
+                .as_deref()
+                .unwrap_or_default(),
+            self.user_config
+                .exclude_properties
+                .as_deref()
+                .unwrap_or_default(),
+        );
+        resolver.rename_classes(&mut documentation);
+        Ok(documentation)
+    }
+
+    /// Read the documentation of the crate from its root file.
     ///
     /// The root file is either stored in `self`, or automatically discovered using
     /// [`find_root_file`].
-    fn build_documentation(&mut self, resolver: &Resolver) -> Result<Documentation, Error> {
+    fn read_documentation(&mut self) -> Result<Documentation, Error> {
         log::debug!("building documentation");
-        let (name, root_file) = match self.package.take() {
-            Some(Package::Root(root_file)) => ("_".to_string(), root_file),
-            Some(Package::Name(name)) => find_root_file(Some(&name))?,
-            None => find_root_file(None)?,
+        let (name, root_file, package) = match self.package.take() {
+            Some(Package::Root(root_file)) => ("_".to_string(), root_file, None),
+            Some(Package::Name(name)) => find_root_file(self.metadata_command(), Some(&name))?,
+            None => find_root_file(self.metadata_command(), None)?,
         };
 
-        let mut documentation = Documentation::from_root_file(name, root_file)?;
-        resolver.rename_classes(&mut documentation);
+        // Keep the checkout alive until the sources have been read.
+        let (_checkout, root_file, package) = match &self.revision {
+            Some(revision) => {
+                let checkout = Checkout::new(&root_file, revision)?;
+                let root_file = checkout.path(&root_file)?;
+                // the manifest may have changed since `revision`
+                let package = match package {
+                    Some(package) => {
+                        let manifest_path = checkout.path(package.manifest_path.as_std_path())?;
+                        read_package(&manifest_path, &package.name)?
+                    }
+                    None => None,
+                };
+                (Some(checkout), root_file, package)
+            }
+            None => (None, root_file, package),
+        };
+        let mut documentation = self.parse(name, root_file)?;
+        if let Some(package) = package {
+            documentation.metadata = CrateMetadata {
+                version: Some(package.version.to_string()),
+                repository: package.repository,
+                homepage: package.homepage,
+                license: package.license,
+            };
+        }
         Ok(documentation)
     }
+
+    /// Command running `cargo metadata` for the crate to document, with the
+    /// selected features.
+    fn metadata_command(&self) -> cargo_metadata::MetadataCommand {
+        let mut command = cargo_metadata::MetadataCommand::new();
+        if let Some(manifest_path) = &self.manifest_path {
+            command.manifest_path(manifest_path);
+        }
+        if !self.features.is_empty() {
+            command.features(CargoOpt::SomeFeatures(self.features.clone()));
+        }
+        if self.no_default_features {
+            command.features(CargoOpt::NoDefaultFeatures);
+        }
+        if self.all_features {
+            command.features(CargoOpt::AllFeatures);
+        }
+        command
+    }
+
+    /// Directory of the cache of [`incremental`](Self::incremental) builds, or
+    /// `None` if they are disabled.
+    ///
+    /// This runs `cargo metadata` to find `target/gdnative-doc` the first time
+    /// it is called, if no [`cache_directory`](Self::cache_directory) was given.
+    #[cfg(feature = "serde")]
+    fn incremental_cache_directory(&self) -> Option<&Path> {
+        if !self.incremental {
+            return None;
+        }
+        self.cache_directory.as_deref().or_else(|| {
+            self.target_cache_directory
+                .get_or_init(|| cache_directory(self.metadata_command()))
+                .as_deref()
+        })
+    }
+
+    /// Parse the crate at `root_file`, going through the cache if
+    /// [`incremental`](Self::incremental) is set.
+    fn parse(&self, name: String, root_file: PathBuf) -> Result<Documentation, Error> {
+        #[cfg(feature = "serde")]
+        if self.revision.is_none() {
+            if let Some(directory) = self.incremental_cache_directory() {
+                let cache = DocumentationCache::new(directory, &name, &root_file);
+                let (documentation, files) =
+                    Documentation::from_root_file(name, root_file, &self.tags, &cache.load())?;
+                cache.store(files);
+                return Ok(documentation);
+            }
+        }
+        let cache = ParsedFiles::default();
+        Ok(Documentation::from_root_file(name, root_file, &self.tags, &cache)?.0)
+    }
+}
+
+/// A generated file that differs from the file on disk, found by
+/// [`Builder::check`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FileDiff {
+    /// Path of the file.
+    pub path: PathBuf,
+    /// How the file differs.
+    pub kind: FileDiffKind,
+}
+
+/// How a file differs from the generated one, in a [`FileDiff`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FileDiffKind {
+    /// The file does not exist.
+    Missing,
+    /// The content of the file is different, starting at `line` (starting at 1).
+    Changed {
+        /// First line that differs.
+        line: usize,
+    },
+}
+
+impl FileDiff {
+    /// Compare the file at `path` with its `expected` content.
+    fn new(path: PathBuf, expected: &[u8]) -> Option<Self> {
+        let kind = match fs::read(&path) {
+            Ok(content) if content == expected => return None,
+            Ok(content) => {
+                let mut lines = content.split(|b| *b == b'\n');
+                let mut expected_lines = expected.split(|b| *b == b'\n');
+                let same = lines
+                    .by_ref()
+                    .zip(expected_lines.by_ref())
+                    .take_while(|(line, expected)| line == expected)
+                    .count();
+                FileDiffKind::Changed { line: same + 1 }
+            }
+            Err(_) => FileDiffKind::Missing,
+        };
+        Some(Self { path, kind })
+    }
+}
+
+impl std::fmt::Display for FileDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            FileDiffKind::Missing => write!(f, "{} is missing", self.path.display()),
+            FileDiffKind::Changed { line } => write!(
+                f,
+                "{} is out of date (from line {})",
+                self.path.display(),
+                line
+            ),
+        }
+    }
+}
+
+/// Output of [`Builder::generate`], before it is written.
+struct Generated {
+    documentation: Documentation,
+    images: Images,
+    backends: Vec<GeneratedBackend>,
+}
+
+/// Files generated by a backend, before they are written.
+struct GeneratedBackend {
+    backend: Option<BuiltinBackend>,
+    output_dir: PathBuf,
+    /// See [`Callbacks::pages_directory`].
+    pages_directory: Option<&'static str>,
+    files: HashMap<String, String>,
 }
 
-/// Returns the name of the crate and the root file.
-fn find_root_file(package_name: Option<&str>) -> Result<(String, PathBuf), Error> {
-    let metadata = cargo_metadata::MetadataCommand::new().exec()?;
+/// Log the files that would be written in `output_dir`, and return their paths.
+fn log_dry_run(
+    output_dir: &Path,
+    files: HashMap<String, String>,
+    pages_directory: Option<&str>,
+    images: &Images,
+) -> Vec<PathBuf> {
+    let mut files: Vec<_> = files.into_iter().collect();
+    files.sort_unstable();
+    let mut paths = Vec::new();
+    for (file_name, content) in files {
+        let path = output_dir.join(file_name);
+        let status = match FileDiff::new(path.clone(), content.as_bytes()) {
+            None => "unchanged",
+            Some(FileDiff {
+                kind: FileDiffKind::Changed { .. },
+                ..
+            }) => "changed",
+            Some(_) => "new",
+        };
+        log::info!(
+            "would write {} ({} bytes, {})",
+            path.display(),
+            content.len(),
+            status
+        );
+        paths.push(path);
+    }
+    if let Some(pages_directory) = pages_directory {
+        for (source, path) in images.iter() {
+            let path = output_dir.join(pages_directory).join(path);
+            log::info!("would copy {} to {}", source.display(), path.display());
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+/// Name of the file listing the generated files, in each output directory.
+const MANIFEST: &str = ".gdnative-doc-manifest";
+
+/// Files listed in the manifest of `output_dir`, relative to it.
+///
+/// Returns `None` if there is no manifest.
+fn read_manifest(output_dir: &Path) -> Option<Vec<PathBuf>> {
+    let content = fs::read_to_string(output_dir.join(MANIFEST)).ok()?;
+    Some(
+        content
+            .lines()
+            .map(PathBuf::from)
+            // never touch files outside of the output directory
+            .filter(|path| {
+                path.components()
+                    .all(|component| matches!(component, Component::Normal(_)))
+            })
+            .collect(),
+    )
+}
+
+/// List the `generated` files in the manifest of `output_dir`.
+fn write_manifest(output_dir: &Path, generated: &[PathBuf]) -> Result<(), Error> {
+    let mut paths: Vec<_> = generated
+        .iter()
+        .map(|path| path.to_string_lossy())
+        .collect();
+    paths.sort_unstable();
+    let mut content = String::new();
+    for path in paths {
+        content.push_str(&path);
+        content.push('\n');
+    }
+    write_if_changed(&output_dir.join(MANIFEST), content.as_bytes())?;
+    Ok(())
+}
+
+/// Remove the files of `output_dir` that are not `generated` anymore, as
+/// specified by `clean`, and return their paths.
+///
+/// Only the files listed in the manifest of a previous build are removed: an
+/// output directory without a manifest is never cleaned. With `dry_run`, the
+/// files are only logged.
+fn clean_output(
+    output_dir: &Path,
+    clean: CleanMode,
+    generated: &[PathBuf],
+    dry_run: bool,
+) -> Result<Vec<PathBuf>, Error> {
+    let previous = match (clean, read_manifest(output_dir)) {
+        (CleanMode::Keep, _) => return Ok(Vec::new()),
+        (_, Some(previous)) => previous,
+        (_, None) => {
+            if clean == CleanMode::All && output_dir.exists() {
+                log::warn!(
+                    "not cleaning {}: it has no {} file",
+                    output_dir.display(),
+                    MANIFEST
+                );
+            }
+            return Ok(Vec::new());
+        }
+    };
+    let verb = if dry_run { "would remove" } else { "removing" };
+    if clean == CleanMode::All {
+        log::info!("{} {}", verb, output_dir.display());
+        if !dry_run {
+            fs::remove_dir_all(output_dir)
+                .map_err(|err| Error::Io(output_dir.to_path_buf(), err))?;
+        }
+        return Ok(vec![output_dir.to_path_buf()]);
+    }
+
+    let mut removed = Vec::new();
+    for path in previous {
+        let path_in_output = output_dir.join(&path);
+        if generated.contains(&path) || !path_in_output.is_file() {
+            continue;
+        }
+        log::info!("{} {}", verb, path_in_output.display());
+        if !dry_run {
+            fs::remove_file(&path_in_output)
+                .map_err(|err| Error::Io(path_in_output.clone(), err))?;
+            // remove the directories left empty, like the ones of removed modules
+            let mut directory = path_in_output.parent();
+            while let Some(dir) = directory.filter(|dir| *dir != output_dir) {
+                if fs::remove_dir(dir).is_err() {
+                    break;
+                }
+                directory = dir.parent();
+            }
+        }
+        removed.push(path_in_output);
+    }
+    Ok(removed)
+}
+
+/// Copy the local images referenced by the documentation in `pages_directory`,
+/// returning the paths of the images that changed.
+fn copy_images(images: &Images, pages_directory: &Path) -> Result<Vec<PathBuf>, Error> {
+    if images.is_empty() {
+        return Ok(Vec::new());
+    }
+    let images_directory = pages_directory.join(IMAGES_DIRECTORY);
+    if let Err(err) = fs::create_dir_all(&images_directory) {
+        return Err(Error::Io(images_directory, err));
+    }
+    let mut copied = Vec::new();
+    for (source, path) in images.iter() {
+        let destination = pages_directory.join(path);
+        let content = fs::read(source).map_err(|err| Error::Io(source.to_path_buf(), err))?;
+        if write_if_changed(&destination, &content)? {
+            copied.push(destination);
+        }
+    }
+    Ok(copied)
+}
+
+/// Write `content` to `path`, unless the file already has this content.
+///
+/// This keeps the modification time of unchanged files, so that file watchers
+/// (like the godot editor or cargo) are not triggered needlessly.
+///
+/// Returns whether the file was written.
+fn write_if_changed(path: &Path, content: &[u8]) -> Result<bool, Error> {
+    if fs::read(path).is_ok_and(|existing| existing == content) {
+        log::debug!("{} is unchanged", path.display());
+        return Ok(false);
+    }
+    fs::write(path, content).map_err(|err| Error::Io(path.to_path_buf(), err))?;
+    Ok(true)
+}
+
+/// Returns the name of the crate, its root file and its package information.
+fn find_root_file(
+    command: cargo_metadata::MetadataCommand,
+    package_name: Option<&str>,
+) -> Result<(String, PathBuf, Option<cargo_metadata::Package>), Error> {
+    let metadata = command.exec()?;
     let mut root_files = Vec::new();
     for package in metadata.packages {
         if metadata.workspace_members.contains(&package.id) {
             if let Some(target) = package
                 .targets
-                .into_iter()
+                .iter()
                 .find(|target| target.kind.iter().any(|kind| kind == "cdylib"))
             {
-                root_files.push((package.name, target.src_path.into()))
+                root_files.push((
+                    package.name.clone(),
+                    target.src_path.clone().into(),
+                    package,
+                ))
             }
         }
     }
@@ -190,21 +1082,50 @@ fn find_root_file(package_name: Option<&str>) -> Result<(String, PathBuf), Error
     if let Some(package_name) = package_name {
         match root_files
             .into_iter()
-            .find(|(name, _)| name == package_name)
+            .find(|(name, _, _)| name == package_name)
         {
-            Some((_, root_file)) => Ok((package_name.to_string(), root_file)),
+            Some((_, root_file, package)) => {
+                Ok((package_name.to_string(), root_file, Some(package)))
+            }
             None => Err(Error::NoMatchingCrate(package_name.to_string())),
         }
     } else {
         if root_files.len() > 1 {
             return Err(Error::MultipleCandidateCrate(
-                root_files.into_iter().map(|(name, _)| name).collect(),
+                root_files.into_iter().map(|(name, _, _)| name).collect(),
             ));
         }
-        if let Some((name, root_file)) = root_files.pop() {
-            Ok((name, root_file))
+        if let Some((name, root_file, package)) = root_files.pop() {
+            Ok((name, root_file, Some(package)))
         } else {
             Err(Error::NoCandidateCrate)
         }
     }
 }
+
+/// Directory of the cache of [`Builder::incremental`]: `target/gdnative-doc`.
+#[cfg(feature = "serde")]
+fn cache_directory(mut command: cargo_metadata::MetadataCommand) -> Option<PathBuf> {
+    match command.no_deps().exec() {
+        Ok(metadata) => Some(metadata.target_directory.join("gdnative-doc").into()),
+        Err(err) => {
+            log::warn!("could not find the target directory, not caching: {}", err);
+            None
+        }
+    }
+}
+
+/// Read the information of the package `name` in the manifest at `manifest_path`.
+fn read_package(
+    manifest_path: &Path,
+    name: &str,
+) -> Result<Option<cargo_metadata::Package>, Error> {
+    let metadata = cargo_metadata::MetadataCommand::new()
+        .manifest_path(manifest_path)
+        .no_deps()
+        .exec()?;
+    Ok(metadata
+        .packages
+        .into_iter()
+        .find(|package| package.name == name))
+}
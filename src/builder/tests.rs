@@ -0,0 +1,476 @@
+use super::*;
+use crate::documentation::tests::documentation;
+
+#[test]
+fn build_with_report() {
+    use crate::diagnostics::{Lint, LintLevel};
+
+    let directory = tempfile::tempdir().unwrap();
+    let directory = directory.path();
+    let mut documentation = documentation(vec![("Graph", Vec::new())]);
+    documentation.root_documentation = String::from("Documentation of the crate.");
+    let report = Builder::new()
+        .with_documentation(documentation)
+        .add_backend(BuiltinBackend::Markdown, directory.join("md"))
+        .add_backend(BuiltinBackend::Gut, directory.join("gut"))
+        .build_with_report()
+        .unwrap();
+
+    assert!(report.documentation.classes.contains_key("Graph"));
+    assert_eq!(report.backends.len(), 2);
+    let markdown = &report.backends[0];
+    assert_eq!(markdown.backend, Some(BuiltinBackend::Markdown));
+    assert_eq!(markdown.output_dir, directory.join("md"));
+    let mut files = markdown.files.clone();
+    files.sort();
+    assert_eq!(
+        files,
+        [directory.join("md/Graph.md"), directory.join("md/index.md")]
+    );
+    assert_eq!(report.backends[1].backend, Some(BuiltinBackend::Gut));
+    let diagnostic = &report.diagnostics[0];
+    assert_eq!(diagnostic.lint, Lint::UndocumentedClass);
+    assert_eq!(diagnostic.level, LintLevel::Allow);
+    assert_eq!(diagnostic.message, "class 'Graph' is undocumented");
+}
+
+#[test]
+fn broken_links() {
+    use crate::{
+        diagnostics::Lint,
+        documentation::{tests::method, Type},
+    };
+
+    let mut documentation = documentation(vec![
+        ("Graph", vec![method("clear", &[], Type::Unit)]),
+        ("Point", Vec::new()),
+    ]);
+    documentation
+        .classes
+        .get_mut("Graph")
+        .unwrap()
+        .documentation = String::from("See [`Self::clear`] and [`Self::missing`].");
+    documentation
+        .classes
+        .get_mut("Point")
+        .unwrap()
+        .documentation = String::from("Not [`Self::clear`].");
+    let report = Builder::new()
+        .with_documentation(documentation)
+        .build_with_report()
+        .unwrap();
+    let mut broken_links: Vec<_> = report
+        .diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.lint == Lint::BrokenLink)
+        .map(|diagnostic| diagnostic.message.as_str())
+        .collect();
+    broken_links.sort_unstable();
+    assert_eq!(
+        broken_links,
+        [
+            "unresolved link to 'Self::clear' in class 'Point' (:0)",
+            "unresolved link to 'Self::missing' in class 'Graph' (:0)",
+        ]
+    );
+}
+
+#[test]
+fn build_to_memory() {
+    let directory = tempfile::tempdir().unwrap();
+    let directory = directory.path();
+    let mut documentation = documentation(vec![("Graph", Vec::new())]);
+    documentation
+        .classes
+        .get_mut("Graph")
+        .unwrap()
+        .documentation = String::from("A graph.");
+    let files = Builder::new()
+        .with_documentation(documentation)
+        .add_backend(BuiltinBackend::Markdown, directory.join("md"))
+        .add_backend(BuiltinBackend::Json, directory.join("json"))
+        .build_to_memory()
+        .unwrap();
+    assert!(!directory.join("md").exists());
+
+    let markdown = &files[&directory.join("md")];
+    assert!(markdown["Graph.md"].contains("A graph."));
+    assert!(markdown["index.md"].contains("[Graph](./Graph.md)"));
+    assert!(!files[&directory.join("json")].is_empty());
+}
+
+#[test]
+fn custom_backend_links() {
+    use crate::backend::{Callbacks, Generator};
+    use pulldown_cmark::Event;
+
+    /// Backend writing the link to each class.
+    struct Links;
+
+    impl Callbacks for Links {
+        fn extension(&self) -> &'static str {
+            "txt"
+        }
+
+        fn generate_files(&mut self, generator: Generator) -> HashMap<String, String> {
+            (generator.documentation.classes.keys())
+                .map(|name| {
+                    let link = generator.resolver.resolve(name).unwrap_or_default();
+                    (format!("{}.txt", name), link.into_owned())
+                })
+                .collect()
+        }
+
+        fn encode(&mut self, _: &mut String, _: Vec<Event<'_>>) {}
+    }
+
+    let documentation = documentation(vec![("Graph", Vec::new())]);
+    let files = Builder::new()
+        .with_documentation(documentation)
+        .add_backend_with_callbacks(Box::new(Links), PathBuf::from("links"))
+        .build_to_memory()
+        .unwrap();
+    // custom backends link to their own pages
+    assert_eq!(files[Path::new("links")]["Graph.txt"], "./Graph.txt");
+}
+
+#[test]
+fn dry_run() {
+    let directory = tempfile::tempdir().unwrap();
+    let directory = directory.path().join("doc");
+    let report = Builder::new()
+        .with_documentation(documentation(vec![("Graph", Vec::new())]))
+        .add_backend(BuiltinBackend::Markdown, directory.clone())
+        .dry_run()
+        .build_with_report()
+        .unwrap();
+    assert!(!directory.exists());
+    let mut files = report.backends[0].files.clone();
+    files.sort();
+    assert_eq!(
+        files,
+        [directory.join("Graph.md"), directory.join("index.md")]
+    );
+}
+
+#[test]
+fn check() {
+    let directory = tempfile::tempdir().unwrap();
+    let directory = directory.path();
+    let builder = || {
+        Builder::new()
+            .with_documentation(documentation(vec![("Graph", Vec::new())]))
+            .add_backend(BuiltinBackend::Markdown, directory.to_path_buf())
+    };
+    match builder().check() {
+        Err(Error::OutdatedFiles(diffs)) => assert_eq!(
+            diffs[0],
+            FileDiff {
+                path: directory.join("Graph.md"),
+                kind: FileDiffKind::Missing
+            }
+        ),
+        result => panic!("unexpected result: {:?}", result),
+    }
+    builder().build().unwrap();
+    builder().check().unwrap();
+
+    let graph = directory.join("Graph.md");
+    let mut content = fs::read_to_string(&graph).unwrap();
+    content.push_str("\nextra");
+    fs::write(&graph, content).unwrap();
+    match builder().check() {
+        Err(Error::OutdatedFiles(diffs)) => {
+            assert_eq!(diffs.len(), 1);
+            assert_eq!(diffs[0].path, graph);
+            assert!(matches!(diffs[0].kind, FileDiffKind::Changed { .. }));
+        }
+        result => panic!("unexpected result: {:?}", result),
+    }
+}
+
+#[test]
+fn unchanged_files_are_not_rewritten() {
+    let directory = tempfile::tempdir().unwrap();
+    let directory = directory.path();
+    let build = |text: &str| {
+        let mut graph = documentation(vec![("Graph", Vec::new())]);
+        graph.classes.get_mut("Graph").unwrap().documentation = text.to_string();
+        Builder::new()
+            .with_documentation(graph)
+            .add_backend(BuiltinBackend::Markdown, directory.to_path_buf())
+            .build()
+            .unwrap();
+    };
+    let modified = |file: &str| {
+        fs::metadata(directory.join(file))
+            .unwrap()
+            .modified()
+            .unwrap()
+    };
+
+    build("A graph.");
+    let (graph, index) = (modified("Graph.md"), modified("index.md"));
+    std::thread::sleep(std::time::Duration::from_millis(20));
+    build("A graph.");
+    assert_eq!(modified("Graph.md"), graph);
+    build("A new graph.");
+    assert_ne!(modified("Graph.md"), graph);
+    assert_eq!(modified("index.md"), index);
+}
+
+#[test]
+fn clean_stale_files() {
+    let directory = tempfile::tempdir().unwrap();
+    let directory = directory.path().join("doc");
+    let build = |class: &str, clean: CleanMode| {
+        Builder::new()
+            .user_config(ConfigFile {
+                clean: Some(clean),
+                ..Default::default()
+            })
+            .with_documentation(documentation(vec![(class, Vec::new())]))
+            .add_backend(BuiltinBackend::Markdown, directory.clone())
+            .build_with_report()
+            .unwrap()
+    };
+
+    build("Graph", CleanMode::Keep);
+    fs::write(directory.join("notes.md"), "").unwrap();
+    build("Map", CleanMode::Keep);
+    assert!(directory.join("Graph.md").exists());
+
+    let report = build("Graph", CleanMode::Stale);
+    assert_eq!(report.backends[0].removed, [directory.join("Map.md")]);
+    assert!(!directory.join("Map.md").exists());
+    assert!(directory.join("notes.md").exists());
+
+    let report = build("Map", CleanMode::All);
+    assert_eq!(report.backends[0].removed, vec![directory.clone()]);
+    assert!(!directory.join("Graph.md").exists());
+    assert!(!directory.join("notes.md").exists());
+}
+
+#[test]
+fn manifest_path() {
+    let directory = tempfile::tempdir().unwrap();
+    let directory = directory.path();
+    fs::create_dir_all(directory.join("src")).unwrap();
+    fs::write(
+        directory.join("Cargo.toml"),
+        "[package]\nname = \"outside\"\nversion = \"0.2.0\"\n\n[lib]\ncrate-type = [\"cdylib\"]\n\n[workspace]\n",
+    )
+    .unwrap();
+    fs::write(
+        directory.join("src/lib.rs"),
+        "#[derive(NativeClass)]\n#[inherit(Reference)]\npub struct Graph;",
+    )
+    .unwrap();
+
+    let report = Builder::new()
+        .manifest_path(directory.join("Cargo.toml"))
+        .build_with_report()
+        .unwrap();
+    assert_eq!(report.documentation.name, "outside");
+    assert_eq!(
+        report.documentation.metadata.version.as_deref(),
+        Some("0.2.0")
+    );
+    assert!(report.documentation.classes.contains_key("Graph"));
+}
+
+#[test]
+fn cargo_features() {
+    let directory = tempfile::tempdir().unwrap();
+    let directory = directory.path();
+    fs::create_dir_all(directory.join("src")).unwrap();
+    fs::write(
+        directory.join("Cargo.toml"),
+        "[package]\nname = \"featured\"\nversion = \"0.1.0\"\n\n[lib]\ncrate-type = [\"cdylib\"]\n\n[features]\ndefault = [\"editor\"]\neditor = []\n\n[workspace]\n",
+    )
+    .unwrap();
+    fs::write(directory.join("src/lib.rs"), "").unwrap();
+    let builder = || Builder::new().manifest_path(directory.join("Cargo.toml"));
+
+    builder()
+        .no_default_features()
+        .features(&["editor"])
+        .build()
+        .unwrap();
+    builder().all_features().build().unwrap();
+    // the features are given to cargo, which checks them
+    let result = builder().features(&["missing"]).build();
+    assert!(matches!(result, Err(Error::Metadata(_))), "{:?}", result);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn incremental() {
+    let directory = tempfile::tempdir().unwrap();
+    let directory = directory.path();
+    let cache_directory = directory.join("cache");
+    let root_file = directory.join("lib.rs");
+    fs::write(&root_file, "//! A crate.\nmod graph;").unwrap();
+    let write_graph = |documentation: &str| {
+        let source = format!(
+            "/// {}\n#[derive(NativeClass)]\n#[inherit(Reference)]\npub struct Graph;",
+            documentation
+        );
+        fs::write(directory.join("graph.rs"), source).unwrap();
+    };
+    let build = || {
+        let documentation = Builder::new()
+            .package(Package::Root(root_file.clone()))
+            .incremental()
+            .cache_directory(cache_directory.clone())
+            .build_with_report()
+            .unwrap()
+            .documentation;
+        (
+            documentation.root_documentation,
+            documentation.classes["Graph"].documentation.clone(),
+        )
+    };
+
+    write_graph("A graph.");
+    assert_eq!(build(), (" A crate.".into(), " A graph.".into()));
+
+    // unchanged files are taken from the cache
+    let cache_file = fs::read_dir(&cache_directory)
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let cache = fs::read_to_string(&cache_file)
+        .unwrap()
+        .replace("A crate.", "A cached crate.")
+        .replace("A graph.", "A cached graph.");
+    fs::write(&cache_file, cache).unwrap();
+    assert_eq!(
+        build(),
+        (" A cached crate.".into(), " A cached graph.".into())
+    );
+
+    // only the file that changed is parsed again
+    write_graph("A new graph.");
+    assert_eq!(build(), (" A cached crate.".into(), " A new graph.".into()));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn incremental_pages() {
+    let directory = tempfile::tempdir().unwrap();
+    let directory = directory.path();
+    let cache_directory = directory.join("cache");
+    let output_dir = directory.join("doc");
+    let build = |graph: &str| {
+        let mut documentation = documentation(vec![("Graph", Vec::new()), ("Map", Vec::new())]);
+        documentation
+            .classes
+            .get_mut("Graph")
+            .unwrap()
+            .documentation = graph.to_string();
+        documentation.classes.get_mut("Map").unwrap().documentation = String::from("A map.");
+        Builder::new()
+            .with_documentation(documentation)
+            .add_backend(BuiltinBackend::Markdown, output_dir.clone())
+            .incremental()
+            .cache_directory(cache_directory.clone())
+            .build_with_report()
+            .unwrap()
+    };
+    let read = |file: &str| fs::read_to_string(output_dir.join(file)).unwrap();
+
+    build("A graph.");
+    let cache_file = fs::read_dir(&cache_directory)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|path| path.to_string_lossy().contains("-pages-"))
+        .unwrap();
+    let cache = fs::read_to_string(&cache_file)
+        .unwrap()
+        .replace("A map.", "A cached map.");
+    fs::write(&cache_file, cache).unwrap();
+
+    // only the page of the class that changed is generated again
+    let report = build("A new graph.");
+    assert!(read("Graph.md").contains("A new graph."));
+    assert!(read("Map.md").contains("A cached map."));
+    // the unchanged files are not listed in the report
+    assert!(!report.backends[0]
+        .files
+        .contains(&output_dir.join("index.md")));
+}
+
+#[test]
+fn at_revision() {
+    use std::process::Command;
+
+    let directory = tempfile::tempdir().unwrap();
+    let directory = directory.path();
+    let git = |args: &[&str]| {
+        let output = Command::new("git")
+            .current_dir(directory)
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{:?}", output);
+    };
+    let class = |name: &str| {
+        format!(
+            "/// A class.\n#[derive(NativeClass)]\n#[inherit(Reference)]\npub struct {};",
+            name
+        )
+    };
+    let root_file = directory.join("lib.rs");
+    git(&["init", "--quiet"]);
+    fs::write(&root_file, class("Graph")).unwrap();
+    git(&["add", "lib.rs"]);
+    git(&["commit", "--quiet", "-m", "graph"]);
+    git(&["tag", "v1"]);
+    fs::write(&root_file, class("Map")).unwrap();
+    git(&["commit", "--quiet", "-am", "map"]);
+    // uncommitted changes
+    fs::write(&root_file, class("Tree")).unwrap();
+    fs::write(directory.join("other.rs"), class("Forest")).unwrap();
+
+    let checkouts = || {
+        let prefix = format!("gdnative-doc-{}-", std::process::id());
+        fs::read_dir(std::env::temp_dir())
+            .unwrap()
+            .filter(|entry| {
+                let name = entry.as_ref().unwrap().file_name();
+                name.to_string_lossy().starts_with(&prefix)
+            })
+            .count()
+    };
+    let build = |root_file: &Path, revision: &str| {
+        Builder::new()
+            .package(Package::Root(root_file.to_path_buf()))
+            .at_revision(revision)
+            .build_with_report()
+            .map(|report| report.documentation.classes.into_keys().collect::<Vec<_>>())
+    };
+
+    assert_eq!(build(&root_file, "v1").unwrap(), ["Graph"]);
+    assert_eq!(build(&root_file, "HEAD").unwrap(), ["Map"]);
+    // the worktree is left untouched, and the checkouts are removed
+    assert_eq!(fs::read_to_string(&root_file).unwrap(), class("Tree"));
+    assert!(directory.join("other.rs").exists());
+    assert_eq!(checkouts(), 0);
+
+    let result = build(&root_file, "v2");
+    assert!(matches!(result, Err(Error::Git(_))), "{:?}", result);
+    // `other.rs` is not committed: the checkout is still removed
+    let result = build(&directory.join("other.rs"), "v1");
+    match result {
+        Err(Error::Git(message)) => {
+            assert_eq!(message, "other.rs does not exist at revision v1")
+        }
+        result => panic!("unexpected result: {:?}", result),
+    }
+    assert_eq!(fs::read_to_string(&root_file).unwrap(), class("Tree"));
+    assert_eq!(checkouts(), 0);
+}
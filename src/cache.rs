@@ -0,0 +1,365 @@
+//! On-disk cache of the parsed module files, and of the encoded classes.
+//!
+//! See [`Builder::incremental`](crate::Builder::incremental).
+
+use crate::{
+    backend::{BuiltinBackend, Generator},
+    diagnostics::Lint,
+    documentation::{GdnativeClass, ParsedFiles},
+    ConfigFile,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap, HashMap, HashSet},
+    fs,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+/// Content of a cache file.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Cache {
+    /// Hash of everything that affects parsing, besides the module files.
+    key: u64,
+    files: ParsedFiles,
+}
+
+/// Cache of the module files of the crate at a given root file.
+pub(crate) struct DocumentationCache {
+    /// Path of the cache file.
+    path: PathBuf,
+    /// Hash of the version of `gdnative-doc` and the root file.
+    key: u64,
+}
+
+impl DocumentationCache {
+    /// Cache for the crate at `root_file`, stored in `directory`.
+    pub(crate) fn new(directory: &Path, name: &str, root_file: &Path) -> Self {
+        let mut hasher = DefaultHasher::new();
+        root_file.hash(&mut hasher);
+        let path = directory.join(format!("{}-{:016x}.json", name, hasher.finish()));
+        env!("CARGO_PKG_VERSION").hash(&mut hasher);
+        Self {
+            path,
+            key: hasher.finish(),
+        }
+    }
+
+    /// Returns the cached module files, or no file if the cache is missing or
+    /// outdated.
+    pub(crate) fn load(&self) -> ParsedFiles {
+        let content = match fs::read(&self.path) {
+            Ok(content) => content,
+            Err(_) => return ParsedFiles::default(),
+        };
+        match serde_json::from_slice::<Cache>(&content) {
+            Ok(cache) if cache.key == self.key => {
+                log::debug!("using cache {}", self.path.display());
+                cache.files
+            }
+            Ok(_) => {
+                log::debug!("ignoring cache {}: outdated", self.path.display());
+                ParsedFiles::default()
+            }
+            Err(err) => {
+                log::debug!("ignoring cache {}: {}", self.path.display(), err);
+                ParsedFiles::default()
+            }
+        }
+    }
+
+    /// Store the module `files`, replacing the previous ones.
+    ///
+    /// Failing to write the cache is not an error: it is only logged.
+    pub(crate) fn store(&self, files: ParsedFiles) {
+        let cache = Cache {
+            key: self.key,
+            files,
+        };
+        if write_cache(&self.path, &cache) {
+            log::debug!("cached module files in {}", self.path.display());
+        }
+    }
+}
+
+/// Content of the cache file of the classes encoded by a backend.
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+struct Pages {
+    /// Hash of everything that the class pages depend on, besides the classes.
+    key: u64,
+    classes: HashMap<String, EncodedClass>,
+}
+
+/// A class encoded by a backend.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+struct EncodedClass {
+    /// Hash of the documentation of the class.
+    hash: u64,
+    /// Documented classes that the page of the class depends on, see [`mentions`].
+    mentions: Vec<String>,
+    /// Result of [`Generator::map_classes`] for this class.
+    encoded: serde_json::Value,
+    /// Diagnostics emitted while encoding the class, by lint code.
+    diagnostics: Vec<(String, String)>,
+}
+
+/// Cache of the classes encoded by a backend, used by [`Generator::map_classes`].
+///
+/// A class is encoded again if it changed, or if its page depends on a class
+/// that changed: because it mentions it (in a link, a signature, or its
+/// inheritance chain), or because the changed class mentions it (like
+/// `referenced_by` or `inherited_by`, before or after the change).
+#[derive(Debug)]
+pub(crate) struct PageCache {
+    /// Path of the cache file.
+    path: PathBuf,
+    key: u64,
+    /// Hash and mentions of the current classes.
+    classes: HashMap<String, (u64, Vec<String>)>,
+    /// Cached classes that do not need to be encoded again.
+    unchanged: HashMap<String, EncodedClass>,
+    /// Classes encoded during this build, or taken from `unchanged`.
+    encoded: Mutex<HashMap<String, EncodedClass>>,
+}
+
+impl PageCache {
+    /// Cache of the classes encoded by `generator`, for the `backend` writing
+    /// in `output_dir`, stored in `directory`.
+    pub(crate) fn new(
+        directory: &Path,
+        output_dir: &Path,
+        backend: Option<BuiltinBackend>,
+        generator: &Generator,
+        config: &ConfigFile,
+    ) -> Self {
+        let documentation = generator.documentation;
+        let mut hasher = DefaultHasher::new();
+        output_dir.hash(&mut hasher);
+        let path = directory.join(format!(
+            "{}-pages-{:016x}.json",
+            documentation.name,
+            hasher.finish()
+        ));
+        env!("CARGO_PKG_VERSION").hash(&mut hasher);
+        backend.hash(&mut hasher);
+        hash_context(generator, config, &mut hasher);
+        let key = hasher.finish();
+
+        let classes: HashMap<_, _> = (documentation.classes.iter())
+            .map(|(name, class)| {
+                let hash = hash_value(class);
+                (name.clone(), (hash, mentions(generator, name, class)))
+            })
+            .collect();
+        let mut cached = load_cache::<Pages>(&path)
+            .filter(|cached| cached.key == key)
+            .unwrap_or_default()
+            .classes;
+        let changed: HashSet<&str> = (classes.iter())
+            .filter(|(name, (hash, _))| cached.get(*name).is_none_or(|old| old.hash != *hash))
+            .map(|(name, _)| name.as_str())
+            .chain(
+                (cached.keys())
+                    .filter(|name| !classes.contains_key(*name))
+                    .map(String::as_str),
+            )
+            .collect();
+        let mentioned_by_changed: HashSet<&str> = (changed.iter())
+            .flat_map(|name| {
+                let new = classes.get(*name).map(|(_, mentions)| mentions);
+                let old = cached.get(*name).map(|class| &class.mentions);
+                new.into_iter().chain(old).flatten()
+            })
+            .map(String::as_str)
+            .collect();
+        let affected: HashSet<String> = (classes.iter())
+            .filter(|(name, (_, mentions))| {
+                changed.contains(name.as_str())
+                    || mentioned_by_changed.contains(name.as_str())
+                    || mentions
+                        .iter()
+                        .any(|class| changed.contains(class.as_str()))
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        cached.retain(|name, _| classes.contains_key(name) && !affected.contains(name));
+        log::debug!(
+            "{} classes are unchanged since the last build",
+            cached.len()
+        );
+
+        Self {
+            path,
+            key,
+            classes,
+            unchanged: cached,
+            encoded: Mutex::default(),
+        }
+    }
+
+    /// Returns the class `name` encoded by the last build, with the
+    /// diagnostics it emitted, if it does not need to be encoded again.
+    pub(crate) fn get<T: DeserializeOwned>(&self, name: &str) -> Option<(T, Vec<(Lint, String)>)> {
+        let class = self.unchanged.get(name)?;
+        let encoded = serde_json::from_value(class.encoded.clone()).ok()?;
+        let diagnostics = (class.diagnostics.iter())
+            .filter_map(|(code, message)| Some((Lint::find(code)?, message.clone())))
+            .collect();
+        let mut classes = self.encoded.lock().unwrap();
+        classes.insert(name.to_string(), class.clone());
+        Some((encoded, diagnostics))
+    }
+
+    /// Cache the result of encoding the class `name`, and the diagnostics it
+    /// emitted.
+    pub(crate) fn insert<T: Serialize>(
+        &self,
+        name: &str,
+        encoded: &T,
+        diagnostics: &[(Lint, String)],
+    ) {
+        let (hash, mentions) = match self.classes.get(name) {
+            Some(class) => class.clone(),
+            None => return,
+        };
+        let encoded = match serde_json::to_value(encoded) {
+            Ok(encoded) => encoded,
+            Err(err) => {
+                log::debug!("not caching class '{}': {}", name, err);
+                return;
+            }
+        };
+        let diagnostics = (diagnostics.iter())
+            .map(|(lint, message)| (lint.code().to_string(), message.clone()))
+            .collect();
+        let class = EncodedClass {
+            hash,
+            mentions,
+            encoded,
+            diagnostics,
+        };
+        let mut classes = self.encoded.lock().unwrap();
+        classes.insert(name.to_string(), class);
+    }
+
+    /// Store the classes encoded during this build, replacing the previous
+    /// ones.
+    ///
+    /// Nothing is stored if the backend did not encode classes.
+    pub(crate) fn store(self) {
+        let classes = self.encoded.into_inner().unwrap();
+        if classes.is_empty() {
+            return;
+        }
+        let pages = Pages {
+            key: self.key,
+            classes,
+        };
+        if write_cache(&self.path, &pages) {
+            log::debug!("cached encoded classes in {}", self.path.display());
+        }
+    }
+}
+
+/// Hash everything that the class pages of `generator` depend on, besides the
+/// classes themselves.
+///
+/// The tables of documented classes and members of the resolver are left out:
+/// they only change with the classes, and are covered by [`mentions`].
+fn hash_context(generator: &Generator, config: &ConfigFile, hasher: &mut DefaultHasher) {
+    hash_value(config).hash(hasher);
+    let mut documentation = serde_json::to_value(generator.documentation).unwrap_or_default();
+    if let Some(documentation) = documentation.as_object_mut() {
+        documentation.remove("classes");
+    }
+    documentation.to_string().hash(hasher);
+    // the sidebar of html pages lists every class
+    generator.class_categories().hash(hasher);
+    let html = &generator.options.html;
+    sorted(&html.templates).hash(hasher);
+    html.extra_css.hash(hasher);
+
+    let resolver = generator.resolver;
+    resolver.godot_version.hash(hasher);
+    for table in [
+        &resolver.godot_items,
+        &resolver.godot_members,
+        &resolver.rust_to_godot,
+        &resolver.url_overrides,
+        &resolver.rename_classes,
+        &resolver.rename_methods,
+        &resolver.rename_properties,
+        &resolver.aliases,
+        &resolver.rust_docs,
+    ] {
+        sorted(table).hash(hasher);
+    }
+}
+
+/// Hash of `value`, independent of the order of its maps.
+fn hash_value(value: &impl Serialize) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    // the maps of `serde_json::Value` are sorted
+    let value = serde_json::to_value(value).unwrap_or_default();
+    value.to_string().hash(&mut hasher);
+    hasher.finish()
+}
+
+fn sorted<V>(map: &HashMap<String, V>) -> BTreeMap<&String, &V> {
+    map.iter().collect()
+}
+
+/// Documented classes that the page of the class `name` depends on: the
+/// classes whose Rust or godot name appears in its documentation, and the
+/// classes it inherits.
+fn mentions(generator: &Generator, name: &str, class: &GdnativeClass) -> Vec<String> {
+    let documentation = generator.documentation;
+    let mut names: HashMap<&str, &str> = HashMap::new();
+    for class_name in documentation.classes.keys() {
+        names.insert(class_name, class_name);
+        if let Some(godot_name) = generator.resolver.rename_classes.get(class_name) {
+            names.insert(godot_name, class_name);
+        }
+    }
+    let content = serde_json::to_string(class).unwrap_or_default();
+    let mut mentions: Vec<String> = content
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter_map(|word| names.get(word))
+        .chain(&documentation.inheritance_chain(name))
+        .filter(|class_name| **class_name != name)
+        .map(|class_name| class_name.to_string())
+        .collect();
+    mentions.sort_unstable();
+    mentions.dedup();
+    mentions
+}
+
+/// Read the cache file at `path`, if it exists and is valid.
+fn load_cache<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    let content = fs::read(path).ok()?;
+    match serde_json::from_slice(&content) {
+        Ok(cache) => Some(cache),
+        Err(err) => {
+            log::debug!("ignoring cache {}: {}", path.display(), err);
+            None
+        }
+    }
+}
+
+/// Write `cache` to `path`, and returns whether it succeeded.
+///
+/// Errors are logged.
+fn write_cache(path: &Path, cache: &impl Serialize) -> bool {
+    let result = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(path, serde_json::to_vec(cache)?));
+    match result {
+        Ok(()) => true,
+        Err(err) => {
+            log::warn!("could not write {}: {}", path.display(), err);
+            false
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! Checking out the crate sources at a given git revision.
+
+use crate::Error;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// A checkout of a git repository in a temporary directory.
+///
+/// The directory is removed when this is dropped.
+#[derive(Debug)]
+pub(crate) struct Checkout {
+    directory: PathBuf,
+    /// Root of the original repository.
+    toplevel: PathBuf,
+    revision: String,
+}
+
+impl Checkout {
+    /// Check out the repository containing `file` at `revision`.
+    pub(crate) fn new(file: &Path, revision: &str) -> Result<Self, Error> {
+        let file = file
+            .canonicalize()
+            .map_err(|err| Error::Io(file.to_path_buf(), err))?;
+        let directory = file.parent().unwrap_or_else(|| Path::new("/"));
+        let toplevel = PathBuf::from(git(directory, &["rev-parse", "--show-toplevel"])?);
+        let toplevel = toplevel
+            .canonicalize()
+            .map_err(|err| Error::Io(toplevel.clone(), err))?;
+        // Resolving the revision in the original repository means branches, tags
+        // and abbreviated hashes are all accepted.
+        let commit = git(
+            &toplevel,
+            &["rev-parse", "--verify", &format!("{}^{{commit}}", revision)],
+        )?;
+        log::info!("checking out revision {} ({})", revision, commit);
+
+        let directory = std::env::temp_dir().join(format!(
+            "gdnative-doc-{}-{}",
+            std::process::id(),
+            &commit[..commit.len().min(12)]
+        ));
+        if directory.exists() {
+            fs::remove_dir_all(&directory).map_err(|err| Error::Io(directory.clone(), err))?;
+        }
+        let checkout = Self {
+            directory,
+            toplevel,
+            revision: revision.to_string(),
+        };
+        git(
+            &checkout.toplevel,
+            &[
+                "clone",
+                "--quiet",
+                "--shared",
+                "--no-checkout",
+                ".",
+                &checkout.directory.to_string_lossy(),
+            ],
+        )?;
+        git(
+            &checkout.directory,
+            &["checkout", "--quiet", "--detach", &commit],
+        )?;
+        Ok(checkout)
+    }
+
+    /// Returns the path of `file` (in the original repository) inside the
+    /// checkout.
+    pub(crate) fn path(&self, file: &Path) -> Result<PathBuf, Error> {
+        let file = file
+            .canonicalize()
+            .map_err(|err| Error::Io(file.to_path_buf(), err))?;
+        let relative_file = file.strip_prefix(&self.toplevel).map_err(|_| {
+            Error::Git(format!(
+                "{} is not in the repository at {}",
+                file.display(),
+                self.toplevel.display()
+            ))
+        })?;
+        let path = self.directory.join(relative_file);
+        if path.exists() {
+            Ok(path)
+        } else {
+            Err(Error::Git(format!(
+                "{} does not exist at revision {}",
+                relative_file.display(),
+                self.revision
+            )))
+        }
+    }
+}
+
+impl Drop for Checkout {
+    fn drop(&mut self) {
+        if !self.directory.exists() {
+            return;
+        }
+        if let Err(err) = fs::remove_dir_all(&self.directory) {
+            log::warn!(
+                "could not remove the temporary checkout at {}: {}",
+                self.directory.display(),
+                err
+            )
+        }
+    }
+}
+
+/// Run git with `args` in `directory`, and return its trimmed output.
+fn git(directory: &Path, args: &[&str]) -> Result<String, Error> {
+    log::debug!("running git {} in {}", args.join(" "), directory.display());
+    let output = Command::new("git")
+        .current_dir(directory)
+        .args(args)
+        .output()
+        .map_err(|err| Error::Io(PathBuf::from("git"), err))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(Error::Git(format!(
+            "`git {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
@@ -1,8 +1,19 @@
 //! User configuration settings.
 
-use crate::Error;
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    diagnostics::{Lint, LintLevel},
+    Error, GodotVersion,
+};
 use serde::Deserialize;
-use std::{collections::HashMap, fs, path::PathBuf};
+use std::{
+    collections::HashMap,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
 
 /// Structure that holds user configuration settings.
 ///
@@ -29,23 +40,90 @@ use std::{collections::HashMap, fs, path::PathBuf};
 ///
 /// Note that if you are reading the configuration file from an on-disk file, you
 /// should prefer [`load_from_path`](ConfigFile::load_from_path).
+///
+/// Unknown keys are rejected, with a suggestion if they look like a typo.
 // Note: any update to this structure should be documented in
 // configuration_file-format.md.
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[serde(deny_unknown_fields)]
 pub struct ConfigFile {
     /// Godot version used.
     ///
-    /// Valid fields are "3.2", "3.3", "3.4" and "3.5".
+    /// Valid fields are "3.2", "3.3", "3.4", "3.5", "4.0", "4.1", "4.2" and "4.3".
+    /// Other `3.x` and `4.x` versions are accepted if
+    /// [`godot_classes_file`](Self::godot_classes_file) is set.
+    ///
+    /// "stable" and "latest" use the newest bundled class list, and link to the
+    /// `stable` or `latest` godot documentation. "auto" detects the version from
+    /// the `project.godot` file of [`godot_project`](Self::godot_project), or
+    /// else from the output of `godot --version`.
     ///
     /// Defaults to "3.5".
     pub godot_version: Option<String>,
+    /// File listing the godot classes, replacing the list bundled for
+    /// [`godot_version`](Self::godot_version).
+    ///
+    /// This can be a directory of class references (like the output of
+    /// `godot --doctool`), an XML dump of the class references, a JSON array of
+    /// class names, or a text file with one class name per line.
+    ///
+    /// # Default
+    /// The list bundled with this crate is used.
+    pub godot_classes_file: Option<PathBuf>,
     /// List of items for which the linking url should be overriden.
     pub url_overrides: Option<HashMap<String, String>>,
+    /// Base URL of the documentation of Rust crates, by crate name.
+    ///
+    /// Rust items of these crates that are not godot classes link to their
+    /// documentation. An empty URL links to `https://doc.rust-lang.org` for the
+    /// standard library, and to `https://docs.rs` for other crates.
+    ///
+    /// # Default
+    /// Rust items are not linked.
+    pub rust_docs: Option<HashMap<String, String>>,
     /// Renaming of types when going from Rust to Godot.
     ///
     /// This is useful because GDNative allows defining a `script_class_name` in the
     /// `.gdns` file.
     pub rename_classes: Option<HashMap<String, String>>,
+    /// Base URL of the pages generated by each backend, by name of the backend
+    /// (like `markdown` or `html`).
+    ///
+    /// Links between the generated pages are relative by default (like
+    /// `./MyClass.md`): for the listed backends, they start with the base URL
+    /// instead.
+    ///
+    /// # Default
+    /// Links between the generated pages are relative.
+    pub absolute_links: Option<HashMap<String, String>>,
+    /// Renaming of methods when going from Rust to Godot.
+    ///
+    /// Keys are either the name of a method, or `Class::method` to only rename
+    /// the method of a class. The previous name is kept as an alias of the
+    /// method, so that links to it still resolve.
+    pub rename_methods: Option<HashMap<String, String>>,
+    /// Renaming of properties when going from Rust to Godot.
+    ///
+    /// Keys are either the name of a property, or `Class::property` to only
+    /// rename the property of a class.
+    pub rename_properties: Option<HashMap<String, String>>,
+    /// Mapping of Rust types to the Godot types they are converted to.
+    ///
+    /// This extends (and overrides) the built-in mapping, like `i32` to `int`.
+    /// Unlike [`rename_classes`](Self::rename_classes), it is meant for types that
+    /// are not documented classes, like `PointId(i32)`.
+    pub rust_to_godot: Option<HashMap<String, String>>,
+    /// Directory of the godot project using the crate.
+    ///
+    /// If set, the `.gdns` files in this directory are read, and each class with
+    /// a `script_class_name` is renamed to it, as if it was in
+    /// [`rename_classes`](ConfigFile::rename_classes). Entries of
+    /// `rename_classes` take precedence.
+    ///
+    /// # Default
+    /// No directory is read.
+    pub godot_project: Option<PathBuf>,
     /// Optional markdown options.
     ///
     /// # Valid options
@@ -66,21 +144,737 @@ pub struct ConfigFile {
     /// # Default
     /// `true`
     pub opening_comment: Option<bool>,
+    /// Split the root documentation on its level 1 headings (`# Title`) into
+    /// separate guide pages.
+    ///
+    /// The text preceding the first heading stays in the index file, which also
+    /// links to every guide.
+    ///
+    /// # Default
+    /// `false`
+    pub guide_pages: Option<bool>,
+    /// Generate a page for each submodule with documentation (`//!` comments),
+    /// linked from the index file.
+    ///
+    /// # Default
+    /// `false`
+    pub module_pages: Option<bool>,
+    /// Order of the methods and properties of classes.
+    ///
+    /// # Default
+    /// [`MemberSort::Source`]
+    pub member_sort: Option<MemberSort>,
+    /// Names of the static methods documented as constructors, in their own
+    /// section. These are glob patterns, like `from_*`.
+    ///
+    /// `new`, and static methods that call `Instance::emplace`, are always
+    /// constructors.
+    ///
+    /// # Default
+    /// `["from_*"]`
+    pub constructors: Option<Vec<String>>,
+    /// Glob patterns (like `Public*` or `api::*`) of the classes to document.
+    ///
+    /// Patterns are matched against the Rust name of each class, and its path
+    /// from the root of the crate.
+    ///
+    /// # Default
+    /// All classes are documented.
+    pub include_classes: Option<Vec<String>>,
+    /// Glob patterns (like `*Helper` or `internal::*`) of classes to leave out of
+    /// the documentation, even if they match
+    /// [`include_classes`](Self::include_classes).
+    ///
+    /// # Default
+    /// No class is excluded.
+    pub exclude_classes: Option<Vec<String>>,
+    /// Glob patterns (like `_*` or `debug_*`) of methods to leave out of the
+    /// documentation.
+    ///
+    /// Patterns are matched against the name of each method, and against
+    /// `Class::method`.
+    ///
+    /// # Default
+    /// No method is excluded.
+    pub exclude_methods: Option<Vec<String>>,
+    /// Glob patterns of properties to leave out of the documentation, matched
+    /// like [`exclude_methods`](Self::exclude_methods).
+    ///
+    /// # Default
+    /// No property is excluded.
+    pub exclude_properties: Option<Vec<String>>,
+    /// Base url used to generate links to the source code of classes, methods and
+    /// properties.
+    ///
+    /// This should point to the directory containing the root file, e.g.
+    /// `https://github.com/user/repo/blob/main/src`. Links are then of the form
+    /// `<repository_url>/<file>#L<line>`.
+    ///
+    /// # Default
+    /// No source link is generated.
+    pub repository_url: Option<String>,
+    /// Add a section to each class renamed via [`rename_classes`], with the lines
+    /// to put in its `.gdns` file to register it in the editor (with
+    /// `script_class_name`, and `script_class_icon_path` if found in
+    /// [`class_icons`]).
+    ///
+    /// [`rename_classes`]: ConfigFile::rename_classes
+    /// [`class_icons`]: ConfigFile::class_icons
+    ///
+    /// # Default
+    /// `false`
+    pub editor_metadata: Option<bool>,
+    /// Path of the editor icon of classes, like `"res://icons/my_class.svg"`.
+    ///
+    /// Keys are the Rust names of the classes. This is used with
+    /// [`editor_metadata`](ConfigFile::editor_metadata).
+    ///
+    /// # Default
+    /// No icon.
+    pub class_icons: Option<HashMap<String, String>>,
+    /// Category of classes, used to group them in the index file.
+    ///
+    /// Keys are the Rust names of the classes. This takes precedence over the
+    /// `@category` tag in the documentation of the classes.
+    ///
+    /// # Default
+    /// Only `@category` tags are used.
+    pub categories: Option<HashMap<String, String>>,
+    /// Options of the markdown backend, in a `[markdown]` table.
+    ///
+    /// # Default
+    /// All options keep their default value.
+    pub markdown: Option<MarkdownConfig>,
+    /// Options of the website generated by the html backend, in an `[html]`
+    /// table.
+    ///
+    /// # Default
+    /// All options keep their default value.
+    pub html: Option<HtmlConfig>,
+    /// Options of the tests generated by the gut backend, in a `[gut]` table.
+    ///
+    /// # Default
+    /// All options keep their default value.
+    pub gut: Option<GutConfig>,
+    /// Support rustdoc-style hidden lines in gdscript code blocks.
+    ///
+    /// Lines starting with `# ` are then hidden from the documentation, but
+    /// included (without the `# ` prefix) in the gut tests, and `##` is used to
+    /// write a comment. This is disabled by default, because `#` starts a
+    /// regular comment in gdscript.
+    ///
+    /// # Default
+    /// `false`
+    pub hidden_lines: Option<bool>,
+    /// Removal of the files left over by previous builds in the output
+    /// directories.
+    ///
+    /// The files generated by each build are listed in a `.gdnative-doc-manifest`
+    /// file in the output directory, so that the files that are not generated
+    /// anymore (for example because a class was renamed) can be found.
+    ///
+    /// # Default
+    /// [`CleanMode::Keep`]
+    pub clean: Option<CleanMode>,
+    /// Level of the diagnostics emitted while generating the documentation.
+    ///
+    /// Keys are the codes (like `"GDOC001"`) or names (like `"broken_link"`) of
+    /// [`Lint`](crate::diagnostics::Lint)s, and values are `"allow"`, `"warn"` or `"deny"`. If a denied
+    /// diagnostic is emitted, [`Builder::build`](crate::Builder::build) fails.
+    ///
+    /// A lint can only be set once: a file setting both `GDOC001` and `broken_link`
+    /// is invalid.
+    ///
+    /// The `gdnative-doc-cli explain <CODE>` command describes each lint.
+    ///
+    /// # Default
+    /// [`Lint::default_level`](crate::diagnostics::Lint::default_level) for every lint.
+    pub lints: Option<HashMap<String, LintLevel>>,
+}
+
+/// Order of the methods and properties in the generated documentation.
+///
+/// See [`ConfigFile::member_sort`].
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[serde(rename_all = "snake_case")]
+pub enum MemberSort {
+    /// Order in which they are declared in the source.
+    #[default]
+    Source,
+    /// Alphabetical order.
+    Alphabetical,
+    /// Constructors first, then associated functions (without `self`), then
+    /// methods, each group in alphabetical order.
+    ///
+    /// Properties are sorted alphabetically.
+    ByKind,
+}
+
+/// Options of the `.gutconfig.json` file generated by the gut backend.
+///
+/// See [`GutConfig::suite`].
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[serde(deny_unknown_fields)]
+pub struct GutSuite {
+    /// Path of the gut output directory in the godot project, like
+    /// `"res://test/generated"`.
+    ///
+    /// # Default
+    /// `"res://test"`
+    pub directory: Option<String>,
+    /// Other directories of tests to run, like `"res://test/unit"`.
+    ///
+    /// # Default
+    /// No other directory.
+    pub dirs: Option<Vec<String>>,
+    /// Exit as soon as the tests are done, even if some failed.
+    ///
+    /// If `false`, godot only exits when all tests succeed, so that failures can
+    /// be inspected.
+    ///
+    /// # Default
+    /// `true`
+    pub exit_on_failure: Option<bool>,
+    /// Also generate a `run_tests.tscn` scene, that runs the generated tests
+    /// when played from the editor.
+    ///
+    /// # Default
+    /// `false`
+    pub runner_scene: Option<bool>,
+}
+
+/// Options of the tests generated by the gut backend.
+///
+/// See [`ConfigFile::gut`].
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[serde(deny_unknown_fields)]
+pub struct GutConfig {
+    /// Script extended by the generated tests: either a path (like
+    /// `"res://vendor/gut/test.gd"`) or the name of a class (like
+    /// `"MyTestBase"`).
+    ///
+    /// # Default
+    /// `"res://addons/gut/test.gd"`
+    pub test_base: Option<String>,
+    /// Generate a pending test (`pending("no example")`) for each method
+    /// without a gdscript example, so that the test runner reports them.
+    ///
+    /// # Default
+    /// `false`
+    pub pending_tests: Option<bool>,
+    /// Write the tests of each class in subdirectories matching its module, like
+    /// `pathfinding/Interface.gd` for a class in the `pathfinding` module.
+    ///
+    /// # Default
+    /// `false`
+    pub module_directories: Option<bool>,
+    /// Generate a `.gutconfig.json` file alongside the gut tests, that runs all
+    /// of them, in a `[gut.suite]` table.
+    ///
+    /// # Default
+    /// No configuration file is generated.
+    pub suite: Option<GutSuite>,
+    /// Overrides [`ConfigFile::opening_comment`] for the gut backend.
+    pub opening_comment: Option<bool>,
+}
+
+impl GutConfig {
+    /// `extends` statement of the generated tests.
+    pub fn extends(&self) -> String {
+        match self.test_base.as_deref() {
+            Some(class) if !class.contains('/') && !class.ends_with(".gd") => {
+                format!("extends {}", class)
+            }
+            test_base => format!(
+                "extends \"{}\"",
+                test_base.unwrap_or("res://addons/gut/test.gd")
+            ),
+        }
+    }
+}
+
+/// Options of the markdown backend.
+///
+/// See [`ConfigFile::markdown`].
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[serde(deny_unknown_fields)]
+pub struct MarkdownConfig {
+    /// Generate a single `API.md` file in the markdown backend, containing the
+    /// index and then every page, instead of a file per page.
+    ///
+    /// Headings of the pages are shifted by one level, and their anchors are
+    /// prefixed by the name of the page, like `#MyClass-func-new`.
+    ///
+    /// # Default
+    /// `false`
+    pub single_file: Option<bool>,
+    /// Write links as inline links (`[text](url)`) in the markdown backends: this
+    /// also applies to the mdBook, GitHub wiki and Docusaurus backends.
+    ///
+    /// By default, links to resolved items are written as shortcut links
+    /// (`[text]`), and their definitions are added at the bottom of the file.
+    /// Some markdown processors handle inline links better.
+    ///
+    /// # Default
+    /// `false`
+    pub inline_links: Option<bool>,
+    /// Anchors of the headings of methods and properties in the markdown backends:
+    /// this also applies to the mdBook and GitHub wiki backends.
+    ///
+    /// By default, raw html anchors (like `<a id="func-new"></a>`) are added to
+    /// the headings. Renderers that strip raw html break the links to these
+    /// anchors: with another style, links use the anchors that the renderer
+    /// generates from the text of the headings instead.
+    ///
+    /// This does not affect the Docusaurus backend, nor the `single_file` mode.
+    ///
+    /// # Default
+    /// [`AnchorStyle::Html`]
+    pub anchor_style: Option<AnchorStyle>,
+    /// Number of levels by which the headings written in the documentation are
+    /// shifted, so that they fit under the headings of the generated pages: this
+    /// also applies to the mdBook, GitHub wiki and Docusaurus backends.
+    ///
+    /// With the default, a `# Errors` heading in the documentation of a method
+    /// becomes a level 4 heading, under the level 3 heading of the method. Headings
+    /// never go past level 6.
+    ///
+    /// # Default
+    /// `3`
+    pub heading_offset: Option<usize>,
+    /// Insert a table of contents at the top of each class page, linking to the
+    /// sections of the page and to each property and method: this also applies
+    /// to the mdBook and GitHub wiki backends.
+    ///
+    /// This is useful when the pages are rendered without a sidebar (like on
+    /// GitHub).
+    ///
+    /// # Default
+    /// `false`
+    pub table_of_contents: Option<bool>,
+    /// Add a front matter at the top of the files of the markdown backend, for
+    /// static site generators like Zola, Hugo or Jekyll.
+    ///
+    /// # Default
+    /// No front matter is added.
+    pub front_matter: Option<FrontMatter>,
+    /// Overrides [`ConfigFile::opening_comment`].
+    pub opening_comment: Option<bool>,
+}
+
+/// Options of the website generated by the html backend.
+///
+/// See [`ConfigFile::html`].
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[serde(deny_unknown_fields)]
+pub struct HtmlConfig {
+    /// URL at which the documentation is hosted, like
+    /// `"https://example.com/docs/"`.
+    ///
+    /// It is used to add a `<link rel="canonical">` to every page, and to
+    /// generate `sitemap.xml` and `robots.txt` files.
+    ///
+    /// # Default
+    /// None of these are generated.
+    pub base_url: Option<String>,
+    /// Description of the documentation, for the `description` meta tag and
+    /// OpenGraph cards.
+    ///
+    /// Class pages use the summary of the class instead.
+    ///
+    /// # Default
+    /// No description, except for class pages.
+    pub description: Option<String>,
+    /// Author of the documentation, for the `author` meta tag.
+    ///
+    /// # Default
+    /// No author.
+    pub author: Option<String>,
+    /// URL of a logo, displayed at the top of the sidebar and used as the image of
+    /// OpenGraph cards.
+    ///
+    /// A relative URL is resolved against [`base_url`](Self::base_url) for the
+    /// cards.
+    ///
+    /// # Default
+    /// No logo.
+    pub logo: Option<String>,
+    /// Twitter account of the documentation, like `"@godotengine"`, for the
+    /// `twitter:site` meta tag.
+    ///
+    /// # Default
+    /// No account.
+    pub twitter_site: Option<String>,
+    /// Prepare the output to be published with GitHub Pages as is, by writing a
+    /// `.nojekyll` file.
+    ///
+    /// # Default
+    /// `false`
+    pub github_pages: Option<bool>,
+    /// Path at which the documentation is served, like `"/my-repo/"` for a
+    /// GitHub Pages project site.
+    ///
+    /// Links between pages and to assets (`./MyClass.html`, `./style.css`...)
+    /// are rewritten to start with this prefix.
+    ///
+    /// # Default
+    /// Links are relative.
+    pub path_prefix: Option<String>,
+    /// Default theme of the html backend.
+    ///
+    /// Readers can switch between the light and dark themes with a button, and
+    /// their choice is remembered.
+    ///
+    /// # Default
+    /// [`HtmlTheme::Dark`]
+    pub theme: Option<HtmlTheme>,
+    /// Show line numbers in the code blocks of the html backend.
+    ///
+    /// Independently of this option, lines can be highlighted by annotating the
+    /// code block, like ` ```gdscript{3-5,7} `.
+    ///
+    /// # Default
+    /// `false`
+    pub line_numbers: Option<bool>,
+    /// Path of a CSS file, relative to the current directory, that is included in
+    /// every page of the html backend after the default style.
+    ///
+    /// Colors of the default style are CSS variables (like `--link-color`), that
+    /// can be overridden for each theme:
+    /// ```css
+    /// [data-theme="dark"] {
+    ///     --link-color: #f90;
+    /// }
+    /// ```
+    ///
+    /// # Default
+    /// No extra CSS is included.
+    pub extra_css: Option<PathBuf>,
+    /// Directory, relative to the current directory, containing
+    /// [handlebars](https://handlebarsjs.com/) templates that replace the default
+    /// ones of the html backend.
+    ///
+    /// The templates are:
+    /// - `layout.hbs`: the whole page.
+    /// - `head.hbs`: partial used by the default layout for the content of
+    ///   `<head>`, with the meta tags.
+    /// - `header.hbs` and `footer.hbs`: partials used by the default layout, before
+    ///   and after the content.
+    /// - `class.hbs`: the content of the pages documenting a class.
+    ///
+    /// Templates missing from the directory keep their default value.
+    ///
+    /// # Default
+    /// The default templates (in `html/templates`) are used.
+    pub templates: Option<PathBuf>,
+    /// Overrides [`ConfigFile::opening_comment`] for the html backend.
+    pub opening_comment: Option<bool>,
+    /// Number of levels by which the headings written in the documentation are
+    /// shifted, like [`MarkdownConfig::heading_offset`].
+    ///
+    /// # Default
+    /// `3`
+    pub heading_offset: Option<usize>,
+}
+
+impl HtmlConfig {
+    /// Absolute URL of the page in `file`, if [`base_url`](Self::base_url) is set.
+    pub fn absolute_url(&self, file: &str) -> Option<String> {
+        let base_url = self.base_url.as_deref()?;
+        Some(format!("{}/{}", base_url.trim_end_matches('/'), file))
+    }
+
+    /// Absolute URL of the [`logo`](Self::logo), if it is set and either absolute
+    /// or [`base_url`](Self::base_url) is set.
+    pub fn logo_url(&self) -> Option<String> {
+        let logo = self.logo.as_deref()?;
+        if logo.starts_with("http://") || logo.starts_with("https://") {
+            Some(logo.to_string())
+        } else {
+            self.absolute_url(logo.trim_start_matches("./"))
+        }
+    }
+}
+
+/// Removal of the stale files in the output directories.
+///
+/// See [`ConfigFile::clean`].
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[serde(rename_all = "snake_case")]
+pub enum CleanMode {
+    /// Leave the files of previous builds.
+    #[default]
+    Keep,
+    /// Remove the files generated by the previous build that are not generated
+    /// anymore.
+    Stale,
+    /// Remove the whole output directory before writing the files, if it was
+    /// generated by a previous build.
+    All,
+}
+
+/// Theme of the html backend.
+///
+/// See [`HtmlConfig::theme`].
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[serde(rename_all = "snake_case")]
+pub enum HtmlTheme {
+    /// Dark theme, resembling godot's documentation.
+    #[default]
+    Dark,
+    /// Light theme.
+    Light,
+    /// Follow the preference of the reader's system.
+    Auto,
+}
+
+impl HtmlTheme {
+    /// Name of the theme, as written in the configuration file.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Dark => "dark",
+            Self::Light => "light",
+            Self::Auto => "auto",
+        }
+    }
+}
+
+/// Anchors of the headings in the markdown backends.
+///
+/// See [`MarkdownConfig::anchor_style`].
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[serde(rename_all = "snake_case")]
+pub enum AnchorStyle {
+    /// Raw html anchors, like `<a id="func-new"></a>`.
+    #[default]
+    Html,
+    /// Anchors generated by GitHub.
+    Github,
+    /// Anchors generated by GitLab.
+    Gitlab,
+    /// Anchors generated by mdBook.
+    Mdbook,
+}
+
+/// Front matter of the files generated by the markdown backend.
+///
+/// See [`MarkdownConfig::front_matter`].
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[serde(deny_unknown_fields)]
+pub struct FrontMatter {
+    /// Syntax of the front matter.
+    ///
+    /// # Default
+    /// [`FrontMatterFormat::Yaml`]
+    pub format: Option<FrontMatterFormat>,
+    /// Content of the front matter, between the delimiters.
+    ///
+    /// The following placeholders are replaced:
+    /// - `{title}`: title of the page (the name of the class, module or guide,
+    ///   or the name of the crate for the index), escaped for a double-quoted
+    ///   string.
+    /// - `{weight}`: position of the page (starting at 1), in the order of the
+    ///   index: the index, guides, modules, and then classes.
+    /// - `{date}`: date of the generation, like `2022-10-25`. The
+    ///   `SOURCE_DATE_EPOCH` environment variable is used if it is set.
+    /// - `{file}`: name of the file, without extension.
+    ///
+    /// # Default
+    /// `title` and `weight` keys, in the syntax of `format`.
+    pub template: Option<String>,
+}
+
+/// Syntax of a [`FrontMatter`].
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[serde(rename_all = "snake_case")]
+pub enum FrontMatterFormat {
+    /// YAML, between `---` lines (Jekyll, Hugo).
+    #[default]
+    Yaml,
+    /// TOML, between `+++` lines (Zola, Hugo).
+    Toml,
+}
+
+impl FrontMatterFormat {
+    /// Line delimiting the front matter.
+    pub fn delimiter(self) -> &'static str {
+        match self {
+            Self::Yaml => "---",
+            Self::Toml => "+++",
+        }
+    }
+
+    /// Template used if [`FrontMatter::template`] is not set.
+    pub fn default_template(self) -> &'static str {
+        match self {
+            Self::Yaml => "title: \"{title}\"\nweight: {weight}\n",
+            Self::Toml => "title = \"{title}\"\nweight = {weight}\n",
+        }
+    }
 }
 
 impl ConfigFile {
     /// Load the config file from the given `path`.
+    ///
+    /// The [format](ConfigFormat::from_path) of the file is given by its
+    /// extension, and the configuration is [validated](Self::validate).
     pub fn load_from_path(path: PathBuf) -> Result<Self, Error> {
         log::debug!("loading user config at {:?}", path);
-        Ok(toml::from_str(&match fs::read_to_string(&path) {
-            Ok(config) => config,
-            Err(err) => return Err(Error::Io(path, err)),
-        })?)
+        match fs::read_to_string(&path) {
+            Ok(config) => Self::load_from_str_with_format(&config, ConfigFormat::from_path(&path)),
+            Err(err) => Err(Error::Io(path, err)),
+        }
     }
 
-    /// Load the config file from the given `config` string.
+    /// Load the config file from the given `config` string, in the TOML format.
+    ///
+    /// The configuration is [validated](Self::validate).
     pub fn load_from_str(config: &str) -> Result<Self, Error> {
-        Ok(toml::from_str(config)?)
+        Self::load_from_str_with_format(config, ConfigFormat::Toml)
+    }
+
+    /// Load the config file from the given `config` string, in the given
+    /// `format`.
+    ///
+    /// JSON and YAML files have the same structure as TOML files. The
+    /// configuration is [validated](Self::validate).
+    ///
+    /// In string values, `${VAR}` is replaced by the value of the environment
+    /// variable `VAR`, and `${VAR:-default}` by `default` if `VAR` is not set.
+    /// `$${` is replaced by a literal `${`. Unknown fields are checked after
+    /// this replacement.
+    pub fn load_from_str_with_format(config: &str, format: ConfigFormat) -> Result<Self, Error> {
+        let mut value: toml::Value = match format {
+            ConfigFormat::Toml => toml::from_str(config)?,
+            ConfigFormat::Json => match serde_json::from_str(config) {
+                Ok(value) => json_to_toml(value),
+                Err(err) => return Err(Error::InvalidConfig(format!("invalid JSON: {}", err))),
+            },
+            ConfigFormat::Yaml => match yaml_rust::YamlLoader::load_from_str(config) {
+                Ok(documents) => match documents.into_iter().next() {
+                    Some(document) => yaml_to_toml(document)?,
+                    None => toml::Value::Table(Default::default()),
+                },
+                Err(err) => return Err(Error::InvalidConfig(format!("invalid YAML: {}", err))),
+            },
+        };
+        interpolate_environment(&mut value)?;
+        let config: Self = value.try_into().map_err(unknown_field_error)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Default configuration file, listing every option with its default value
+    /// and a short description.
+    ///
+    /// Options without a default value are commented out.
+    pub fn default_toml() -> &'static str {
+        include_str!("default_config.toml")
+    }
+
+    /// Write the [default configuration file](Self::default_toml) at `path`.
+    ///
+    /// This fails if `path` already exists, so that an existing configuration is
+    /// never overwritten.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use gdnative_doc::{ConfigFile, Error};
+    /// # use std::path::Path;
+    /// # fn main() -> Result<(), Error> {
+    /// let path = Path::new("config.toml");
+    /// if !path.exists() {
+    ///     ConfigFile::write_default(path)?;
+    /// }
+    /// let config = ConfigFile::load_from_path(path.to_path_buf())?;
+    /// # Ok(()) }
+    /// ```
+    pub fn write_default(path: &Path) -> Result<(), Error> {
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+            .map_err(|err| Error::Io(path.to_path_buf(), err))?;
+        file.write_all(Self::default_toml().as_bytes())
+            .map_err(|err| Error::Io(path.to_path_buf(), err))
+    }
+
+    /// Check the values of [`godot_version`](Self::godot_version),
+    /// [`markdown_options`](Self::markdown_options) and [`lints`](Self::lints).
+    ///
+    /// This is done when loading a configuration file, and can be used on
+    /// configurations built in code.
+    pub fn validate(&self) -> Result<(), Error> {
+        if let Some(version) = &self.godot_version {
+            self.validate_godot_version(version)?;
+        }
+        for option in self.markdown_options.iter().flatten() {
+            if !MARKDOWN_OPTIONS.contains(&option.as_str()) {
+                return Err(invalid_value(
+                    "markdown_options",
+                    option,
+                    MARKDOWN_OPTIONS.to_vec(),
+                ));
+            }
+        }
+        let mut lints: Vec<&String> = self.lints.iter().flat_map(HashMap::keys).collect();
+        lints.sort_unstable();
+        let mut configured = HashMap::new();
+        for code_or_name in lints {
+            let lint = match Lint::find(code_or_name) {
+                Some(lint) => lint,
+                None => {
+                    let names = Lint::ALL.iter().map(|lint| lint.name()).collect();
+                    return Err(invalid_value("lints", code_or_name, names));
+                }
+            };
+            if let Some(previous) = configured.insert(lint, code_or_name) {
+                return Err(Error::InvalidConfig(format!(
+                    "lint `{}` is set twice in `lints`, as `{}` and `{}`",
+                    lint.name(),
+                    previous,
+                    code_or_name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_godot_version(&self, version: &str) -> Result<(), Error> {
+        let symbolic = ["stable", "latest", "auto"];
+        if symbolic.contains(&version) || GodotVersion::try_from(version).is_ok() {
+            return Ok(());
+        }
+        if GodotVersion::closest(version).is_some() {
+            return match self.godot_classes_file {
+                Some(_) => Ok(()),
+                None => Err(Error::InvalidConfig(format!(
+                    "godot version \"{}\" has no bundled class list: set `godot_classes_file`",
+                    version
+                ))),
+            };
+        }
+        let versions = GodotVersion::ALL.iter().map(|version| version.as_str());
+        let expected: Vec<&str> = versions.chain(symbolic).collect();
+        if version.starts_with(|c: char| c.is_ascii_digit()) {
+            // Numbers close to a supported version are not typos.
+            Err(Error::InvalidConfig(format!(
+                "unsupported godot version \"{}\", expected one of {:?}",
+                version, expected
+            )))
+        } else {
+            Err(invalid_value("godot_version", version, expected))
+        }
     }
 
     /// Convert the `String` list of options to `pulldown_cmark::Options`, logging
@@ -107,3 +901,266 @@ impl ConfigFile {
         }
     }
 }
+
+/// Format of a configuration file.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ConfigFormat {
+    /// [TOML](https://toml.io/en/).
+    #[default]
+    Toml,
+    /// JSON.
+    Json,
+    /// YAML.
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// Format of the file at `path`, given by its extension: `.json` for JSON,
+    /// `.yaml` or `.yml` for YAML, and TOML otherwise.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => Self::Json,
+            Some("yaml" | "yml") => Self::Yaml,
+            _ => Self::Toml,
+        }
+    }
+}
+
+/// Convert a JSON `value` to TOML, so that it can be deserialized in the same way.
+///
+/// `null` values are left out, like absent keys.
+fn json_to_toml(value: serde_json::Value) -> toml::Value {
+    use serde_json::Value;
+    match value {
+        Value::Null => toml::Value::Table(Default::default()),
+        Value::Bool(b) => toml::Value::Boolean(b),
+        Value::Number(number) => match number.as_i64() {
+            Some(integer) => toml::Value::Integer(integer),
+            None => toml::Value::Float(number.as_f64().unwrap_or_default()),
+        },
+        Value::String(string) => toml::Value::String(string),
+        Value::Array(values) => toml::Value::Array(
+            values
+                .into_iter()
+                .filter(|value| !value.is_null())
+                .map(json_to_toml)
+                .collect(),
+        ),
+        Value::Object(entries) => toml::Value::Table(
+            entries
+                .into_iter()
+                .filter(|(_, value)| !value.is_null())
+                .map(|(key, value)| (key, json_to_toml(value)))
+                .collect(),
+        ),
+    }
+}
+
+/// Convert a YAML `value` to TOML, so that it can be deserialized in the same way.
+///
+/// `null` values are left out, like absent keys.
+fn yaml_to_toml(value: yaml_rust::Yaml) -> Result<toml::Value, Error> {
+    use yaml_rust::Yaml;
+    Ok(match value {
+        Yaml::Null => toml::Value::Table(Default::default()),
+        Yaml::Boolean(b) => toml::Value::Boolean(b),
+        Yaml::Integer(integer) => toml::Value::Integer(integer),
+        Yaml::Real(real) => match real.parse() {
+            Ok(real) => toml::Value::Float(real),
+            Err(_) => {
+                return Err(Error::InvalidConfig(format!(
+                    "invalid YAML number: {}",
+                    real
+                )))
+            }
+        },
+        Yaml::String(string) => toml::Value::String(string),
+        Yaml::Array(values) => toml::Value::Array(
+            values
+                .into_iter()
+                .filter(|value| !value.is_null())
+                .map(yaml_to_toml)
+                .collect::<Result<_, _>>()?,
+        ),
+        Yaml::Hash(entries) => {
+            let mut table = toml::value::Table::new();
+            for (key, value) in entries {
+                let key = match key {
+                    Yaml::String(key) => key,
+                    Yaml::Integer(key) => key.to_string(),
+                    Yaml::Boolean(key) => key.to_string(),
+                    key => {
+                        return Err(Error::InvalidConfig(format!("invalid YAML key: {:?}", key)))
+                    }
+                };
+                if !value.is_null() {
+                    table.insert(key, yaml_to_toml(value)?);
+                }
+            }
+            toml::Value::Table(table)
+        }
+        Yaml::Alias(_) | Yaml::BadValue => {
+            return Err(Error::InvalidConfig(String::from("unsupported YAML value")))
+        }
+    })
+}
+
+/// Replace `${VAR}` and `${VAR:-default}` by the value of the environment variable
+/// `VAR` in the strings of `value`, and `$${` by `${`.
+fn interpolate_environment(value: &mut toml::Value) -> Result<(), Error> {
+    match value {
+        toml::Value::String(string) if string.contains("${") => {
+            let mut result = String::new();
+            let mut rest = string.as_str();
+            while let Some(start) = rest.find("${") {
+                // `$${` escapes a literal `${`
+                if rest[..start].ends_with('$') {
+                    result.push_str(&rest[..start - 1]);
+                    result.push_str("${");
+                    rest = &rest[start + 2..];
+                    continue;
+                }
+                result.push_str(&rest[..start]);
+                let end = rest[start..].find('}').ok_or_else(|| {
+                    Error::InvalidConfig(format!("unclosed `${{` in \"{}\"", string))
+                })?;
+                let variable = &rest[start + 2..start + end];
+                let (name, default) = match variable.split_once(":-") {
+                    Some((name, default)) => (name, Some(default)),
+                    None => (variable, None),
+                };
+                match (std::env::var(name), default) {
+                    (Ok(value), _) => result.push_str(&value),
+                    (Err(_), Some(default)) => result.push_str(default),
+                    (Err(_), None) => {
+                        return Err(Error::InvalidConfig(format!(
+                            "environment variable `{}` is not set, in \"{}\"",
+                            name, string
+                        )))
+                    }
+                }
+                rest = &rest[start + end + 1..];
+            }
+            result.push_str(rest);
+            *string = result;
+        }
+        toml::Value::Array(values) => {
+            for value in values {
+                interpolate_environment(value)?;
+            }
+        }
+        toml::Value::Table(table) => {
+            for (_, value) in table.iter_mut() {
+                interpolate_environment(value)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Values of the `{crate_name}` and `{crate_version}` placeholders, which can be
+/// used in URLs of the configuration and in output directories.
+pub(crate) struct Placeholders {
+    crate_name: String,
+    crate_version: Option<String>,
+}
+
+impl Placeholders {
+    pub(crate) fn new(crate_name: &str, crate_version: Option<&str>) -> Self {
+        Self {
+            crate_name: crate_name.to_string(),
+            crate_version: crate_version.map(String::from),
+        }
+    }
+
+    /// Replace the placeholders in `value`.
+    ///
+    /// `{crate_version}` is left as is if the version of the crate is unknown.
+    pub(crate) fn replace(&self, value: &str) -> String {
+        let value = value.replace("{crate_name}", &self.crate_name);
+        match &self.crate_version {
+            Some(version) => value.replace("{crate_version}", version),
+            None => {
+                if value.contains("{crate_version}") {
+                    log::warn!("unknown crate version for {{crate_version}} in '{}'", value);
+                }
+                value
+            }
+        }
+    }
+}
+
+/// Options accepted in [`ConfigFile::markdown_options`].
+const MARKDOWN_OPTIONS: &[&str] = &[
+    "FOOTNOTES",
+    "SMART_PUNCTUATION",
+    "STRIKETHROUGH",
+    "TABLES",
+    "TASKLISTS",
+];
+
+/// Error for the invalid `value` of `key`, suggesting the closest of the
+/// `expected` values.
+fn invalid_value(key: &str, value: &str, expected: Vec<&str>) -> Error {
+    let mut message = format!("invalid value \"{}\" in `{}`", value, key);
+    match did_you_mean(value, &expected) {
+        Some(suggestion) => message.push_str(&format!(", did you mean \"{}\"?", suggestion)),
+        None => message.push_str(&format!(", expected one of {:?}", expected)),
+    }
+    Error::InvalidConfig(message)
+}
+
+/// Turn the "unknown field" errors of `toml` into [`Error::InvalidConfig`],
+/// suggesting the closest known field.
+///
+/// Other errors are returned unchanged.
+fn unknown_field_error(err: toml::de::Error) -> Error {
+    let message = err.to_string();
+    let unknown = message
+        .strip_prefix("unknown field `")
+        .and_then(|rest| rest.split_once('`'));
+    let (field, rest) = match unknown {
+        Some(unknown) => unknown,
+        None => return Error::Toml(err),
+    };
+    // `rest` looks like ", expected one of `a`, `b` for key `c` at line 1 column 1".
+    let (expected, context) = match rest.find(" for key `").or_else(|| rest.find(" at line")) {
+        Some(index) => rest.split_at(index),
+        None => (rest, ""),
+    };
+    let expected: Vec<&str> = expected.split('`').skip(1).step_by(2).collect();
+    match did_you_mean(field, &expected) {
+        Some(suggestion) => Error::InvalidConfig(format!(
+            "unknown field `{}`{}, did you mean `{}`?",
+            field, context, suggestion
+        )),
+        None => Error::InvalidConfig(message),
+    }
+}
+
+/// Closest of the `candidates` to `name`, if it is close enough to be a typo.
+fn did_you_mean<'a>(name: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    let name = name.to_lowercase();
+    candidates
+        .iter()
+        .map(|candidate| (edit_distance(&name, &candidate.to_lowercase()), *candidate))
+        .filter(|(distance, candidate)| *distance <= candidate.len().max(3) / 3)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance between `a` and `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != *b);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
@@ -0,0 +1,164 @@
+use super::*;
+
+#[test]
+fn strict_config() {
+    let error = |config: &str| ConfigFile::load_from_str(config).unwrap_err().to_string();
+    assert_eq!(
+        error("renam_classes = { A = \"B\" }"),
+        "Invalid configuration: unknown field `renam_classes`, did you mean `rename_classes`?"
+    );
+    assert_eq!(
+        error("[html]\nbase_ur = \"x\""),
+        "Invalid configuration: unknown field `base_ur` for key `html`, did you mean `base_url`?"
+    );
+    assert_eq!(
+        error("godot_version = \"stabel\""),
+        "Invalid configuration: invalid value \"stabel\" in `godot_version`, did you mean \"stable\"?"
+    );
+    assert!(error("markdown_options = [\"TABLE\"]").contains("did you mean \"TABLES\"?"));
+    assert!(error("lints = { broken_links = \"deny\" }").contains("did you mean \"broken_link\"?"));
+    assert_eq!(
+        error("lints = { broken_link = \"deny\", GDOC001 = \"allow\" }"),
+        "Invalid configuration: lint `broken_link` is set twice in `lints`, as `GDOC001` and `broken_link`"
+    );
+    assert!(error("godot_version = \"4.9\"").contains("set `godot_classes_file`"));
+    assert!(error("godot_version = \"2.1\"").contains("expected one of"));
+    // the options of the backends are only read in their table
+    assert!(error("theme = \"light\"").contains("unknown field `theme`"));
+    assert!(error("heading_offset = 2").contains("unknown field `heading_offset`"));
+
+    assert!(
+        ConfigFile::load_from_str("godot_version = \"auto\"\nlints = { GDOC001 = \"deny\" }")
+            .is_ok()
+    );
+    assert!(ConfigFile::load_from_str(
+        "godot_version = \"4.9\"\ngodot_classes_file = \"classes.txt\""
+    )
+    .is_ok());
+}
+
+#[test]
+fn config_formats() {
+    let toml = ConfigFile::load_from_str(
+        r#"
+godot_version = "4.2"
+markdown_options = ["TABLES"]
+module_pages = true
+
+[rename_classes]
+RustName = "GodotName"
+"#,
+    )
+    .unwrap();
+    let json = ConfigFile::load_from_str_with_format(
+        r#"{
+    "godot_version": "4.2",
+    "markdown_options": ["TABLES"],
+    "module_pages": true,
+    "url_overrides": null,
+    "rename_classes": { "RustName": "GodotName" }
+}"#,
+        ConfigFormat::Json,
+    )
+    .unwrap();
+    let yaml = ConfigFile::load_from_str_with_format(
+        r#"
+godot_version: "4.2"
+markdown_options: [TABLES]
+module_pages: true
+url_overrides:
+rename_classes:
+  RustName: GodotName
+"#,
+        ConfigFormat::Yaml,
+    )
+    .unwrap();
+    assert_eq!(json, toml);
+    assert_eq!(yaml, toml);
+
+    let error =
+        ConfigFile::load_from_str_with_format("{\"renam_classes\": {}}", ConfigFormat::Json)
+            .unwrap_err();
+    assert!(error.to_string().contains("did you mean `rename_classes`?"));
+
+    assert_eq!(
+        ConfigFormat::from_path(Path::new("gdnative-doc.json")),
+        ConfigFormat::Json
+    );
+    assert_eq!(
+        ConfigFormat::from_path(Path::new("config.yml")),
+        ConfigFormat::Yaml
+    );
+    assert_eq!(
+        ConfigFormat::from_path(Path::new("config.toml")),
+        ConfigFormat::Toml
+    );
+}
+
+#[test]
+fn config_interpolation() {
+    std::env::set_var("GDNATIVE_DOC_TEST_URL", "https://ci.example.com");
+    std::env::remove_var("GDNATIVE_DOC_TEST_UNSET");
+    let config = ConfigFile::load_from_str(
+        r#"
+repository_url = "${GDNATIVE_DOC_TEST_URL}/src"
+url_overrides = { Graph = "${GDNATIVE_DOC_TEST_UNSET:-http://localhost}/{crate_version}/Graph.html" }
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        config.repository_url.as_deref(),
+        Some("https://ci.example.com/src")
+    );
+    let url = &config.url_overrides.as_ref().unwrap()["Graph"];
+    assert_eq!(url, "http://localhost/{crate_version}/Graph.html");
+
+    let placeholders = crate::config::Placeholders::new("my-crate", Some("1.2.0"));
+    assert_eq!(
+        placeholders.replace(url),
+        "http://localhost/1.2.0/Graph.html"
+    );
+    assert_eq!(placeholders.replace("docs/{crate_name}"), "docs/my-crate");
+
+    let error = ConfigFile::load_from_str("repository_url = \"${GDNATIVE_DOC_TEST_UNSET}\"");
+    assert!(error
+        .unwrap_err()
+        .to_string()
+        .contains("environment variable `GDNATIVE_DOC_TEST_UNSET` is not set"));
+
+    // `$${` is a literal `${`
+    let config = ConfigFile::load_from_str(
+        r#"repository_url = "$${GDNATIVE_DOC_TEST_UNSET}/${GDNATIVE_DOC_TEST_URL}""#,
+    )
+    .unwrap();
+    assert_eq!(
+        config.repository_url.as_deref(),
+        Some("${GDNATIVE_DOC_TEST_UNSET}/https://ci.example.com")
+    );
+
+    // unknown fields are checked on the interpolated configuration
+    let error = ConfigFile::load_from_str("[html]\nbase_urll = \"${GDNATIVE_DOC_TEST_URL}\"");
+    assert!(error.unwrap_err().to_string().contains("base_urll"));
+}
+
+#[test]
+fn default_config() {
+    let config = ConfigFile::load_from_str(ConfigFile::default_toml()).unwrap();
+    assert_eq!(config.godot_version.as_deref(), Some("3.5"));
+    let markdown = config.markdown.unwrap();
+    assert_eq!(markdown.heading_offset, Some(3));
+    assert_eq!(markdown.front_matter, None);
+    assert_eq!(config.html.unwrap().heading_offset, Some(3));
+    assert_eq!(config.gut.unwrap().suite, None);
+
+    // every documented option is listed
+    let format = include_str!("../../configuration_file-format.md");
+    for option in format.lines().filter_map(|line| line.strip_prefix("- ## ")) {
+        assert!(
+            ConfigFile::default_toml().contains(&format!("{} =", option))
+                || ConfigFile::default_toml().contains(&format!("[{}]", option)),
+            "{} is missing from the default configuration",
+            option
+        );
+    }
+}
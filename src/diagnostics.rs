@@ -0,0 +1,521 @@
+//! Diagnostics emitted while generating the documentation.
+//!
+//! Each kind of diagnostic is a [`Lint`] with a stable code (like `GDOC001`),
+//! whose [level](LintLevel) can be changed in the
+//! [configuration file](crate::ConfigFile::lints).
+
+use crate::{
+    backend::{strip_backticks, Resolver},
+    documentation::Documentation,
+};
+use pulldown_cmark::{BrokenLink, Options, Parser};
+use serde::Deserialize;
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    fmt::{self, Write as _},
+    mem,
+};
+
+/// A kind of diagnostic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Lint {
+    /// `GDOC001`: a link in the documentation could not be resolved.
+    BrokenLink,
+    /// `GDOC002`: an exported method has no documentation.
+    UndocumentedMethod,
+    /// `GDOC003`: a class has no documentation.
+    UndocumentedClass,
+    /// `GDOC004`: an exported property has no documentation.
+    UndocumentedProperty,
+    /// `GDOC005`: the base class of a class could not be inferred.
+    AmbiguousInherit,
+    /// `GDOC006`: documented classes inherit each other in a cycle.
+    InheritanceCycle,
+    /// `GDOC007`: some markdown features are not supported by a backend.
+    UnsupportedMarkdown,
+    /// `GDOC008`: a code block annotation contains an invalid line range.
+    InvalidLineRange,
+    /// `GDOC009`: the arguments of a `#[property]` attribute could not be parsed.
+    InvalidPropertyArguments,
+    /// `GDOC010`: a `#[doc(alias)]` is already used by another item.
+    ConflictingAlias,
+    /// `GDOC011`: classes in different modules have the same name.
+    ClassNameCollision,
+    /// `GDOC012`: a test generated by the gut backend contains no assertion.
+    TestWithoutAssertion,
+}
+
+/// What to do when a [`Lint`] is emitted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[serde(rename_all = "snake_case")]
+pub enum LintLevel {
+    /// Ignore the diagnostic (it is still logged at the debug level).
+    Allow,
+    /// Log a warning.
+    Warn,
+    /// Log an error, and make [`Builder::build`](crate::Builder::build) fail
+    /// once all files are generated.
+    Deny,
+}
+
+impl Lint {
+    /// All lints, in the order of their codes.
+    pub const ALL: [Lint; 12] = [
+        Lint::BrokenLink,
+        Lint::UndocumentedMethod,
+        Lint::UndocumentedClass,
+        Lint::UndocumentedProperty,
+        Lint::AmbiguousInherit,
+        Lint::InheritanceCycle,
+        Lint::UnsupportedMarkdown,
+        Lint::InvalidLineRange,
+        Lint::InvalidPropertyArguments,
+        Lint::ConflictingAlias,
+        Lint::ClassNameCollision,
+        Lint::TestWithoutAssertion,
+    ];
+
+    /// Stable code of the lint, like `GDOC001`.
+    pub fn code(self) -> &'static str {
+        match self {
+            Lint::BrokenLink => "GDOC001",
+            Lint::UndocumentedMethod => "GDOC002",
+            Lint::UndocumentedClass => "GDOC003",
+            Lint::UndocumentedProperty => "GDOC004",
+            Lint::AmbiguousInherit => "GDOC005",
+            Lint::InheritanceCycle => "GDOC006",
+            Lint::UnsupportedMarkdown => "GDOC007",
+            Lint::InvalidLineRange => "GDOC008",
+            Lint::InvalidPropertyArguments => "GDOC009",
+            Lint::ConflictingAlias => "GDOC010",
+            Lint::ClassNameCollision => "GDOC011",
+            Lint::TestWithoutAssertion => "GDOC012",
+        }
+    }
+
+    /// Name of the lint, like `broken_link`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Lint::BrokenLink => "broken_link",
+            Lint::UndocumentedMethod => "undocumented_method",
+            Lint::UndocumentedClass => "undocumented_class",
+            Lint::UndocumentedProperty => "undocumented_property",
+            Lint::AmbiguousInherit => "ambiguous_inherit",
+            Lint::InheritanceCycle => "inheritance_cycle",
+            Lint::UnsupportedMarkdown => "unsupported_markdown",
+            Lint::InvalidLineRange => "invalid_line_range",
+            Lint::InvalidPropertyArguments => "invalid_property_arguments",
+            Lint::ConflictingAlias => "conflicting_alias",
+            Lint::ClassNameCollision => "class_name_collision",
+            Lint::TestWithoutAssertion => "test_without_assertion",
+        }
+    }
+
+    /// Find a lint by its code or name (case-insensitive).
+    pub fn find(code_or_name: &str) -> Option<Lint> {
+        Self::ALL.into_iter().find(|lint| {
+            lint.code().eq_ignore_ascii_case(code_or_name)
+                || lint.name().eq_ignore_ascii_case(code_or_name)
+        })
+    }
+
+    /// Level of the lint if it is not set in the configuration file.
+    pub fn default_level(self) -> LintLevel {
+        match self {
+            Lint::UndocumentedMethod | Lint::UndocumentedClass | Lint::UndocumentedProperty => {
+                LintLevel::Allow
+            }
+            _ => LintLevel::Warn,
+        }
+    }
+
+    /// Long explanation of the lint: what triggers it, and how to fix it.
+    pub fn explanation(self) -> &'static str {
+        match self {
+            Lint::BrokenLink => {
+                r"A link in the documentation could not be resolved.
+
+Links like [`Name`] or [Name] are looked up in the godot classes and
+constants, in the `url_overrides` and `rename_classes` tables of the
+configuration file, and in the `#[doc(alias)]` of documented items.
+
+To fix it, check the spelling of the link, add the name to `url_overrides`,
+or write the destination explicitly: [Name](https://...)."
+            }
+            Lint::UndocumentedMethod => {
+                r"An exported method has no documentation comment.
+
+This lint is allowed by default. To fix it, add `///` comments to the method."
+            }
+            Lint::UndocumentedClass => {
+                r"A class has no documentation comment.
+
+This lint is allowed by default. To fix it, add `///` comments to the
+`struct` deriving `NativeClass`."
+            }
+            Lint::UndocumentedProperty => {
+                r"An exported property has no documentation comment.
+
+This lint is allowed by default. To fix it, add `///` comments to the field
+marked with `#[property]`."
+            }
+            Lint::AmbiguousInherit => {
+                r"A class has no `#[inherit]` attribute, and its methods use owner
+arguments of different godot classes, so its base class cannot be inferred.
+
+It is then documented as inheriting `Reference`. To fix it, add an
+`#[inherit(Class)]` attribute to the class."
+            }
+            Lint::InheritanceCycle => {
+                r"Documented classes inherit each other in a cycle, usually because of
+`rename_classes`: for example `A` inherits `B`, which is renamed to `A`.
+
+To fix it, check the `#[inherit]` attributes and the `rename_classes` table."
+            }
+            Lint::UnsupportedMarkdown => {
+                r"The documentation uses markdown features that the markdown backend
+cannot output, like footnotes. They are dropped from the generated files.
+
+To fix it, rewrite the documentation without these features, or disable the
+corresponding `markdown_options`."
+            }
+            Lint::InvalidLineRange => {
+                r"A code block annotation like ```gdscript{2,4-5} contains a range
+that is not of the form `<line>` or `<start>-<end>`, with `start <= end`.
+
+The invalid range is ignored. To fix it, correct the annotation."
+            }
+            Lint::InvalidPropertyArguments => {
+                r#"The arguments of a `#[property]` attribute could not be parsed, so
+its default value and accessors are not documented.
+
+To fix it, make sure the attribute is valid for gdnative, like
+`#[property(default = 10, get = "Self::get_x")]`."#
+            }
+            Lint::ConflictingAlias => {
+                r"A `#[doc(alias)]` is already the name of a class, or the alias of
+another item. Links to this alias may not resolve to the intended item.
+
+To fix it, choose another alias."
+            }
+            Lint::ClassNameCollision => {
+                r#"Classes in different modules have the same name. They are documented
+under their module path, like `graph::Interface`, in files like
+`graph-Interface.md`. Links and types using the bare name cannot tell them
+apart, and Godot cannot register both under the same name.
+
+To fix it, rename one of the classes, or rename it in the documentation with
+the `rename_classes` table of the configuration file:
+
+    [rename_classes]
+    "graph::Interface" = "GraphInterface""#
+            }
+            Lint::TestWithoutAssertion => {
+                r"A gdscript example became a gut test that contains no assertion (like
+`assert_eq(...)` or `assert(...)`), so it passes as long as it runs without
+error.
+
+To fix it, check the results of the example with assertions, or mark it as
+```gdscript,no_run if it is only illustrative. To make such tests fail the
+generation, deny this lint."
+            }
+        }
+    }
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.code(), self.name())
+    }
+}
+
+/// A diagnostic emitted while generating the documentation.
+///
+/// See [`BuildReport::diagnostics`](crate::BuildReport::diagnostics).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Diagnostic {
+    /// Kind of the diagnostic.
+    pub lint: Lint,
+    /// Level of `lint` when the diagnostic was emitted.
+    pub level: LintLevel,
+    /// Message of the diagnostic.
+    pub message: String,
+}
+
+#[derive(Default)]
+struct State {
+    levels: HashMap<Lint, LintLevel>,
+    /// Messages already emitted, to report each diagnostic once even if the
+    /// documentation is rendered by several backends.
+    emitted: HashSet<(Lint, String)>,
+    /// Diagnostics emitted, in order.
+    diagnostics: Vec<Diagnostic>,
+    denied: usize,
+    /// Diagnostics collected by [`defer`], instead of being emitted.
+    deferred: Option<Vec<(Lint, String)>>,
+}
+
+impl State {
+    fn level(&self, lint: Lint) -> LintLevel {
+        self.levels
+            .get(&lint)
+            .copied()
+            .unwrap_or_else(|| lint.default_level())
+    }
+}
+
+thread_local! {
+    static STATE: RefCell<State> = RefCell::new(State::default());
+}
+
+/// Set the levels of lints for the current thread, and reset the count of denied
+/// diagnostics.
+///
+/// Keys of `levels` are codes or names of lints. If a lint is set by both its code
+/// and its name, the level of its name is used.
+pub(crate) fn configure(levels: &HashMap<String, LintLevel>) {
+    let mut levels: Vec<_> = levels.iter().collect();
+    levels.sort_by_key(|(code_or_name, _)| {
+        let is_name = Lint::find(code_or_name)
+            .is_some_and(|lint| lint.name().eq_ignore_ascii_case(code_or_name));
+        (is_name, code_or_name.as_str())
+    });
+    let mut lint_levels = HashMap::new();
+    for (code_or_name, level) in levels {
+        match Lint::find(code_or_name) {
+            Some(lint) => {
+                lint_levels.insert(lint, *level);
+            }
+            None => log::warn!("unknown lint: {}", code_or_name),
+        }
+    }
+    STATE.with(|state| {
+        *state.borrow_mut() = State {
+            levels: lint_levels,
+            ..State::default()
+        }
+    })
+}
+
+/// Returns the number of denied diagnostics emitted since the last call to
+/// [`configure`].
+pub(crate) fn denied_count() -> usize {
+    STATE.with(|state| state.borrow().denied)
+}
+
+/// Returns the diagnostics emitted since the last call to [`configure`].
+pub(crate) fn emitted() -> Vec<Diagnostic> {
+    STATE.with(|state| state.borrow().diagnostics.clone())
+}
+
+/// Emit a diagnostic, according to the level of `lint`.
+///
+/// A diagnostic with the same lint and message is only emitted once.
+///
+/// Prefer the [`diagnostic!`] macro.
+pub(crate) fn emit(lint: Lint, message: fmt::Arguments) {
+    let message = message.to_string();
+    let level = STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        if let Some(deferred) = &mut state.deferred {
+            deferred.push((lint, message.clone()));
+            return None;
+        }
+        if !state.emitted.insert((lint, message.clone())) {
+            return None;
+        }
+        let level = state.level(lint);
+        if level == LintLevel::Deny {
+            state.denied += 1;
+        }
+        state.diagnostics.push(Diagnostic {
+            lint,
+            level,
+            message: message.clone(),
+        });
+        Some(level)
+    });
+    let Some(level) = level else { return };
+    match level {
+        LintLevel::Allow => log::debug!("[{}] {}", lint.code(), message),
+        LintLevel::Warn => log::warn!("[{}] {}", lint.code(), message),
+        LintLevel::Deny => log::error!("[{}] {}", lint.code(), message),
+    }
+}
+
+/// Run `f`, collecting the diagnostics it emits instead of emitting them.
+///
+/// Diagnostics are tracked per thread: this allows to run `f` on another thread,
+/// and to give its diagnostics to [`emit_deferred`] on the calling thread.
+pub(crate) fn defer<T>(f: impl FnOnce() -> T) -> (T, Vec<(Lint, String)>) {
+    let outer = STATE.with(|state| state.borrow_mut().deferred.replace(Vec::new()));
+    let result = f();
+    let deferred = STATE.with(|state| mem::replace(&mut state.borrow_mut().deferred, outer));
+    (result, deferred.unwrap_or_default())
+}
+
+/// Emit the diagnostics collected by [`defer`].
+pub(crate) fn emit_deferred(diagnostics: Vec<(Lint, String)>) {
+    for (lint, message) in diagnostics {
+        emit(lint, format_args!("{}", message));
+    }
+}
+
+/// Emit a diagnostic for a [`Lint`], with a message formatted like [`format!`].
+macro_rules! diagnostic {
+    ($lint:expr, $($arg:tt)+) => {
+        $crate::diagnostics::emit($lint, format_args!($($arg)+))
+    };
+}
+pub(crate) use diagnostic;
+
+/// Emit the diagnostics about missing documentation.
+pub(crate) fn check_documentation(documentation: &Documentation) {
+    let mut classes: Vec<_> = documentation.classes.values().collect();
+    classes.sort_unstable_by(|class1, class2| class1.name.cmp(&class2.name));
+    for class in classes {
+        if class.documentation.trim().is_empty() {
+            diagnostic!(
+                Lint::UndocumentedClass,
+                "class '{}' is undocumented",
+                class.name
+            );
+        }
+        for property in &class.properties {
+            if property.documentation.trim().is_empty() {
+                diagnostic!(
+                    Lint::UndocumentedProperty,
+                    "property '{}.{}' is undocumented",
+                    class.name,
+                    property.name
+                );
+            }
+        }
+        for method in &class.methods {
+            if method.documentation.trim().is_empty() {
+                diagnostic!(
+                    Lint::UndocumentedMethod,
+                    "method '{}.{}' is undocumented",
+                    class.name,
+                    method.name
+                );
+            }
+        }
+    }
+}
+
+/// Emit a diagnostic for each link of the documentation that cannot be
+/// resolved, with the item and the file it is in.
+///
+/// The documentation of a class is checked on its page, where `Self::member`
+/// refers to its members.
+///
+/// If there are any (and [`Lint::BrokenLink`] is not allowed), a table of the
+/// broken links is then logged.
+pub(crate) fn check_links(documentation: &Documentation, resolver: &Resolver) {
+    // (location, item, link)
+    let mut broken_links: Vec<(String, String, String)> = Vec::new();
+    let mut check = |resolver: &Resolver, text: &str, item: &str, location: String| {
+        for link in unresolved_links(text, resolver) {
+            diagnostic!(
+                Lint::BrokenLink,
+                "unresolved link to '{}' in {} ({})",
+                link,
+                item,
+                location
+            );
+            broken_links.push((location.clone(), item.to_string(), link));
+        }
+    };
+    check(
+        resolver,
+        &documentation.root_documentation,
+        "the crate documentation",
+        documentation.root_file.display().to_string(),
+    );
+    for module in &documentation.modules {
+        check(
+            resolver,
+            &module.documentation,
+            &format!("module '{}'", module.path),
+            module.file.display().to_string(),
+        );
+    }
+    let mut classes: Vec<_> = documentation.classes.iter().collect();
+    classes.sort_unstable_by_key(|(name, _)| *name);
+    for (name, class) in classes {
+        let resolver = &resolver.for_class(name);
+        let item = format!("class '{}'", class.name);
+        let location = format!("{}:{}", class.file.display(), class.line);
+        check(resolver, &class.documentation, &item, location.clone());
+        if let Some(note) = &class.deprecated {
+            check(resolver, note, &item, location.clone());
+        }
+        for tag in &class.tags {
+            check(resolver, &tag.value, &item, location.clone());
+        }
+        for property in &class.properties {
+            let item = format!("property '{}.{}'", class.name, property.name);
+            let location = format!("{}:{}", class.file.display(), property.line);
+            check(resolver, &property.documentation, &item, location.clone());
+            if let Some(note) = &property.deprecated {
+                check(resolver, note, &item, location.clone());
+            }
+            for tag in &property.tags {
+                check(resolver, &tag.value, &item, location.clone());
+            }
+        }
+        for method in &class.methods {
+            let item = format!("method '{}.{}'", class.name, method.name);
+            let location = format!("{}:{}", method.file.display(), method.line);
+            check(resolver, &method.documentation, &item, location.clone());
+            if let Some(note) = &method.deprecated {
+                check(resolver, note, &item, location.clone());
+            }
+            for tag in &method.tags {
+                check(resolver, &tag.value, &item, location.clone());
+            }
+        }
+    }
+
+    let level = STATE.with(|state| state.borrow().level(Lint::BrokenLink));
+    if broken_links.is_empty() || level == LintLevel::Allow {
+        return;
+    }
+    let location_width = broken_links.iter().map(|(location, ..)| location.len());
+    let item_width = broken_links.iter().map(|(_, item, _)| item.len());
+    let (location_width, item_width) = (
+        location_width.max().unwrap_or(0),
+        item_width.max().unwrap_or(0),
+    );
+    let mut table = format!("{} broken link(s):", broken_links.len());
+    for (location, item, link) in &broken_links {
+        let _ = write!(
+            table,
+            "\n  {:location_width$}  {:item_width$}  {}",
+            location, item, link
+        );
+    }
+    log::info!("{}", table);
+}
+
+/// Links of `text` that cannot be resolved.
+///
+/// Brackets are also used in plain text (like `array[0]`), so only what looks
+/// like an item is reported.
+fn unresolved_links(text: &str, resolver: &Resolver) -> Vec<String> {
+    let mut links = Vec::new();
+    let mut callback = |broken_link: BrokenLink| {
+        let (link, is_code) = strip_backticks(&broken_link.reference);
+        if resolver.resolve(link).is_none()
+            && (is_code || syn::parse_str::<syn::Path>(link).is_ok())
+        {
+            links.push(link.to_string());
+        }
+        None
+    };
+    Parser::new_with_broken_link_callback(text, Options::all(), Some(&mut callback)).for_each(drop);
+    links
+}
@@ -1,16 +1,56 @@
 use super::{
-    attributes_contains, get_docs, get_type_name, read_file_at, Documentation, GdnativeClass, Type,
+    attributes_contains, get_attributes_metas, get_constructor_defaults, get_deprecated,
+    get_doc_aliases, get_docs, get_summary, get_type_name, Documentation, GdnativeClass, Module,
+    Type,
+};
+use crate::{
+    diagnostics::{self, diagnostic, Lint},
+    Error,
+};
+use std::{
+    collections::{
+        hash_map::{DefaultHasher, Entry},
+        BTreeMap, BTreeSet, HashMap,
+    },
+    fs,
+    hash::{Hash, Hasher},
+    mem,
+    path::PathBuf,
 };
-use crate::Error;
-use std::{mem, path::PathBuf};
 use syn::{
     visit::{self, Visit},
-    ItemImpl, ItemMod, ItemStruct,
+    ExprMethodCall, ItemImpl, ItemMod, ItemStruct,
 };
 
+/// Editor classes meant to be extended by scripts running in the editor.
+///
+/// Classes inheriting one of these are always tool classes.
+const EDITOR_BASE_CLASSES: &[&str] = &[
+    "EditorDebuggerPlugin",
+    "EditorExportPlugin",
+    "EditorImportPlugin",
+    "EditorInspectorPlugin",
+    "EditorNode3DGizmoPlugin",
+    "EditorPlugin",
+    "EditorProperty",
+    "EditorResourceConversionPlugin",
+    "EditorResourcePreviewGenerator",
+    "EditorSceneFormatImporter",
+    "EditorSceneImporter",
+    "EditorScenePostImport",
+    "EditorScenePostImportPlugin",
+    "EditorScript",
+    "EditorSpatialGizmoPlugin",
+    "EditorSyntaxHighlighter",
+    "EditorTranslationParserPlugin",
+    "EditorVCSInterface",
+];
+
 /// Structure that builds the [`Documentation`] by visiting source files.
 ///
 /// It uses the visitor pattern implemented by [`syn::Visit`].
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(super) struct DocumentationBuilder {
     /// Documentation we are building along the way.
     pub(super) documentation: Documentation,
@@ -22,13 +62,224 @@ pub(super) struct DocumentationBuilder {
     pub(super) current_file: (PathBuf, bool),
     /// Path of the current module in `current_file`.
     pub(super) current_module: Vec<String>,
-    /// Error encountered.
-    ///
-    /// If it is some, the exploration will stop prematuraly and return it.
-    pub(super) error: Option<Error>,
+    /// Path of the current module from the root of the crate.
+    pub(super) module_path: Vec<String>,
+    /// Godot classes of the owner arguments found in each class's methods.
+    pub(super) owner_types: HashMap<String, BTreeSet<String>>,
+    /// Classes that do not have an `#[inherit]` attribute.
+    pub(super) missing_inherit: Vec<String>,
+    /// Constant field values found in the `new` constructor of each class.
+    pub(super) constructor_defaults: HashMap<String, Vec<(String, String)>>,
+    /// Classes registered with `add_tool_class::<Class>()`.
+    pub(super) tool_classes: BTreeSet<String>,
+    /// Keys of the classes whose `struct` was visited (as opposed to only their
+    /// `impl` blocks).
+    pub(super) structs: BTreeSet<String>,
+    /// Modules declared in their own file, left for
+    /// [`visit_module_files`](Self::visit_module_files).
+    pub(super) module_files: Vec<ModuleFile>,
+}
+
+/// A module declared with `mod module;`, whose items are in another file.
+#[derive(Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(super) struct ModuleFile {
+    /// Path of the file.
+    path: PathBuf,
+    /// `true` if the path is `module/mod.rs` rather that `module.rs`.
+    mod_rs: bool,
+    /// Path of the module from the root of the crate.
+    module_path: Vec<String>,
+    /// Documentation on the `mod module;` declaration.
+    documentation: String,
+    /// Index in [`Documentation::modules`] at which the modules of the file are
+    /// inserted, to keep them in declaration order.
+    module_index: usize,
+}
+
+impl ModuleFile {
+    /// The root file of the crate.
+    pub(super) fn root(path: PathBuf) -> Self {
+        Self {
+            path,
+            mod_rs: true,
+            module_path: Vec::new(),
+            documentation: String::new(),
+            module_index: 0,
+        }
+    }
+}
+
+/// Module files visited by [`DocumentationBuilder`], by path.
+///
+/// This is stored by [`Builder::incremental`](crate::Builder::incremental), so
+/// that only the files that changed are parsed again.
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct ParsedFiles {
+    pub(super) files: HashMap<PathBuf, ParsedFile>,
+}
+
+/// A module file visited by [`DocumentationBuilder`].
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(super) struct ParsedFile {
+    /// Hash of the content of the file and of its [`ModuleFile`].
+    hash: u64,
+    /// Result of visiting the file, before visiting the modules it declares.
+    builder: DocumentationBuilder,
 }
 
 impl DocumentationBuilder {
+    /// Create a builder that fills `documentation`, starting in `current_file`.
+    pub(super) fn new(documentation: Documentation, current_file: (PathBuf, bool)) -> Self {
+        Self {
+            documentation,
+            current_file,
+            current_module: Vec::new(),
+            module_path: Vec::new(),
+            owner_types: HashMap::new(),
+            missing_inherit: Vec::new(),
+            constructor_defaults: HashMap::new(),
+            tool_classes: BTreeSet::new(),
+            structs: BTreeSet::new(),
+            module_files: Vec::new(),
+        }
+    }
+
+    /// Visit the [`module_files`](Self::module_files) found so far, and
+    /// recursively the modules they declare.
+    ///
+    /// Files that did not change since they were stored in `cache` are not
+    /// parsed again. Returns every file that was visited.
+    ///
+    /// With the `rayon` feature, the files are read and visited in parallel.
+    pub(super) fn visit_module_files(&mut self, cache: &ParsedFiles) -> Result<ParsedFiles, Error> {
+        let module_files = mem::take(&mut self.module_files);
+        let visit = |module_file: &ModuleFile| {
+            diagnostics::defer(|| Self::visit_module_file(module_file, cache))
+        };
+        #[cfg(feature = "rayon")]
+        let builders: Vec<_> = {
+            use rayon::prelude::*;
+            module_files.par_iter().map(visit).collect()
+        };
+        #[cfg(not(feature = "rayon"))]
+        let builders: Vec<_> = module_files.iter().map(visit).collect();
+
+        let mut visited = Vec::new();
+        let mut parsed = ParsedFiles::default();
+        for (builder, emitted) in builders {
+            diagnostics::emit_deferred(emitted);
+            let (builder, files) = builder?;
+            visited.push(builder);
+            parsed.files.extend(files.files);
+        }
+        // merge from the end, so that the `module_index`es stay valid
+        for (module_file, builder) in module_files.iter().zip(visited).rev() {
+            self.merge(builder, module_file.module_index);
+        }
+        Ok(parsed)
+    }
+
+    /// Visit the file of `module_file`, and the modules it declares.
+    ///
+    /// The file is taken from `cache` if it did not change. It is not stored in
+    /// the returned files if visiting it emitted diagnostics, so that they are
+    /// reported again on the next build.
+    pub(super) fn visit_module_file(
+        module_file: &ModuleFile,
+        cache: &ParsedFiles,
+    ) -> Result<(Self, ParsedFiles), Error> {
+        let content = fs::read_to_string(&module_file.path)
+            .map_err(|err| Error::Io(module_file.path.clone(), err))?;
+        let mut hasher = DefaultHasher::new();
+        content.hash(&mut hasher);
+        module_file.path.hash(&mut hasher);
+        module_file.mod_rs.hash(&mut hasher);
+        module_file.module_path.hash(&mut hasher);
+        module_file.documentation.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let mut parsed = ParsedFiles::default();
+        let mut builder = match cache.files.get(&module_file.path) {
+            Some(file) if file.hash == hash => {
+                log::trace!("using cached {}", module_file.path.display());
+                parsed.files.insert(module_file.path.clone(), file.clone());
+                file.builder.clone()
+            }
+            _ => {
+                let (builder, emitted) =
+                    diagnostics::defer(|| Self::parse_module_file(module_file, &content));
+                let builder = builder?;
+                if emitted.is_empty() {
+                    let file = ParsedFile {
+                        hash,
+                        builder: builder.clone(),
+                    };
+                    parsed.files.insert(module_file.path.clone(), file);
+                }
+                diagnostics::emit_deferred(emitted);
+                builder
+            }
+        };
+        parsed
+            .files
+            .extend(builder.visit_module_files(cache)?.files);
+        Ok((builder, parsed))
+    }
+
+    /// Parse and visit the file of `module_file`, whose content is `content`,
+    /// without visiting the modules it declares.
+    fn parse_module_file(module_file: &ModuleFile, content: &str) -> Result<Self, Error> {
+        let file = syn::parse_file(content)?;
+        let mut builder = Self::new(
+            Documentation::empty(String::new(), PathBuf::new()),
+            (module_file.path.clone(), module_file.mod_rs),
+        );
+        builder.module_path = module_file.module_path.clone();
+        if module_file.module_path.is_empty() {
+            // the root file documents the crate
+            builder.documentation.root_documentation = get_docs(&file.attrs);
+        } else {
+            // keep the `///` comments on the declaration
+            let documentation = match (module_file.documentation.as_str(), get_docs(&file.attrs)) {
+                ("", documentation) => documentation,
+                (declaration, documentation) if documentation.is_empty() => declaration.to_string(),
+                (declaration, documentation) => format!("{}\n{}", declaration, documentation),
+            };
+            if !documentation.is_empty() {
+                let path = builder.module_path.join("::");
+                log::trace!("found documentation for module '{path}'");
+                builder.documentation.modules.push(Module {
+                    path,
+                    documentation,
+                    file: module_file.path.clone(),
+                });
+            }
+        }
+        for item in &file.items {
+            builder.visit_item(item);
+        }
+        Ok(builder)
+    }
+
+    /// Merge the result of visiting a module file, inserting its modules at
+    /// `module_index`.
+    fn merge(&mut self, other: Self, module_index: usize) {
+        let modules = &mut self.documentation.modules;
+        modules.splice(module_index..module_index, other.documentation.modules);
+        // keys contain the module path, so that they are distinct
+        self.documentation
+            .classes
+            .extend(other.documentation.classes);
+        self.owner_types.extend(other.owner_types);
+        self.missing_inherit.extend(other.missing_inherit);
+        self.constructor_defaults.extend(other.constructor_defaults);
+        self.tool_classes.extend(other.tool_classes);
+        self.structs.extend(other.structs);
+    }
+
     /// Given the current context and a module name, returns the 2 possible files
     /// corresponding to the module (aka `module/mod.rs` and `module.rs`).
     fn get_module_path(&self, module: &str) -> (PathBuf, PathBuf) {
@@ -48,6 +299,99 @@ impl DocumentationBuilder {
         })
     }
 
+    /// Key of the class `name` in the current module, like `graph::Interface`.
+    ///
+    /// Keys are simplified by [`disambiguate_classes`](Self::disambiguate_classes).
+    fn class_key(&self, name: &str) -> String {
+        self.module_path
+            .iter()
+            .map(String::as_str)
+            .chain([name])
+            .collect::<Vec<_>>()
+            .join("::")
+    }
+
+    /// Key classes by their name, except for classes with the same name in
+    /// different modules, that keep their module path (like `graph::Interface`).
+    ///
+    /// `impl` blocks of a class in another module than its `struct` are merged
+    /// into it, if this is not ambiguous.
+    ///
+    /// This must be called before any other post-processing.
+    pub(super) fn disambiguate_classes(&mut self) {
+        let mut keys_by_name: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (key, class) in &self.documentation.classes {
+            keys_by_name
+                .entry(class.name.clone())
+                .or_default()
+                .push(key.clone());
+        }
+        let mut new_keys = HashMap::new();
+        for (name, mut keys) in keys_by_name {
+            keys.sort_unstable();
+            let (struct_keys, impl_keys): (Vec<_>, Vec<_>) =
+                keys.into_iter().partition(|key| self.structs.contains(key));
+            // without a `struct`, the impl blocks are the classes
+            let (targets, impl_keys) = if struct_keys.is_empty() {
+                (impl_keys, Vec::new())
+            } else {
+                (struct_keys, impl_keys)
+            };
+            if targets.len() == 1 {
+                for key in targets.into_iter().chain(impl_keys) {
+                    new_keys.insert(key, name.clone());
+                }
+                continue;
+            }
+            diagnostic!(
+                Lint::ClassNameCollision,
+                "classes {} have the same name, they are documented with their module path",
+                targets
+                    .iter()
+                    .map(|key| format!("'{key}'"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            for key in impl_keys {
+                log::warn!(
+                    "cannot determine which class '{name}' the impl block for '{key}' belongs to"
+                );
+            }
+        }
+
+        // keys not in `new_keys` are kept
+        let rekey = |key: String| new_keys.get(&key).cloned().unwrap_or(key);
+        let mut classes: HashMap<String, GdnativeClass> = HashMap::new();
+        // visit the struct first, so that impl blocks are merged into it
+        let mut old_classes: Vec<_> = mem::take(&mut self.documentation.classes)
+            .into_iter()
+            .collect();
+        old_classes.sort_by_key(|(key, _)| !self.structs.contains(key));
+        for (key, class) in old_classes {
+            match classes.entry(rekey(key)) {
+                Entry::Occupied(mut entry) => entry.get_mut().methods.extend(class.methods),
+                Entry::Vacant(entry) => {
+                    entry.insert(class);
+                }
+            }
+        }
+        self.documentation.classes = classes;
+        for (key, owner_types) in mem::take(&mut self.owner_types) {
+            self.owner_types
+                .entry(rekey(key))
+                .or_default()
+                .extend(owner_types);
+        }
+        for (key, defaults) in mem::take(&mut self.constructor_defaults) {
+            self.constructor_defaults
+                .entry(rekey(key))
+                .or_insert(defaults);
+        }
+        for key in &mut self.missing_inherit {
+            *key = rekey(mem::take(key));
+        }
+    }
+
     /// Inner function for Visit::visit_item_impl
     ///
     /// Used for the early return
@@ -62,22 +406,122 @@ impl DocumentationBuilder {
                 }
             };
             log::trace!("found #[methods] impl block for '{}'", self_type);
+            let key = self.class_key(&self_type);
             let class = self
                 .documentation
                 .classes
-                .entry(self_type.clone())
+                .entry(key.clone())
                 .or_insert(GdnativeClass {
                     name: self_type,
                     inherit: String::new(),
                     documentation: String::new(),
+                    summary: String::new(),
+                    tags: Vec::new(),
                     properties: Vec::new(),
                     methods: Vec::new(),
+                    aliases: Vec::new(),
+                    is_tool: false,
+                    deprecated: None,
+                    module: self.module_path.join("::"),
                     file: PathBuf::new(),
+                    line: 0,
                 });
             for item in &impl_block.items {
                 if let syn::ImplItem::Method(method) = item {
-                    class.add_method(method, self.current_file.0.clone());
+                    if method.sig.ident == "new" {
+                        self.constructor_defaults.insert(
+                            key.clone(),
+                            get_constructor_defaults(&method.block, &class.name),
+                        );
+                    }
+                    if let Some(owner_type) = class.add_method(method, self.current_file.0.clone())
+                    {
+                        self.owner_types
+                            .entry(key.clone())
+                            .or_default()
+                            .insert(owner_type);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Use the values in the constructors as defaults for properties without
+    /// `#[property(default = ...)]`.
+    pub(super) fn fill_property_defaults(&mut self) {
+        for (class_name, defaults) in self.constructor_defaults.drain() {
+            let class = match self.documentation.classes.get_mut(&class_name) {
+                Some(class) => class,
+                None => continue,
+            };
+            for (field, value) in defaults {
+                if let Some(property) = class
+                    .properties
+                    .iter_mut()
+                    .find(|property| property.name == field)
+                {
+                    property.default.get_or_insert(value);
+                }
+            }
+        }
+    }
+
+    /// Set [`GdnativeClass::is_tool`] for classes registered as tool classes, or
+    /// inheriting one of the [`EDITOR_BASE_CLASSES`].
+    ///
+    /// This must be called after [`infer_inherits`](Self::infer_inherits).
+    pub(super) fn mark_tool_classes(&mut self) {
+        let tool_classes: Vec<String> = self
+            .documentation
+            .classes
+            .iter()
+            .filter(|(key, class)| {
+                self.tool_classes.contains(&class.name)
+                    || self
+                        .documentation
+                        .inheritance_chain(key)
+                        .into_iter()
+                        .any(|parent| EDITOR_BASE_CLASSES.contains(&parent))
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+        for name in tool_classes {
+            log::trace!("'{name}' is a tool class");
+            if let Some(class) = self.documentation.classes.get_mut(&name) {
+                class.is_tool = true;
+            }
+        }
+    }
+
+    /// Infer the base class of classes without an `#[inherit]` attribute, from the
+    /// owner arguments of their methods.
+    ///
+    /// If there is no owner argument, or if they disagree, this falls back to
+    /// `Reference`, which is what `gdnative` does.
+    pub(super) fn infer_inherits(&mut self) {
+        for class_name in self.missing_inherit.drain(..) {
+            let class = match self.documentation.classes.get_mut(&class_name) {
+                Some(class) => class,
+                None => continue,
+            };
+            let owner_types = self.owner_types.remove(&class_name).unwrap_or_default();
+            let mut owner_types = owner_types.into_iter();
+            match (owner_types.next(), owner_types.next()) {
+                (Some(owner_type), None) => {
+                    log::debug!("inferred that '{class_name}' inherits '{owner_type}'");
+                    class.inherit = owner_type;
+                }
+                (Some(first), Some(second)) => {
+                    let candidates: Vec<_> =
+                        [first, second].into_iter().chain(owner_types).collect();
+                    diagnostic!(
+                        Lint::AmbiguousInherit,
+                        "cannot infer the base class of '{class_name}': its methods use the owner types {}. Defaulting to 'Reference', add an #[inherit] attribute to remove this warning",
+                        candidates.join(", ")
+                    );
+                    class.inherit = String::from("Reference");
                 }
+                (None, _) => class.inherit = String::from("Reference"),
             }
         }
     }
@@ -85,74 +529,62 @@ impl DocumentationBuilder {
 
 impl<'ast> Visit<'ast> for DocumentationBuilder {
     fn visit_item_mod(&mut self, module: &'ast ItemMod) {
-        if self.error.is_some() {
+        if module.content.is_none() {
+            let module_name = module.ident.to_string();
+            let (mod_rs, file_rs) = self.get_module_path(&module_name);
+            let (path, mod_rs) = if mod_rs.exists() {
+                (mod_rs, true)
+            } else {
+                (file_rs, false)
+            };
+            let mut module_path = self.module_path.clone();
+            module_path.push(module_name);
+            self.module_files.push(ModuleFile {
+                path,
+                mod_rs,
+                module_path,
+                documentation: get_docs(&module.attrs),
+                module_index: self.documentation.modules.len(),
+            });
             return;
         }
 
-        let file_module: ItemMod;
-
-        let (module, old_data) = match &module.content {
-            Some(_) => (module, None),
-            None => {
-                let module_name = module.ident.to_string();
-                let (mod_rs, file_rs) = self.get_module_path(&module_name);
-                let (path, mod_rs) = if mod_rs.exists() {
-                    (mod_rs, true)
-                } else {
-                    (file_rs, false)
-                };
-                let file = match read_file_at(&path) {
-                    Ok(file) => file,
-                    Err(err) => {
-                        self.error = Some(err);
-                        return;
-                    }
-                };
-                file_module = ItemMod {
-                    attrs: file.attrs,
-                    vis: module.vis.clone(),
-                    mod_token: module.mod_token,
-                    ident: module.ident.clone(),
-                    content: Some((syn::token::Brace::default(), file.items)),
-                    semi: None,
-                };
-                let old_data = (
-                    mem::take(&mut self.current_file),
-                    mem::take(&mut self.current_module),
-                );
-                self.current_file = (path, mod_rs);
-                (&file_module, Some(old_data))
-            }
-        };
-
-        visit::visit_item_mod(self, module);
-        if let Some((old_file, old_module)) = old_data {
-            self.current_file = old_file;
-            self.current_module = old_module;
+        self.module_path.push(module.ident.to_string());
+        let documentation = get_docs(&module.attrs);
+        if !documentation.is_empty() {
+            let path = self.module_path.join("::");
+            log::trace!("found documentation for module '{path}'");
+            self.documentation.modules.push(Module {
+                path,
+                documentation,
+                file: self.current_file.0.clone(),
+            });
         }
+        visit::visit_item_mod(self, module);
+        self.module_path.pop();
     }
 
     fn visit_item_struct(&mut self, strukt: &'ast ItemStruct) {
-        if self.error.is_some() {
-            return;
-        }
         let mut implement_native_class = false;
-        let mut inherit = String::from("Reference");
-        for attr in &strukt.attrs {
-            if let Ok(syn::Meta::List(syn::MetaList { path, nested, .. })) = attr.parse_meta() {
+        let mut inherit = None;
+        for meta in get_attributes_metas(&strukt.attrs) {
+            if let syn::Meta::List(syn::MetaList { path, nested, .. }) = meta {
                 if path.is_ident("inherit") && nested.len() == 1 {
                     if let Some(syn::NestedMeta::Meta(syn::Meta::Path(path))) = nested.first() {
                         // TODO: support path of the form "gdnative::Class"
                         if let Some(class) = path.get_ident() {
-                            inherit = class.to_string();
-                        }
-                    }
-                } else if path.is_ident("derive") && nested.len() == 1 {
-                    if let Some(syn::NestedMeta::Meta(syn::Meta::Path(path))) = nested.first() {
-                        if path.is_ident("NativeClass") {
-                            implement_native_class = true;
+                            inherit = Some(class.to_string());
                         }
                     }
+                } else if path.is_ident("derive") {
+                    // accept both `NativeClass` and `gdnative::NativeClass`
+                    implement_native_class |= nested.iter().any(|derived| {
+                        matches!(
+                            derived,
+                            syn::NestedMeta::Meta(syn::Meta::Path(path))
+                                if path.segments.last().is_some_and(|segment| segment.ident == "NativeClass")
+                        )
+                    });
                 }
             }
         }
@@ -162,32 +594,68 @@ impl<'ast> Visit<'ast> for DocumentationBuilder {
         }
 
         let self_type = strukt.ident.to_string();
-        log::trace!("found GDNative class '{self_type}' that inherits '{inherit}'");
-        // FIXME: warn or error if we already visited a struct with the same name
-        // But be careful ! We *could* have encountered the name in an `impl` block, in which case no warning is warranted.
+        let key = self.class_key(&self_type);
+        match &inherit {
+            Some(inherit) => {
+                log::trace!("found GDNative class '{key}' that inherits '{inherit}'")
+            }
+            None => {
+                log::trace!("found GDNative class '{key}' without #[inherit]");
+                self.missing_inherit.push(key.clone());
+            }
+        }
+        self.structs.insert(key.clone());
         let class = self
             .documentation
             .classes
-            .entry(self_type.clone())
+            .entry(key)
             .or_insert(GdnativeClass {
                 name: self_type,
                 inherit: String::new(),
                 documentation: String::new(),
+                summary: String::new(),
+                tags: Vec::new(),
                 properties: Vec::new(),
                 methods: Vec::new(),
-                file: self.current_file.0.clone(),
+                aliases: Vec::new(),
+                is_tool: false,
+                deprecated: None,
+                module: String::new(),
+                file: PathBuf::new(),
+                line: 0,
             });
         if let syn::Fields::Named(fields) = &strukt.fields {
             class.get_properties(fields)
         }
-        class.inherit = inherit;
+        // if missing, this is filled by `infer_inherits`
+        class.inherit = inherit.unwrap_or_default();
         class.documentation = get_docs(&strukt.attrs);
+        class.summary = get_summary(&class.documentation);
+        class.aliases = get_doc_aliases(&strukt.attrs);
+        class.deprecated = get_deprecated(&strukt.attrs);
+        class.module = self.module_path.join("::");
+        class.file = self.current_file.0.clone();
+        class.line = strukt.ident.span().start().line;
     }
 
-    fn visit_item_impl(&mut self, impl_block: &'ast ItemImpl) {
-        if self.error.is_some() {
-            return;
+    fn visit_expr_method_call(&mut self, call: &'ast ExprMethodCall) {
+        if call.method == "add_tool_class" {
+            let class =
+                call.turbofish
+                    .as_ref()
+                    .and_then(|turbofish| match turbofish.args.first()? {
+                        syn::GenericMethodArgument::Type(typ) => get_type_name(typ),
+                        syn::GenericMethodArgument::Const(_) => None,
+                    });
+            if let Some(Type::Named(class)) = class {
+                self.tool_classes.insert(class);
+            }
         }
+
+        visit::visit_expr_method_call(self, call)
+    }
+
+    fn visit_item_impl(&mut self, impl_block: &'ast ItemImpl) {
         self.visit_item_impl_inner(impl_block);
 
         visit::visit_item_impl(self, impl_block)
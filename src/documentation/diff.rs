@@ -0,0 +1,287 @@
+//! Comparison of two [`Documentation`]s.
+
+use super::{Documentation, GdnativeClass, Method, ParameterAttribute, Type};
+use std::fmt;
+
+/// Differences between two [`Documentation`]s.
+///
+/// This is obtained via [`Documentation::diff`]. All lists are sorted by name.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DocumentationDiff {
+    /// Classes only present in the new documentation.
+    pub added_classes: Vec<String>,
+    /// Classes only present in the old documentation.
+    pub removed_classes: Vec<String>,
+    /// Classes present in both, but with a different API.
+    pub changed_classes: Vec<ClassDiff>,
+}
+
+/// Differences in the API of a class.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ClassDiff {
+    /// Name of the class.
+    pub name: String,
+    /// Old and new inherited classes, if they differ.
+    pub inherit: Option<(String, String)>,
+    /// Methods only present in the new class.
+    pub added_methods: Vec<String>,
+    /// Methods only present in the old class.
+    pub removed_methods: Vec<String>,
+    /// Methods present in both classes, but with a different signature.
+    pub changed_methods: Vec<MethodDiff>,
+    /// Properties only present in the new class.
+    pub added_properties: Vec<String>,
+    /// Properties only present in the old class.
+    pub removed_properties: Vec<String>,
+    /// Properties present in both classes, with their old and new types.
+    pub changed_properties: Vec<(String, Type, Type)>,
+}
+
+/// Differences in the signature of a method.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MethodDiff {
+    /// Name of the method.
+    pub name: String,
+    /// Old and new parameters, if they differ.
+    pub parameters: Option<(Parameters, Parameters)>,
+    /// Old and new return types, if they differ.
+    pub return_type: Option<(Type, Type)>,
+}
+
+/// Parameters of a method, as in [`Method::parameters`].
+pub type Parameters = Vec<(String, Type, ParameterAttribute)>;
+
+impl Documentation {
+    /// Compare `self` (the old documentation) with `new`, and report the changes in
+    /// the API.
+    ///
+    /// Only the API is compared: changes in the documentation text or in the
+    /// source location of items are ignored.
+    pub fn diff(&self, new: &Documentation) -> DocumentationDiff {
+        let mut diff = DocumentationDiff::default();
+        for (name, old_class) in &self.classes {
+            match new.classes.get(name) {
+                Some(new_class) => {
+                    let class_diff = ClassDiff::new(name, old_class, new_class);
+                    if !class_diff.is_empty() {
+                        diff.changed_classes.push(class_diff)
+                    }
+                }
+                None => diff.removed_classes.push(name.clone()),
+            }
+        }
+        for name in new.classes.keys() {
+            if !self.classes.contains_key(name) {
+                diff.added_classes.push(name.clone())
+            }
+        }
+        diff.added_classes.sort_unstable();
+        diff.removed_classes.sort_unstable();
+        diff.changed_classes
+            .sort_unstable_by(|class1, class2| class1.name.cmp(&class2.name));
+        diff
+    }
+}
+
+impl DocumentationDiff {
+    /// Returns `true` if the two documentations have the same API.
+    pub fn is_empty(&self) -> bool {
+        self.added_classes.is_empty()
+            && self.removed_classes.is_empty()
+            && self.changed_classes.is_empty()
+    }
+}
+
+impl ClassDiff {
+    fn new(name: &str, old: &GdnativeClass, new: &GdnativeClass) -> Self {
+        let mut diff = Self {
+            name: name.to_string(),
+            ..Self::default()
+        };
+        if old.inherit != new.inherit {
+            diff.inherit = Some((old.inherit.clone(), new.inherit.clone()));
+        }
+
+        fn find_method<'a>(methods: &'a [Method], name: &str) -> Option<&'a Method> {
+            methods.iter().find(|method| method.name == name)
+        }
+        for old_method in &old.methods {
+            match find_method(&new.methods, &old_method.name) {
+                Some(new_method) => {
+                    if let Some(method_diff) = MethodDiff::new(old_method, new_method) {
+                        diff.changed_methods.push(method_diff)
+                    }
+                }
+                None => diff.removed_methods.push(old_method.name.clone()),
+            }
+        }
+        for new_method in &new.methods {
+            if find_method(&old.methods, &new_method.name).is_none() {
+                diff.added_methods.push(new_method.name.clone())
+            }
+        }
+
+        for old_property in &old.properties {
+            match new
+                .properties
+                .iter()
+                .find(|property| property.name == old_property.name)
+            {
+                Some(new_property) => {
+                    if old_property.typ != new_property.typ {
+                        diff.changed_properties.push((
+                            old_property.name.clone(),
+                            old_property.typ.clone(),
+                            new_property.typ.clone(),
+                        ))
+                    }
+                }
+                None => diff.removed_properties.push(old_property.name.clone()),
+            }
+        }
+        for new_property in &new.properties {
+            if !old
+                .properties
+                .iter()
+                .any(|property| property.name == new_property.name)
+            {
+                diff.added_properties.push(new_property.name.clone())
+            }
+        }
+
+        diff.added_methods.sort_unstable();
+        diff.removed_methods.sort_unstable();
+        diff.changed_methods
+            .sort_unstable_by(|method1, method2| method1.name.cmp(&method2.name));
+        diff.added_properties.sort_unstable();
+        diff.removed_properties.sort_unstable();
+        diff.changed_properties
+            .sort_unstable_by(|p1, p2| p1.0.cmp(&p2.0));
+        diff
+    }
+
+    /// Returns `true` if the two classes have the same API.
+    pub fn is_empty(&self) -> bool {
+        self.inherit.is_none()
+            && self.added_methods.is_empty()
+            && self.removed_methods.is_empty()
+            && self.changed_methods.is_empty()
+            && self.added_properties.is_empty()
+            && self.removed_properties.is_empty()
+            && self.changed_properties.is_empty()
+    }
+}
+
+impl MethodDiff {
+    /// Returns `None` if the two methods have the same signature.
+    fn new(old: &Method, new: &Method) -> Option<Self> {
+        let parameters = if old.parameters != new.parameters {
+            Some((old.parameters.clone(), new.parameters.clone()))
+        } else {
+            None
+        };
+        let return_type = if old.return_type != new.return_type {
+            Some((old.return_type.clone(), new.return_type.clone()))
+        } else {
+            None
+        };
+        if parameters.is_none() && return_type.is_none() {
+            None
+        } else {
+            Some(Self {
+                name: old.name.clone(),
+                parameters,
+                return_type,
+            })
+        }
+    }
+}
+
+/// Write a changelog-like summary, one change per line.
+impl fmt::Display for DocumentationDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for class in &self.added_classes {
+            writeln!(f, "+ class {}", class)?;
+        }
+        for class in &self.removed_classes {
+            writeln!(f, "- class {}", class)?;
+        }
+        for class in &self.changed_classes {
+            let name = &class.name;
+            if let Some((old, new)) = &class.inherit {
+                writeln!(f, "~ class {}: inherit {} -> {}", name, old, new)?;
+            }
+            for method in &class.added_methods {
+                writeln!(f, "+ method {}.{}", name, method)?;
+            }
+            for method in &class.removed_methods {
+                writeln!(f, "- method {}.{}", name, method)?;
+            }
+            for method in &class.changed_methods {
+                if let Some((old, new)) = &method.parameters {
+                    writeln!(
+                        f,
+                        "~ method {}.{}: parameters ({}) -> ({})",
+                        name,
+                        method.name,
+                        DisplayParameters(old),
+                        DisplayParameters(new)
+                    )?;
+                }
+                if let Some((old, new)) = &method.return_type {
+                    writeln!(
+                        f,
+                        "~ method {}.{}: return type {} -> {}",
+                        name,
+                        method.name,
+                        DisplayType(old),
+                        DisplayType(new)
+                    )?;
+                }
+            }
+            for property in &class.added_properties {
+                writeln!(f, "+ property {}.{}", name, property)?;
+            }
+            for property in &class.removed_properties {
+                writeln!(f, "- property {}.{}", name, property)?;
+            }
+            for (property, old, new) in &class.changed_properties {
+                writeln!(
+                    f,
+                    "~ property {}.{}: type {} -> {}",
+                    name,
+                    property,
+                    DisplayType(old),
+                    DisplayType(new)
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+struct DisplayType<'a>(&'a Type);
+
+impl fmt::Display for DisplayType<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Type::Option(name) => write!(f, "{} (opt)", name),
+            Type::Named(name) => f.write_str(name),
+            Type::Unit => f.write_str("void"),
+        }
+    }
+}
+
+struct DisplayParameters<'a>(&'a [(String, Type, ParameterAttribute)]);
+
+impl fmt::Display for DisplayParameters<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, (name, typ, _)) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}: {}", name, DisplayType(typ))?;
+        }
+        Ok(())
+    }
+}
@@ -1,13 +1,5 @@
 use super::Type;
-use crate::Error;
-
-/// Read and parse the file at the given `path` with `syn`, reporting any error.
-pub(super) fn read_file_at(path: &std::path::Path) -> Result<syn::File, Error> {
-    match std::fs::read_to_string(path) {
-        Ok(content) => Ok(syn::parse_file(&content)?),
-        Err(err) => Err(Error::Io(path.to_path_buf(), err)),
-    }
-}
+use crate::diagnostics::{diagnostic, Lint};
 
 /// Returns whether or not `attr` contains `#[attribute]`.
 pub(super) fn attributes_contains(attrs: &[syn::Attribute], attribute: &str) -> bool {
@@ -16,6 +8,312 @@ pub(super) fn attributes_contains(attrs: &[syn::Attribute], attribute: &str) ->
         .any(|attr| attr.path.is_ident(attribute) && attr.tokens.is_empty())
 }
 
+/// Parse `attrs` into their meta representation, unwrapping the attributes inside
+/// `#[cfg_attr(condition, attributes...)]`.
+///
+/// Attributes that cannot be parsed are ignored.
+pub(super) fn get_attributes_metas(attrs: &[syn::Attribute]) -> Vec<syn::Meta> {
+    fn push_meta(metas: &mut Vec<syn::Meta>, meta: syn::Meta) {
+        match meta {
+            syn::Meta::List(syn::MetaList { path, nested, .. }) if path.is_ident("cfg_attr") => {
+                // skip the condition
+                for nested in nested.into_iter().skip(1) {
+                    if let syn::NestedMeta::Meta(meta) = nested {
+                        push_meta(metas, meta)
+                    }
+                }
+            }
+            meta => metas.push(meta),
+        }
+    }
+
+    let mut metas = Vec::new();
+    for attr in attrs {
+        if let Ok(meta) = attr.parse_meta() {
+            push_meta(&mut metas, meta)
+        }
+    }
+    metas
+}
+
+/// Returns the arguments of `#[attribute]` or `#[attribute(...)]` in `attrs`.
+///
+/// Returns `None` if the attribute is not present.
+pub(super) fn get_attribute_arguments(
+    attrs: &[syn::Attribute],
+    attribute: &str,
+) -> Option<Vec<syn::NestedMeta>> {
+    let attr = attrs.iter().find(|attr| attr.path.is_ident(attribute))?;
+    if attr.tokens.is_empty() {
+        return Some(Vec::new());
+    }
+    match attr.parse_meta() {
+        Ok(syn::Meta::List(syn::MetaList { nested, .. })) => Some(nested.into_iter().collect()),
+        _ => None,
+    }
+}
+
+/// Returns the mode of the `rpc = "..."` argument of `#[method(...)]`, if any.
+pub(super) fn get_rpc_mode(method_arguments: &[syn::NestedMeta]) -> Option<super::RpcMode> {
+    method_arguments.iter().find_map(|argument| match argument {
+        syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+            path,
+            lit: syn::Lit::Str(mode),
+            ..
+        })) if path.is_ident("rpc") => {
+            let rpc_mode = super::RpcMode::from_gdnative(&mode.value());
+            if rpc_mode.is_none() && mode.value() != "disabled" {
+                log::warn!("unknown rpc mode: '{}'", mode.value());
+            }
+            rpc_mode
+        }
+        _ => None,
+    })
+}
+
+/// Argument of `#[property(...)]`: either `name` or `name = value`.
+pub(super) struct PropertyArgument {
+    pub(super) name: String,
+    pub(super) value: Option<syn::Expr>,
+}
+
+impl syn::parse::Parse for PropertyArgument {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let name: syn::Ident = input.parse()?;
+        let value = if input.peek(syn::Token![=]) {
+            input.parse::<syn::Token![=]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(Self {
+            name: name.to_string(),
+            value,
+        })
+    }
+}
+
+/// Returns the arguments of `#[property]` or `#[property(...)]` in `attrs`.
+///
+/// Unlike [`get_attribute_arguments`], this accepts arbitrary expressions as values
+/// (`#[property(default = Vector2::new(1.0, 2.0))]`).
+///
+/// Returns `None` if the attribute is not present.
+pub(super) fn get_property_arguments(attrs: &[syn::Attribute]) -> Option<Vec<PropertyArgument>> {
+    let attr = attrs.iter().find(|attr| attr.path.is_ident("property"))?;
+    if attr.tokens.is_empty() {
+        return Some(Vec::new());
+    }
+    match attr.parse_args_with(
+        syn::punctuated::Punctuated::<PropertyArgument, syn::Token![,]>::parse_terminated,
+    ) {
+        Ok(arguments) => Some(arguments.into_iter().collect()),
+        Err(err) => {
+            diagnostic!(
+                Lint::InvalidPropertyArguments,
+                "failed to parse #[property] arguments: {}",
+                err
+            );
+            Some(Vec::new())
+        }
+    }
+}
+
+/// Returns the name of the method in an accessor argument of `#[property]`, like
+/// `get = "Self::get_x"`.
+pub(super) fn get_accessor_name(value: &syn::Expr) -> Option<String> {
+    match value {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(path),
+            ..
+        }) => {
+            let path = path.value();
+            let name = path.rsplit("::").next().unwrap_or_default().trim();
+            Some(name.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Returns `node` as it is written in the source code.
+pub(super) fn source_text<T: syn::spanned::Spanned + quote::ToTokens>(node: &T) -> String {
+    node.span()
+        .source_text()
+        .unwrap_or_else(|| node.to_token_stream().to_string())
+}
+
+/// Returns `true` if `expr` does not depend on the surrounding code, and is thus
+/// suitable for documenting a default value.
+///
+/// This accepts literals, constants (`Color::RED`), and calls or macros whose
+/// arguments are also accepted (`Vector2::new(1.0, 2.0)`, `"a".to_string()`,
+/// `vec![]`). Local variables like `x` and `Default::default()` are rejected.
+fn is_constant_expression(expr: &syn::Expr) -> bool {
+    match expr {
+        syn::Expr::Lit(_) => true,
+        syn::Expr::Unary(unary) => is_constant_expression(&unary.expr),
+        syn::Expr::Paren(paren) => is_constant_expression(&paren.expr),
+        syn::Expr::Path(path) => path.path.segments.len() > 1 || path.path.is_ident("None"),
+        syn::Expr::Call(call) => match call.func.as_ref() {
+            // `Default::default()` does not tell anything useful
+            syn::Expr::Path(path) => {
+                path.path
+                    .segments
+                    .last()
+                    .is_some_and(|segment| segment.ident != "default")
+                    && call.args.iter().all(is_constant_expression)
+            }
+            _ => false,
+        },
+        syn::Expr::MethodCall(call) => {
+            is_constant_expression(&call.receiver) && call.args.iter().all(is_constant_expression)
+        }
+        syn::Expr::Array(array) => array.elems.iter().all(is_constant_expression),
+        syn::Expr::Tuple(tuple) => tuple.elems.iter().all(is_constant_expression),
+        syn::Expr::Macro(_) => true,
+        _ => false,
+    }
+}
+
+/// Find the fields initialized in the constructor `body` of the class
+/// `self_type`, and returns those whose value is constant, with this value.
+///
+/// This looks for the first `Self { ... }` or `SelfType { ... }` expression.
+pub(super) fn get_constructor_defaults(
+    body: &syn::Block,
+    self_type: &str,
+) -> Vec<(String, String)> {
+    use syn::visit::Visit;
+
+    struct FindConstructor<'a, 'ast> {
+        self_type: &'a str,
+        found: Option<&'ast syn::ExprStruct>,
+    }
+
+    impl<'a, 'ast> Visit<'ast> for FindConstructor<'a, 'ast> {
+        fn visit_expr_struct(&mut self, expr: &'ast syn::ExprStruct) {
+            if self.found.is_none()
+                && (expr.path.is_ident("Self") || expr.path.is_ident(self.self_type))
+            {
+                self.found = Some(expr);
+            }
+            syn::visit::visit_expr_struct(self, expr)
+        }
+    }
+
+    let mut finder = FindConstructor {
+        self_type,
+        found: None,
+    };
+    finder.visit_block(body);
+    let mut defaults = Vec::new();
+    if let Some(constructor) = finder.found {
+        for field in &constructor.fields {
+            if let (syn::Member::Named(name), Some(_)) = (&field.member, field.colon_token) {
+                if is_constant_expression(&field.expr) {
+                    defaults.push((name.to_string(), source_text(&field.expr)))
+                }
+            }
+        }
+    }
+    defaults
+}
+
+/// Returns `true` if `body` creates an instance with `Instance::emplace`.
+pub(super) fn calls_emplace(body: &syn::Block) -> bool {
+    use syn::visit::Visit;
+
+    #[derive(Default)]
+    struct FindEmplace {
+        found: bool,
+    }
+
+    impl<'ast> Visit<'ast> for FindEmplace {
+        fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+            self.found |= call.method == "emplace";
+            syn::visit::visit_expr_method_call(self, call)
+        }
+
+        fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+            if let syn::Expr::Path(syn::ExprPath { path, .. }) = call.func.as_ref() {
+                self.found |= path
+                    .segments
+                    .last()
+                    .is_some_and(|segment| segment.ident == "emplace");
+            }
+            syn::visit::visit_expr_call(self, call)
+        }
+    }
+
+    let mut finder = FindEmplace::default();
+    finder.visit_block(body);
+    finder.found
+}
+
+/// Returns the note of a `#[deprecated]` attribute in `attrs`, if any.
+///
+/// A bare `#[deprecated]` gives an empty note.
+pub(super) fn get_deprecated(attrs: &[syn::Attribute]) -> Option<String> {
+    let attr = attrs.iter().find(|attr| attr.path.is_ident("deprecated"))?;
+    match attr.parse_meta() {
+        Ok(syn::Meta::NameValue(syn::MetaNameValue {
+            lit: syn::Lit::Str(note),
+            ..
+        })) => Some(note.value()),
+        Ok(syn::Meta::List(syn::MetaList { nested, .. })) => {
+            for meta in nested {
+                if let syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(note),
+                    ..
+                })) = meta
+                {
+                    if path.is_ident("note") {
+                        return Some(note.value());
+                    }
+                }
+            }
+            Some(String::new())
+        }
+        _ => Some(String::new()),
+    }
+}
+
+/// Get the aliases of an item, declared with `#[doc(alias = "name")]` or
+/// `#[doc(alias("name1", "name2"))]`.
+pub(super) fn get_doc_aliases(attrs: &[syn::Attribute]) -> Vec<String> {
+    let mut aliases = Vec::new();
+    for attr in attrs {
+        if !attr.path.is_ident("doc") {
+            continue;
+        }
+        let nested = match attr.parse_meta() {
+            Ok(syn::Meta::List(syn::MetaList { nested, .. })) => nested,
+            _ => continue,
+        };
+        for meta in nested {
+            match meta {
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(alias),
+                    ..
+                })) if path.is_ident("alias") => aliases.push(alias.value()),
+                syn::NestedMeta::Meta(syn::Meta::List(syn::MetaList { path, nested, .. }))
+                    if path.is_ident("alias") =>
+                {
+                    for alias in nested {
+                        if let syn::NestedMeta::Lit(syn::Lit::Str(alias)) = alias {
+                            aliases.push(alias.value())
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    aliases
+}
+
 /// Get this type's base name if it has one.
 pub(super) fn get_type_name(typ: &syn::Type) -> Option<Type> {
     match typ {
@@ -56,6 +354,46 @@ pub(super) fn get_type_name(typ: &syn::Type) -> Option<Type> {
     }
 }
 
+/// Returns whether `arg` is the owner (or base) argument of a method.
+///
+/// This is either marked with `#[base]`, or named `owner`/`base` (possibly with
+/// leading underscores).
+pub(super) fn is_owner_argument(arg: &syn::PatType) -> bool {
+    if attributes_contains(&arg.attrs, "base") {
+        return true;
+    }
+    match arg.pat.as_ref() {
+        syn::Pat::Ident(syn::PatIdent { ident, .. }) => {
+            matches!(ident.to_string().trim_start_matches('_'), "owner" | "base")
+        }
+        _ => false,
+    }
+}
+
+/// Get the godot class of an owner argument, of the form `&Class`,
+/// `TRef<Class>` or `Ref<Class>`.
+pub(super) fn get_owner_type(typ: &syn::Type) -> Option<String> {
+    match typ {
+        syn::Type::Reference(reference) => get_owner_type(&reference.elem),
+        syn::Type::Path(path) => {
+            let path_end = path.path.segments.last()?;
+            match &path_end.arguments {
+                syn::PathArguments::None => Some(path_end.ident.to_string()),
+                syn::PathArguments::AngleBracketed(arguments)
+                    if path_end.ident == "TRef" || path_end.ident == "Ref" =>
+                {
+                    arguments.args.iter().find_map(|argument| match argument {
+                        syn::GenericArgument::Type(typ) => get_owner_type(typ),
+                        _ => None,
+                    })
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
 /// Extract '\n'-separated documentation from `attrs`.
 pub(super) fn get_docs(attrs: &[syn::Attribute]) -> String {
     let mut doc = String::new();
@@ -80,3 +418,49 @@ pub(super) fn get_docs(attrs: &[syn::Attribute]) -> String {
     }
     doc
 }
+
+/// Extract the first sentence of the first paragraph of `documentation`.
+///
+/// The result is still markdown, but fits on a single line.
+pub(super) fn get_summary(documentation: &str) -> String {
+    use pulldown_cmark::{Event, Parser, Tag};
+
+    let mut paragraph = None;
+    for (event, range) in Parser::new(documentation).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Paragraph) => {
+                paragraph = Some(&documentation[range]);
+                break;
+            }
+            // the summary should not come after a heading or code block
+            Event::Start(_) => break,
+            _ => {}
+        }
+    }
+    let paragraph = match paragraph {
+        Some(paragraph) => paragraph,
+        None => return String::new(),
+    };
+
+    let mut summary = String::new();
+    let mut in_code = false;
+    let mut chars = paragraph.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '`' => in_code = !in_code,
+            '\n' => {
+                while chars.next_if(|next| next.is_whitespace()).is_some() {}
+                summary.truncate(summary.trim_end().len());
+                summary.push(' ');
+                continue;
+            }
+            '.' | '!' | '?' if !in_code && chars.peek().is_none_or(|next| next.is_whitespace()) => {
+                summary.push(c);
+                break;
+            }
+            _ => {}
+        }
+        summary.push(c);
+    }
+    summary.trim().to_string()
+}
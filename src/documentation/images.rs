@@ -0,0 +1,167 @@
+//! Local images referenced in the documentation.
+
+use super::Documentation;
+use crate::diagnostics::{diagnostic, Lint};
+use pulldown_cmark::{Event, Options, Parser, Tag};
+use std::{
+    collections::HashMap,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+/// Directory, relative to the generated pages, in which local images are copied.
+pub(crate) const IMAGES_DIRECTORY: &str = "images";
+
+/// Local images found by [`Documentation::relocate_images`].
+#[derive(Default)]
+pub(crate) struct Images {
+    /// Path of each image (relative to the generated pages), by source path.
+    paths: HashMap<PathBuf, String>,
+}
+
+impl Images {
+    /// Pairs of the source path of an image, and its path relative to the generated
+    /// pages (like `images/grid.png`).
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&Path, &str)> {
+        self.paths
+            .iter()
+            .map(|(source, path)| (source.as_path(), path.as_str()))
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    /// Path of the image at `source` in the generated pages.
+    ///
+    /// Images with the same file name get a suffix, like `grid-1.png`.
+    fn path_of(&mut self, source: PathBuf) -> String {
+        if let Some(path) = self.paths.get(&source) {
+            return path.clone();
+        }
+        let stem = source
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or("image");
+        let extension = source
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| format!(".{}", extension))
+            .unwrap_or_default();
+        let mut path = format!("{}/{}{}", IMAGES_DIRECTORY, stem, extension);
+        let mut suffix = 0;
+        while self.paths.values().any(|other| *other == path) {
+            suffix += 1;
+            path = format!("{}/{}-{}{}", IMAGES_DIRECTORY, stem, suffix, extension);
+        }
+        self.paths.insert(source, path.clone());
+        path
+    }
+}
+
+impl Documentation {
+    /// Rewrite the links to local images in the documentation, so that they point
+    /// to copies of the images next to the generated pages.
+    ///
+    /// Local images are relative to the file the documentation comes from, like
+    /// `![grid](images/hexgrid.png)`. Missing images emit a
+    /// [`BrokenLink`](Lint::BrokenLink) diagnostic, and are left untouched.
+    pub(crate) fn relocate_images(&mut self) -> Images {
+        let mut images = Images::default();
+        let root_dir = parent_dir(&self.root_file);
+        relocate(&mut self.root_documentation, &root_dir, &mut images);
+        for module in &mut self.modules {
+            relocate(
+                &mut module.documentation,
+                &parent_dir(&module.file),
+                &mut images,
+            );
+        }
+        let mut names: Vec<String> = self.classes.keys().cloned().collect();
+        names.sort_unstable();
+        for name in names {
+            let class = self.classes.get_mut(&name).unwrap();
+            let dir = parent_dir(&class.file);
+            relocate(&mut class.documentation, &dir, &mut images);
+            for property in &mut class.properties {
+                relocate(&mut property.documentation, &dir, &mut images);
+            }
+            for method in &mut class.methods {
+                relocate(&mut method.documentation, &dir, &mut images);
+            }
+        }
+        images
+    }
+}
+
+fn parent_dir(file: &Path) -> PathBuf {
+    file.parent().map(Path::to_path_buf).unwrap_or_default()
+}
+
+/// Returns `true` if `url` is relative to the current file.
+fn is_local(url: &str) -> bool {
+    !(url.is_empty()
+        || url.starts_with('/')
+        || url.starts_with('#')
+        || url.starts_with("//")
+        || url.contains("://")
+        || url.starts_with("data:"))
+}
+
+/// Rewrite the local images of `documentation`, relative to `dir`.
+fn relocate(documentation: &mut String, dir: &Path, images: &mut Images) {
+    if !documentation.contains("![") {
+        return;
+    }
+    let mut replacements: Vec<(Range<usize>, String)> = Vec::new();
+    let mut add_replacement = |url: &str, span: Range<usize>| {
+        if !is_local(url) {
+            return;
+        }
+        let source = dir.join(url);
+        if !source.is_file() {
+            diagnostic!(
+                Lint::BrokenLink,
+                "image '{}' not found at {}",
+                url,
+                source.display()
+            );
+            return;
+        }
+        // the url appears verbatim in `span`, unless it is escaped
+        if let Some(start) = documentation[span.clone()].rfind(url) {
+            let start = span.start + start;
+            replacements.push((start..start + url.len(), images.path_of(source)));
+        }
+    };
+
+    let parser = Parser::new_ext(documentation, Options::all());
+    let definitions: Vec<_> = parser
+        .reference_definitions()
+        .iter()
+        .map(|(_, definition)| (definition.dest.to_string(), definition.span.clone()))
+        .collect();
+    let mut reference_images = Vec::new();
+    for (event, span) in parser.into_offset_iter() {
+        if let Event::Start(Tag::Image(link_type, url, _)) = event {
+            use pulldown_cmark::LinkType::*;
+            match link_type {
+                Inline | Autolink | Email => add_replacement(&url, span),
+                // the url is in the reference definition
+                Reference | ReferenceUnknown | Collapsed | CollapsedUnknown | Shortcut
+                | ShortcutUnknown => reference_images.push(url.to_string()),
+            }
+        }
+    }
+    for (url, span) in definitions {
+        if reference_images.contains(&url) {
+            add_replacement(&url, span);
+        }
+    }
+
+    replacements.sort_unstable_by_key(|(range, _)| std::cmp::Reverse(range.start));
+    replacements.dedup_by_key(|(range, _)| range.start);
+    for (range, path) in replacements {
+        documentation.replace_range(range, &path);
+    }
+}
@@ -1,14 +1,42 @@
 //! Structures representing the documentation of a `gdnative` package.
+//!
+//! With the `serde` feature enabled, these structures implement
+//! [`Serialize`](serde::Serialize) and [`Deserialize`](serde::Deserialize), so that
+//! they can be dumped and reloaded by external tools. The serialized form is
+//! versioned by [`SCHEMA_VERSION`].
 
 mod builder;
+mod diff;
 mod helpers;
+mod images;
+mod tags;
+#[cfg(test)]
+pub(crate) mod tests;
 
-use crate::Error;
+pub use diff::{ClassDiff, DocumentationDiff, MethodDiff, Parameters};
+pub use tags::{Tag, TagRegistry, BUILTIN_TAGS};
+
+pub(crate) use builder::ParsedFiles;
+pub(crate) use images::{Images, IMAGES_DIRECTORY};
+
+use crate::{
+    diagnostics::{diagnostic, Lint},
+    Error, MemberSort,
+};
 use helpers::*;
 use std::{collections::HashMap, path::PathBuf};
 
+/// Version of the serialized form of the structures in this module.
+///
+/// It is stored in [`Documentation::schema_version`], and incremented (at most once
+/// per release of this crate) whenever the serialized form changes. Deserializing a
+/// [`Documentation`] with a different version fails, instead of silently producing
+/// incomplete data.
+pub const SCHEMA_VERSION: u32 = 1;
+
 /// Attribute in a function parameter.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ParameterAttribute {
     /// No or unrecognized attribute
     None,
@@ -16,8 +44,56 @@ pub enum ParameterAttribute {
     Opt,
 }
 
+/// RPC mode of a method, declared with `#[method(rpc = "...")]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RpcMode {
+    /// `rpc = "remote"`
+    Remote,
+    /// `rpc = "remote_sync"`
+    RemoteSync,
+    /// `rpc = "master"`
+    Master,
+    /// `rpc = "master_sync"`
+    MasterSync,
+    /// `rpc = "puppet"`
+    Puppet,
+    /// `rpc = "puppet_sync"`
+    PuppetSync,
+}
+
+impl RpcMode {
+    /// Parse the value of the `rpc` argument, as accepted by gdnative.
+    ///
+    /// Returns `None` for `"disabled"` and unknown modes.
+    pub fn from_gdnative(mode: &str) -> Option<Self> {
+        match mode {
+            "remote" => Some(Self::Remote),
+            "remote_sync" => Some(Self::RemoteSync),
+            "master" => Some(Self::Master),
+            "master_sync" => Some(Self::MasterSync),
+            "puppet" => Some(Self::Puppet),
+            "puppet_sync" => Some(Self::PuppetSync),
+            _ => None,
+        }
+    }
+
+    /// GDScript keyword for this mode, like `remotesync`.
+    pub fn keyword(self) -> &'static str {
+        match self {
+            Self::Remote => "remote",
+            Self::RemoteSync => "remotesync",
+            Self::Master => "master",
+            Self::MasterSync => "mastersync",
+            Self::Puppet => "puppet",
+            Self::PuppetSync => "puppetsync",
+        }
+    }
+}
+
 /// Most type are simply `String`, but not all (e.g. return type)
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Type {
     /// `Option<Type>`
     Option(String),
@@ -27,11 +103,37 @@ pub enum Type {
     Unit,
 }
 
+impl Type {
+    /// Name of the underlying type, if any.
+    ///
+    /// This is `None` for [`Type::Unit`].
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Type::Option(name) | Type::Named(name) => Some(name),
+            Type::Unit => None,
+        }
+    }
+}
+
 /// Method in an `impl` block.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Method {
     /// Does this method have a `self` parameter ?
     pub has_self: bool,
+    /// Is this method asynchronous ?
+    ///
+    /// This is the case for `async fn` and methods exported with
+    /// `#[method(async)]`: in GDScript, their result must be `yield`ed.
+    pub is_async: bool,
+    /// RPC mode of the method, if it can be called remotely.
+    pub rpc_mode: Option<RpcMode>,
+    /// Is this method a constructor ?
+    ///
+    /// This is the case for `new`, for static methods that call
+    /// `Instance::emplace`, and for static methods whose name matches one of the
+    /// patterns given to [`Documentation::mark_constructors`].
+    pub is_constructor: bool,
     /// Name of the method.
     pub name: String,
     /// Name of the type that is being `impl`emented.
@@ -50,8 +152,24 @@ pub struct Method {
     /// # Note
     /// This keeps the leading space in `/// doc`
     pub documentation: String,
+    /// First sentence of `documentation`, on a single line.
+    pub summary: String,
+    /// Tags (like `@since 1.2`) extracted from the documentation.
+    pub tags: Vec<Tag>,
+    /// Other names of the method, declared with `#[doc(alias = "...")]`.
+    pub aliases: Vec<String>,
+    /// Note of the `#[deprecated]` attribute, if the method is deprecated.
+    ///
+    /// A bare `#[deprecated]` gives an empty note.
+    pub deprecated: Option<String>,
     /// File in which the method was declared
     pub file: PathBuf,
+    /// Line (starting at 1) at which the method was declared in `file`.
+    pub line: usize,
+    /// Name of the property, if this method is its getter or setter.
+    ///
+    /// See [`Property::getter`] and [`Property::setter`].
+    pub accessor_of: Option<String>,
 }
 
 /// Property exported to godot
@@ -73,16 +191,41 @@ pub struct Method {
 /// ```text
 /// name: "my_property",
 /// typ: Type::Named("String"),
-/// documentation: "Some doc"
+/// default: None,
+/// getter: None,
+/// setter: None,
+/// documentation: "Some doc",
+/// summary: "Some doc",
+/// deprecated: None,
 /// ```
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Property {
     /// Name of the property
     pub name: String,
     /// Type of the property
     pub typ: Type,
+    /// Default value of the property, as written in the source code.
+    ///
+    /// This comes from `#[property(default = ...)]`, or else from the field's
+    /// value in the `new` constructor, if it is a constant.
+    pub default: Option<String>,
+    /// Name of the getter method, from `#[property(get = "Self::get_x")]` or
+    /// `#[property(get_ref = "Self::get_x")]`.
+    pub getter: Option<String>,
+    /// Name of the setter method, from `#[property(set = "Self::set_x")]`.
+    pub setter: Option<String>,
     /// Documentation associated with  the property
     pub documentation: String,
+    /// First sentence of `documentation`, on a single line.
+    pub summary: String,
+    /// Tags (like `@since 1.2`) extracted from the documentation.
+    pub tags: Vec<Tag>,
+    /// Note of the `#[deprecated]` attribute, if the property is deprecated.
+    pub deprecated: Option<String>,
+    /// Line (starting at 1) at which the property was declared, in the file of its
+    /// class.
+    pub line: usize,
 }
 
 /// Structure that derive `NativeClass`
@@ -90,13 +233,21 @@ pub struct Property {
 /// # Note
 /// It cannot be generic.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GdnativeClass {
     /// Name of the structure
     pub name: String,
     /// Name of the type in `#[inherit(...)]`
+    ///
+    /// If the attribute is absent, this is inferred from the owner argument of
+    /// the methods (`&Node2D`, `TRef<Spatial>`...), or defaults to `Reference`.
     pub inherit: String,
     /// Documentation associated with the structure.
     pub documentation: String,
+    /// First sentence of `documentation`, on a single line.
+    pub summary: String,
+    /// Tags (like `@since 1.2`) extracted from the documentation.
+    pub tags: Vec<Tag>,
     /// Properties exported by the structure
     pub properties: Vec<Property>,
     /// Exported methods of this structure
@@ -105,70 +256,486 @@ pub struct GdnativeClass {
     /// - In a `#[methods]` impl block
     /// - Either `new`, or marked with `#[method]`
     pub methods: Vec<Method>,
+    /// Other names of the structure, declared with `#[doc(alias = "...")]`.
+    pub aliases: Vec<String>,
+    /// Does this class run in the editor ?
+    ///
+    /// This is the case for classes registered with `add_tool_class`, and for
+    /// classes inheriting an editor class (like `EditorPlugin`).
+    pub is_tool: bool,
+    /// Note of the `#[deprecated]` attribute, if the structure is deprecated.
+    pub deprecated: Option<String>,
+    /// Path of the module in which the `struct` was declared, like
+    /// `graph::nodes`.
+    ///
+    /// This is empty for the root module.
+    pub module: String,
     /// File in which the `struct` was declared
     pub file: PathBuf,
+    /// Line (starting at 1) at which the `struct` was declared in `file`.
+    ///
+    /// This is `0` if the `struct` was not found.
+    pub line: usize,
 }
 
 /// Holds the documentation for the crate.
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Documentation {
+    /// Version of the serialized form of this structure.
+    ///
+    /// This is always [`SCHEMA_VERSION`] when created by this crate.
+    #[cfg_attr(
+        feature = "serde",
+        serde(deserialize_with = "deserialize_schema_version")
+    )]
+    pub schema_version: u32,
     /// Name of the crate.
     pub name: String,
+    /// Information from the manifest of the crate.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub metadata: CrateMetadata,
     /// Path of the root file for the documentation.
     pub root_file: PathBuf,
     /// Documentation of the root module.
     pub root_documentation: String,
+    /// Documented modules other than the root module, in the order in which they
+    /// are declared.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub modules: Vec<Module>,
     /// Classes, organized by name.
     // FIXME: the name of the class is repeated all over the place.
     //       It may be better to use identifiers ?
     pub classes: HashMap<String, GdnativeClass>,
 }
 
-impl Documentation {
-    pub(crate) fn from_root_file(name: String, root_file: PathBuf) -> Result<Self, Error> {
-        use syn::visit::Visit;
-
-        let root_file_content = read_file_at(&root_file)?;
-        let mut builder = builder::DocumentationBuilder {
-            documentation: Self {
-                name,
-                root_file: root_file.clone(),
-                root_documentation: String::new(),
-                classes: HashMap::new(),
+/// Documentation of a module other than the root module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Module {
+    /// Path of the module from the root of the crate, like `graph::nodes`.
+    pub path: String,
+    /// Documentation of the module, from its `//!` comments and the `///`
+    /// comments on its declaration.
+    pub documentation: String,
+    /// File in which the module is defined.
+    pub file: PathBuf,
+}
+
+impl Module {
+    /// Name of the page generated for this module, without extension.
+    ///
+    /// This is `module-graph-nodes` for the module `graph::nodes`.
+    pub fn file_stem(&self) -> String {
+        format!("module-{}", self.path.replace("::", "-"))
+    }
+}
+
+/// Information about the crate, taken from its `Cargo.toml`.
+///
+/// This is empty when the crate is given by its
+/// [root file](crate::Package::Root).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CrateMetadata {
+    /// Version of the crate.
+    pub version: Option<String>,
+    /// `repository` field of the manifest.
+    pub repository: Option<String>,
+    /// `homepage` field of the manifest.
+    pub homepage: Option<String>,
+    /// `license` field of the manifest.
+    pub license: Option<String>,
+}
+
+impl CrateMetadata {
+    /// Returns `true` if no information is available.
+    pub fn is_empty(&self) -> bool {
+        self.version.is_none()
+            && self.repository.is_none()
+            && self.homepage.is_none()
+            && self.license.is_none()
+    }
+}
+
+/// Does `name` match the glob `pattern` ?
+///
+/// `*` matches any sequence of characters, and `?` matches any single character.
+pub(crate) fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    // position of the last `*`, and of the character of `name` it stops at
+    let mut star = None;
+    let (mut p, mut n) = (0, 0);
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    p = star_p + 1;
+                    n = star_n + 1;
+                    star = Some((star_p, star_n + 1));
+                }
+                None => return false,
             },
-            current_file: (root_file, true),
-            current_module: Vec::new(),
-            error: None,
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Deserialize [`Documentation::schema_version`], checking that it matches
+/// [`SCHEMA_VERSION`].
+#[cfg(feature = "serde")]
+fn deserialize_schema_version<'de, D>(deserializer: D) -> Result<u32, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::{de::Error as _, Deserialize as _};
+    let version = u32::deserialize(deserializer)?;
+    if version == SCHEMA_VERSION {
+        Ok(version)
+    } else {
+        Err(D::Error::custom(format!(
+            "unsupported schema version {} (expected {})",
+            version, SCHEMA_VERSION
+        )))
+    }
+}
+
+impl Documentation {
+    /// Documentation of the crate `name` at `root_file`, without any item.
+    fn empty(name: String, root_file: PathBuf) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            name,
+            metadata: CrateMetadata::default(),
+            root_file,
+            root_documentation: String::new(),
+            modules: Vec::new(),
+            classes: HashMap::new(),
+        }
+    }
+
+    /// Parse the crate at `root_file`.
+    ///
+    /// The files that did not change since they were stored in `cache` are not
+    /// parsed again. Also returns the files that were visited, to be cached for
+    /// the next parse.
+    pub(crate) fn from_root_file(
+        name: String,
+        root_file: PathBuf,
+        tags: &TagRegistry,
+        cache: &ParsedFiles,
+    ) -> Result<(Self, ParsedFiles), Error> {
+        let root = builder::ModuleFile::root(root_file.clone());
+        let (mut builder, parsed) = builder::DocumentationBuilder::visit_module_file(&root, cache)?;
+        builder.documentation.name = name;
+        builder.documentation.root_file = root_file;
+        builder.disambiguate_classes();
+        builder.infer_inherits();
+        builder.mark_tool_classes();
+        builder.fill_property_defaults();
+        for class in builder.documentation.classes.values_mut() {
+            class.link_accessors();
+        }
+        builder.documentation.extract_tags(tags);
+        Ok((builder.documentation, parsed))
+    }
+
+    /// Move the registered tags out of the documentation of every item.
+    fn extract_tags(&mut self, registry: &TagRegistry) {
+        fn extract(
+            registry: &TagRegistry,
+            documentation: &mut String,
+            summary: &mut String,
+        ) -> Vec<Tag> {
+            let tags = registry.extract(documentation);
+            if !tags.is_empty() {
+                *summary = get_summary(documentation);
+            }
+            tags
+        }
+
+        for class in self.classes.values_mut() {
+            class.tags = extract(registry, &mut class.documentation, &mut class.summary);
+            for property in &mut class.properties {
+                property.tags =
+                    extract(registry, &mut property.documentation, &mut property.summary);
+            }
+            for method in &mut class.methods {
+                method.tags = extract(registry, &mut method.documentation, &mut method.summary);
+            }
+        }
+    }
+
+    /// Merge the documentation of another crate into `self`.
+    ///
+    /// This is useful for plugins split over multiple crates, but shipped as a
+    /// single godot addon. The classes of `other` are added to `self`, and its root
+    /// documentation is appended to `self.root_documentation`.
+    ///
+    /// # Errors
+    /// If both documentations contain different classes with the same name, this
+    /// returns [`Error::MergeConflict`] with the list of conflicting names, and
+    /// `self` is left untouched.
+    pub fn merge(&mut self, other: Documentation) -> Result<(), Error> {
+        let mut conflicts: Vec<String> = other
+            .classes
+            .iter()
+            .filter(|(name, class)| {
+                matches!(self.classes.get(name.as_str()), Some(self_class) if self_class != *class)
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        if !conflicts.is_empty() {
+            conflicts.sort_unstable();
+            return Err(Error::MergeConflict(conflicts));
+        }
+
+        log::debug!(
+            "merging documentation of '{}' into '{}'",
+            other.name,
+            self.name
+        );
+        if !other.root_documentation.is_empty() {
+            if !self.root_documentation.is_empty() {
+                self.root_documentation.push_str("\n\n");
+            }
+            self.root_documentation.push_str(&other.root_documentation);
+        }
+        self.modules.extend(other.modules);
+        self.classes.extend(other.classes);
+        Ok(())
+    }
+
+    /// Mark the static methods whose name matches one of `patterns` as
+    /// [constructors](Method::is_constructor).
+    ///
+    /// Patterns are globs, like `from_*`.
+    pub fn mark_constructors(&mut self, patterns: &[String]) {
+        let matches = |name: &str| patterns.iter().any(|pattern| glob_match(pattern, name));
+        for class in self.classes.values_mut() {
+            for method in &mut class.methods {
+                if !method.has_self && matches(&method.name) {
+                    method.is_constructor = true;
+                }
+            }
+        }
+    }
+
+    /// Keep only the classes matching one of the `include` patterns (or every
+    /// class if `include` is empty), and none of the `exclude` patterns.
+    ///
+    /// Patterns are globs, matched against the name of the class (like
+    /// `Helper*`) and its path (like `internal::*`).
+    pub fn filter_classes(&mut self, include: &[String], exclude: &[String]) {
+        self.classes.retain(|name, class| {
+            let path = match class.module.as_str() {
+                "" => name.clone(),
+                module => format!("{}::{}", module, name),
+            };
+            let matches =
+                |pattern: &String| glob_match(pattern, name) || glob_match(pattern, &path);
+            let keep =
+                (include.is_empty() || include.iter().any(matches)) && !exclude.iter().any(matches);
+            if !keep {
+                log::debug!("excluding class {}", path);
+            }
+            keep
+        });
+    }
+
+    /// Remove the methods and properties matching one of the `exclude_methods` or
+    /// `exclude_properties` patterns.
+    ///
+    /// Patterns are globs, matched against the name of the member (like
+    /// `debug_*`) and against `Class::member`.
+    pub fn filter_members(&mut self, exclude_methods: &[String], exclude_properties: &[String]) {
+        for (class_name, class) in &mut self.classes {
+            let excluded = |patterns: &[String], name: &str| {
+                let path = format!("{}::{}", class_name, name);
+                let excluded = patterns
+                    .iter()
+                    .any(|pattern| glob_match(pattern, name) || glob_match(pattern, &path));
+                if excluded {
+                    log::debug!("excluding {}", path);
+                }
+                excluded
+            };
+            class
+                .methods
+                .retain(|method| !excluded(exclude_methods, &method.name));
+            class
+                .properties
+                .retain(|property| !excluded(exclude_properties, &property.name));
+            // accessors of removed members are not linked to
+            for property in &mut class.properties {
+                let methods = &class.methods;
+                let exists = |name: &String| methods.iter().any(|method| &method.name == name);
+                property.getter = property.getter.take().filter(exists);
+                property.setter = property.setter.take().filter(exists);
+            }
+            for method in &mut class.methods {
+                let properties = &class.properties;
+                let exists =
+                    |name: &String| properties.iter().any(|property| &property.name == name);
+                method.accessor_of = method.accessor_of.take().filter(exists);
+            }
+        }
+    }
+
+    /// Sort the methods and properties of every class according to `sort`.
+    ///
+    /// Sorts are stable, so [`MemberSort::Source`] keeps the current order.
+    pub fn sort_members(&mut self, sort: MemberSort) {
+        fn method_kind(method: &Method) -> u8 {
+            if method.is_constructor {
+                0
+            } else if !method.has_self {
+                1
+            } else {
+                2
+            }
+        }
+
+        for class in self.classes.values_mut() {
+            match sort {
+                MemberSort::Source => {}
+                MemberSort::Alphabetical => {
+                    class.methods.sort_by(|m1, m2| m1.name.cmp(&m2.name));
+                }
+                MemberSort::ByKind => {
+                    class.methods.sort_by(|m1, m2| {
+                        (method_kind(m1), &m1.name).cmp(&(method_kind(m2), &m2.name))
+                    });
+                }
+            }
+            if sort != MemberSort::Source {
+                class.properties.sort_by(|p1, p2| p1.name.cmp(&p2.name));
+            }
+        }
+    }
+
+    /// Name of the crate, followed by its version if it is known.
+    pub fn name_and_version(&self) -> String {
+        match &self.metadata.version {
+            Some(version) => format!("{} {}", self.name, version),
+            None => self.name.clone(),
+        }
+    }
+
+    /// Returns the chain of classes inherited by the class `name`, starting with its
+    /// direct parent.
+    ///
+    /// The chain follows classes documented in this crate, and ends with the first
+    /// class that is not (usually a godot class).
+    pub fn inheritance_chain(&self, name: &str) -> Vec<&str> {
+        let mut chain = Vec::new();
+        let mut current = match self.classes.get(name) {
+            Some(class) => class,
+            None => return chain,
         };
-        let root_documentation = get_docs(&root_file_content.attrs);
-        for item in root_file_content.items {
-            builder.visit_item(&item);
-            if let Some(error) = builder.error.take() {
-                return Err(error);
+        loop {
+            let parent = current.inherit.as_str();
+            if parent.is_empty() {
+                break;
+            }
+            // guard against inheritance cycles
+            if parent == name || chain.contains(&parent) {
+                diagnostic!(
+                    Lint::InheritanceCycle,
+                    "inheritance cycle detected for class '{}'",
+                    name
+                );
+                break;
+            }
+            chain.push(parent);
+            match self.classes.get(parent) {
+                Some(class) => current = class,
+                None => break,
+            }
+        }
+        chain
+    }
+
+    /// Returns the (sorted) names of the documented classes that directly inherit
+    /// the class `name`.
+    pub fn inherited_by(&self, name: &str) -> Vec<&str> {
+        let mut children: Vec<&str> = self
+            .classes
+            .iter()
+            .filter(|(_, class)| class.inherit == name)
+            .map(|(child, _)| child.as_str())
+            .collect();
+        children.sort_unstable();
+        children
+    }
+
+    /// Returns the methods of other documented classes whose parameters or return
+    /// type mention the class `name`.
+    ///
+    /// The result contains `(class name, method)` pairs, sorted by class and method
+    /// names.
+    pub fn referenced_by(&self, name: &str) -> Vec<(&str, &Method)> {
+        let mut references: Vec<(&str, &Method)> = Vec::new();
+        for (class_name, class) in &self.classes {
+            if class_name == name {
+                continue;
+            }
+            for method in &class.methods {
+                if method.return_type.name() == Some(name)
+                    || method
+                        .parameters
+                        .iter()
+                        .any(|(_, typ, _)| typ.name() == Some(name))
+                {
+                    references.push((class_name, method))
+                }
             }
         }
-        builder.documentation.root_documentation = root_documentation;
-        Ok(builder.documentation)
+        references.sort_unstable_by(|(class1, method1), (class2, method2)| {
+            (class1, &method1.name).cmp(&(class2, &method2.name))
+        });
+        references
     }
 }
 
 impl GdnativeClass {
     /// Check that the method is exported, parse it, and add it to the class.
-    fn add_method(&mut self, method: &syn::ImplItemMethod, file: PathBuf) {
+    ///
+    /// Returns the godot class of the method's owner argument, if it has one.
+    fn add_method(&mut self, method: &syn::ImplItemMethod, file: PathBuf) -> Option<String> {
         let syn::ImplItemMethod {
             vis, attrs, sig, ..
         } = method;
 
         // not public
         if !matches!(vis, syn::Visibility::Public(_)) {
-            return;
+            return None;
         }
+        // `#[export]` is the attribute used before gdnative 0.10
+        let method_arguments = get_attribute_arguments(attrs, "method")
+            .or_else(|| get_attribute_arguments(attrs, "export"));
         // not exported nor a constructor
-        if !(attributes_contains(attrs, "method") || sig.ident == "new") {
-            return;
+        if method_arguments.is_none() && sig.ident != "new" {
+            return None;
         }
 
         let has_self = sig.receiver().is_some();
+        let method_arguments = method_arguments.unwrap_or_default();
+        let is_async = sig.asyncness.is_some()
+            || method_arguments.iter().any(|argument| {
+                matches!(argument, syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("async"))
+            });
+        let rpc_mode = get_rpc_mode(&method_arguments);
+        let is_constructor = sig.ident == "new" || (!has_self && calls_emplace(&method.block));
         let syn::Signature {
             ident: method_name,
             inputs,
@@ -177,13 +744,24 @@ impl GdnativeClass {
         } = sig;
 
         let mut parameters = inputs.into_iter();
+        let mut owner_type = None;
         // - for `self` methods: Remove the `self` argument.
         // - for `new`: remove the 'owner' argument.
-        parameters.next();
+        if let Some(syn::FnArg::Typed(owner)) = parameters.next() {
+            if method_name == "new" || is_owner_argument(owner) {
+                owner_type = get_owner_type(&owner.ty);
+            }
+        }
         let parameters = {
             let mut params = Vec::new();
             for arg in parameters {
-                if let syn::FnArg::Typed(syn::PatType { attrs, pat, ty, .. }) = arg {
+                if let syn::FnArg::Typed(pat_type) = arg {
+                    // the owner is not visible from GDScript
+                    if is_owner_argument(pat_type) {
+                        owner_type = owner_type.or_else(|| get_owner_type(&pat_type.ty));
+                        continue;
+                    }
+                    let syn::PatType { attrs, pat, ty, .. } = pat_type;
                     let arg_name = {
                         if let syn::Pat::Ident(syn::PatIdent { ident, .. }) = pat.as_ref() {
                             ident.to_string()
@@ -216,21 +794,64 @@ impl GdnativeClass {
             parameters,
             return_type
         );
+        let documentation = get_docs(attrs);
         self.methods.push(Method {
             has_self,
+            is_async,
+            rpc_mode,
+            is_constructor,
             name: method_name.to_string(),
             self_type: self.name.clone(),
             parameters,
             return_type,
-            documentation: get_docs(attrs),
+            summary: get_summary(&documentation),
+            tags: Vec::new(),
+            documentation,
+            aliases: get_doc_aliases(attrs),
+            deprecated: get_deprecated(attrs),
             file,
-        })
+            line: method_name.span().start().line,
+            accessor_of: None,
+        });
+        owner_type
+    }
+
+    /// Mark the methods used as getters or setters of properties, see
+    /// [`Method::accessor_of`].
+    fn link_accessors(&mut self) {
+        for property in &self.properties {
+            for accessor in [&property.getter, &property.setter].into_iter().flatten() {
+                if let Some(method) = self
+                    .methods
+                    .iter_mut()
+                    .find(|method| &method.name == accessor)
+                {
+                    method.accessor_of = Some(property.name.clone());
+                }
+            }
+        }
     }
 
     /// Extract `#[property]` fields
     fn get_properties(&mut self, fields: &syn::FieldsNamed) {
         for field in &fields.named {
-            if attributes_contains(&field.attrs, "property") {
+            if let Some(arguments) = get_property_arguments(&field.attrs) {
+                let documentation = get_docs(&field.attrs);
+                let mut default = None;
+                let mut getter = None;
+                let mut setter = None;
+                for argument in arguments {
+                    let value = match argument.value {
+                        Some(value) => value,
+                        None => continue,
+                    };
+                    match argument.name.as_str() {
+                        "default" => default = Some(source_text(&value)),
+                        "get" | "get_ref" => getter = get_accessor_name(&value),
+                        "set" => setter = get_accessor_name(&value),
+                        _ => {}
+                    }
+                }
                 let property = Property {
                     name: field
                         .ident
@@ -239,7 +860,17 @@ impl GdnativeClass {
                         .unwrap_or_default(),
                     // FIXME: log unsupported types
                     typ: get_type_name(&field.ty).unwrap_or(Type::Unit),
-                    documentation: get_docs(&field.attrs),
+                    default,
+                    getter,
+                    setter,
+                    summary: get_summary(&documentation),
+                    tags: Vec::new(),
+                    documentation,
+                    deprecated: get_deprecated(&field.attrs),
+                    line: field
+                        .ident
+                        .as_ref()
+                        .map_or(0, |ident| ident.span().start().line),
                 };
                 log::trace!(
                     "added property '{}' of type {:?}",
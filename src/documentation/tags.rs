@@ -0,0 +1,104 @@
+//! Structured tags in documentation comments, like `@since 1.2`.
+
+use std::collections::BTreeSet;
+
+/// Tags recognized by default.
+///
+/// - `@category <name>`: category of the item.
+/// - `@tutorial <link>`: link to a tutorial using the item.
+/// - `@since <version>`: version in which the item was introduced.
+/// - `@emits <signal>`: signal emitted by the item.
+/// - `@complexity <note>`: algorithmic complexity of a method, like `O(V log V)`.
+/// - `@requires <note>`: what must be done before calling a method.
+pub const BUILTIN_TAGS: [&str; 6] = [
+    "category",
+    "tutorial",
+    "since",
+    "emits",
+    "complexity",
+    "requires",
+];
+
+/// A tag extracted from a documentation comment.
+///
+/// In the documentation, a tag is a line of the form `@name value`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tag {
+    /// Name of the tag, without the `@`.
+    pub name: String,
+    /// Rest of the line, trimmed.
+    pub value: String,
+}
+
+/// Set of tags that are extracted from documentation comments.
+///
+/// Lines starting with an unregistered `@name` are left in the documentation.
+///
+/// The default registry contains the [`BUILTIN_TAGS`]; more can be added with
+/// [`Builder::register_tag`](crate::Builder::register_tag).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TagRegistry {
+    names: BTreeSet<String>,
+}
+
+impl Default for TagRegistry {
+    fn default() -> Self {
+        Self {
+            names: BUILTIN_TAGS.iter().map(|name| name.to_string()).collect(),
+        }
+    }
+}
+
+impl TagRegistry {
+    /// Create an empty registry, that does not even contain the [`BUILTIN_TAGS`].
+    pub fn empty() -> Self {
+        Self {
+            names: BTreeSet::new(),
+        }
+    }
+
+    /// Register a new tag (without the leading `@`).
+    pub fn register(&mut self, name: &str) {
+        self.names.insert(name.trim_start_matches('@').to_string());
+    }
+
+    /// Returns `true` if `name` is registered.
+    pub fn contains(&self, name: &str) -> bool {
+        self.names.contains(name)
+    }
+
+    /// Remove the tags lines from `documentation`, and return them.
+    ///
+    /// Lines in fenced code blocks are never considered as tags.
+    pub fn extract(&self, documentation: &mut String) -> Vec<Tag> {
+        let mut tags = Vec::new();
+        let mut remaining = String::with_capacity(documentation.len());
+        let mut in_code_block = false;
+        for line in documentation.lines() {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_code_block = !in_code_block;
+            } else if let (false, Some(tag)) = (in_code_block, trimmed.strip_prefix('@')) {
+                let (name, value) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+                if self.contains(name) {
+                    tags.push(Tag {
+                        name: name.to_string(),
+                        value: value.trim().to_string(),
+                    });
+                    continue;
+                }
+                log::debug!("unknown tag '@{name}', leaving it in the documentation");
+            }
+            if !remaining.is_empty() {
+                remaining.push('\n');
+            }
+            remaining.push_str(line);
+        }
+        if !tags.is_empty() {
+            remaining.truncate(remaining.trim_end().len());
+            *documentation = remaining;
+        }
+        tags
+    }
+}
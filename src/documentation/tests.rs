@@ -0,0 +1,953 @@
+use super::*;
+
+pub(crate) fn method(name: &str, parameters: &[(&str, &str)], return_type: Type) -> Method {
+    Method {
+        has_self: true,
+        is_async: false,
+        rpc_mode: None,
+        is_constructor: false,
+        name: name.to_string(),
+        self_type: String::from("MyClass"),
+        parameters: parameters
+            .iter()
+            .map(|(name, typ)| {
+                (
+                    name.to_string(),
+                    Type::Named(typ.to_string()),
+                    ParameterAttribute::None,
+                )
+            })
+            .collect(),
+        return_type,
+        documentation: String::new(),
+        summary: String::new(),
+        tags: Vec::new(),
+        aliases: Vec::new(),
+        deprecated: None,
+        file: PathBuf::new(),
+        line: 0,
+        accessor_of: None,
+    }
+}
+
+pub(crate) fn documentation(classes: Vec<(&str, Vec<Method>)>) -> Documentation {
+    Documentation {
+        schema_version: SCHEMA_VERSION,
+        name: String::from("my-crate"),
+        metadata: CrateMetadata::default(),
+        root_file: PathBuf::from("lib.rs"),
+        root_documentation: String::new(),
+        modules: Vec::new(),
+        classes: classes
+            .into_iter()
+            .map(|(name, methods)| {
+                let class = GdnativeClass {
+                    name: name.to_string(),
+                    inherit: String::from("Reference"),
+                    documentation: String::new(),
+                    summary: String::new(),
+                    tags: Vec::new(),
+                    properties: Vec::new(),
+                    methods,
+                    aliases: Vec::new(),
+                    is_tool: false,
+                    deprecated: None,
+                    module: String::new(),
+                    file: PathBuf::new(),
+                    line: 0,
+                };
+                (name.to_string(), class)
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn diff() {
+    let old = documentation(vec![
+        (
+            "MyClass",
+            vec![
+                method("kept", &[("x", "int")], Type::Unit),
+                method("removed", &[], Type::Unit),
+                method("changed", &[("x", "int")], Type::Unit),
+            ],
+        ),
+        ("RemovedClass", Vec::new()),
+    ]);
+    let new = documentation(vec![
+        (
+            "MyClass",
+            vec![
+                method("kept", &[("x", "int")], Type::Unit),
+                method("added", &[], Type::Unit),
+                method(
+                    "changed",
+                    &[("x", "float")],
+                    Type::Named(String::from("int")),
+                ),
+            ],
+        ),
+        ("AddedClass", Vec::new()),
+    ]);
+
+    assert!(old.diff(&old).is_empty());
+    let diff = old.diff(&new);
+    assert_eq!(diff.added_classes, ["AddedClass"]);
+    assert_eq!(diff.removed_classes, ["RemovedClass"]);
+    assert_eq!(diff.changed_classes.len(), 1);
+    let class_diff = &diff.changed_classes[0];
+    assert_eq!(class_diff.added_methods, ["added"]);
+    assert_eq!(class_diff.removed_methods, ["removed"]);
+    assert_eq!(class_diff.changed_methods.len(), 1);
+    assert_eq!(class_diff.changed_methods[0].name, "changed");
+    assert_eq!(
+        diff.to_string(),
+        "+ class AddedClass
+- class RemovedClass
++ method MyClass.added
+- method MyClass.removed
+~ method MyClass.changed: parameters (x: int) -> (x: float)
+~ method MyClass.changed: return type void -> int
+"
+    );
+}
+
+#[test]
+fn merge() {
+    let mut documentation1 = documentation(vec![("Shared", Vec::new()), ("First", Vec::new())]);
+    let documentation2 = documentation(vec![("Shared", Vec::new()), ("Second", Vec::new())]);
+    documentation1.merge(documentation2).unwrap();
+    let mut names: Vec<_> = documentation1.classes.keys().collect();
+    names.sort_unstable();
+    assert_eq!(names, ["First", "Second", "Shared"]);
+
+    let conflicting = documentation(vec![
+        ("First", vec![method("new", &[], Type::Unit)]),
+        ("Other", Vec::new()),
+    ]);
+    match documentation1.merge(conflicting) {
+        Err(Error::MergeConflict(conflicts)) => assert_eq!(conflicts, ["First"]),
+        result => panic!("expected a conflict, got {:?}", result),
+    }
+    assert!(!documentation1.classes.contains_key("Other"));
+}
+
+#[test]
+fn summary() {
+    use super::helpers::get_summary;
+
+    assert_eq!(
+        get_summary("First sentence. Second one."),
+        "First sentence."
+    );
+    assert_eq!(
+        get_summary("Calls `self.run()` once.\nThen stops."),
+        "Calls `self.run()` once."
+    );
+    assert_eq!(get_summary("Spans\n two lines"), "Spans two lines");
+    assert_eq!(get_summary("# Heading\n\nText."), "");
+    assert_eq!(get_summary(""), "");
+}
+
+/// Visit `source` as if it were the root file.
+pub(crate) fn visit(source: &str) -> Documentation {
+    use syn::visit::Visit;
+
+    let mut builder = builder::DocumentationBuilder::new(
+        documentation(Vec::new()),
+        (PathBuf::from("lib.rs"), true),
+    );
+    builder.visit_file(&syn::parse_str(source).unwrap());
+    builder.disambiguate_classes();
+    builder.infer_inherits();
+    builder.mark_tool_classes();
+    builder.fill_property_defaults();
+    for class in builder.documentation.classes.values_mut() {
+        class.link_accessors();
+    }
+    builder.documentation.extract_tags(&TagRegistry::default());
+    builder.documentation
+}
+
+#[test]
+fn native_class_detection() {
+    let documentation = visit(
+        r#"
+        #[derive(Debug, NativeClass)]
+        #[inherit(Node)]
+        struct DeriveList;
+
+        #[derive(Clone, gdnative::NativeClass, Default)]
+        #[inherit(Node)]
+        struct DerivePath;
+
+        #[cfg_attr(feature = "godot", derive(NativeClass))]
+        #[cfg_attr(feature = "godot", inherit(Spatial))]
+        struct CfgAttr;
+
+        #[cfg_attr(all(feature = "godot", not(test)), derive(Debug, NativeClass), inherit(Node2D))]
+        struct CfgAttrList;
+
+        #[derive(Debug, Clone)]
+        #[cfg_attr(feature = "godot", derive(Default))]
+        struct NotNative;
+        "#,
+    );
+    let mut inherits: Vec<_> = documentation
+        .classes
+        .into_values()
+        .map(|class| (class.name, class.inherit))
+        .collect();
+    inherits.sort_unstable();
+    assert_eq!(
+        inherits,
+        [
+            ("CfgAttr", "Spatial"),
+            ("CfgAttrList", "Node2D"),
+            ("DeriveList", "Node"),
+            ("DerivePath", "Node"),
+        ]
+        .map(|(name, inherit)| (name.to_string(), inherit.to_string()))
+    );
+}
+
+#[test]
+fn infer_inherit() {
+    let documentation = visit(
+        r#"
+        #[derive(NativeClass)]
+        #[inherit(Node)]
+        struct Explicit;
+
+        #[derive(NativeClass)]
+        struct FromNew;
+
+        #[methods]
+        impl FromNew {
+            pub fn new(_owner: &Node2D) -> Self { Self }
+        }
+
+        #[derive(NativeClass)]
+        struct FromBase;
+
+        #[methods]
+        impl FromBase {
+            #[method]
+            pub fn run(&self, #[base] base: TRef<Spatial>, speed: f32) {}
+        }
+
+        #[derive(NativeClass)]
+        struct Ambiguous;
+
+        #[methods]
+        impl Ambiguous {
+            pub fn new(owner: &Node) -> Self { Self }
+            #[method]
+            pub fn run(&self, #[base] base: &Node2D) {}
+        }
+
+        #[derive(NativeClass)]
+        struct Default;
+        "#,
+    );
+    let mut inherits: Vec<_> = documentation
+        .classes
+        .into_values()
+        .map(|class| (class.name, class.inherit))
+        .collect();
+    inherits.sort_unstable();
+    assert_eq!(
+        inherits,
+        [
+            ("Ambiguous", "Reference"),
+            ("Default", "Reference"),
+            ("Explicit", "Node"),
+            ("FromBase", "Spatial"),
+            ("FromNew", "Node2D"),
+        ]
+        .map(|(name, inherit)| (name.to_string(), inherit.to_string()))
+    );
+}
+
+#[test]
+fn owner_is_not_a_parameter() {
+    let mut class = documentation(vec![("MyClass", Vec::new())])
+        .classes
+        .remove("MyClass")
+        .unwrap();
+    class.methods.clear();
+    let owner_type = class.add_method(
+        &syn::parse_str("#[method] pub fn run(&self, #[base] base: TRef<Spatial>, speed: f32) {}")
+            .unwrap(),
+        PathBuf::new(),
+    );
+    assert_eq!(owner_type.as_deref(), Some("Spatial"));
+    assert_eq!(
+        class.methods[0].parameters,
+        [(
+            String::from("speed"),
+            Type::Named(String::from("f32")),
+            ParameterAttribute::None
+        )]
+    );
+}
+
+#[test]
+fn extract_tags() {
+    let mut registry = TagRegistry::default();
+    registry.register("@author");
+    let mut documentation = String::from(
+        " Does things.\n\n @since 1.2\n @author Someone\n @unknown tag\n ```\n @since 0.1\n ```\n @emits",
+    );
+    let tags = registry.extract(&mut documentation);
+    assert_eq!(
+        documentation,
+        " Does things.\n\n @unknown tag\n ```\n @since 0.1\n ```"
+    );
+    assert_eq!(
+        tags,
+        [("since", "1.2"), ("author", "Someone"), ("emits", "")].map(|(name, value)| Tag {
+            name: name.to_string(),
+            value: value.to_string(),
+        })
+    );
+}
+
+#[test]
+fn property_defaults() {
+    let documentation = visit(
+        r#"
+        #[derive(NativeClass)]
+        struct MyClass {
+            #[property(default = 10)]
+            from_attribute: i32,
+            #[property(default = 10)]
+            both: i32,
+            #[property(get = "Self::get_vector")]
+            from_constructor: Vector2,
+            #[property]
+            not_constant: f32,
+            #[property]
+            none: bool,
+        }
+
+        #[methods]
+        impl MyClass {
+            pub fn new(_owner: &Node) -> Self {
+                let speed = 2.0;
+                Self {
+                    both: 20,
+                    from_constructor: Vector2::new(1.0, -2.0),
+                    not_constant: speed,
+                    none: Default::default(),
+                }
+            }
+        }
+        "#,
+    );
+    let defaults: Vec<_> = documentation.classes["MyClass"]
+        .properties
+        .iter()
+        .map(|property| (property.name.as_str(), property.default.as_deref()))
+        .collect();
+    assert_eq!(
+        defaults,
+        [
+            ("from_attribute", Some("10")),
+            ("both", Some("10")),
+            ("from_constructor", Some("Vector2::new(1.0, -2.0)")),
+            ("not_constant", None),
+            ("none", None),
+        ]
+    );
+}
+
+#[test]
+fn accessors() {
+    let documentation = visit(
+        r#"
+        #[derive(NativeClass)]
+        struct MyClass {
+            #[property(get = "Self::get_speed", set = "Self::set_speed")]
+            speed: f32,
+        }
+
+        #[methods]
+        impl MyClass {
+            #[method]
+            pub fn get_speed(&self) -> f32 { self.speed }
+            #[method]
+            pub fn run(&self) {}
+        }
+        "#,
+    );
+    let class = &documentation.classes["MyClass"];
+    let property = &class.properties[0];
+    assert_eq!(property.getter.as_deref(), Some("get_speed"));
+    assert_eq!(property.setter.as_deref(), Some("set_speed"));
+    let accessors: Vec<_> = class
+        .methods
+        .iter()
+        .map(|method| (method.name.as_str(), method.accessor_of.as_deref()))
+        .collect();
+    assert_eq!(accessors, [("get_speed", Some("speed")), ("run", None)]);
+}
+
+#[test]
+fn doc_aliases() {
+    let documentation = visit(
+        r#"
+        #[derive(NativeClass)]
+        #[inherit(Reference)]
+        #[doc(alias = "Graph")]
+        struct MyClass;
+
+        #[methods]
+        impl MyClass {
+            #[method]
+            #[doc(alias("connectPoints", "connect"))]
+            pub fn connect_points(&self) {}
+        }
+        "#,
+    );
+    let class = &documentation.classes["MyClass"];
+    assert_eq!(class.aliases, ["Graph"]);
+    assert_eq!(class.methods[0].aliases, ["connectPoints", "connect"]);
+}
+
+#[test]
+fn tool_classes() {
+    let documentation = visit(
+        r#"
+        #[derive(NativeClass)]
+        #[inherit(EditorPlugin)]
+        struct Plugin;
+
+        #[derive(NativeClass)]
+        #[inherit(Plugin)]
+        struct DerivedPlugin;
+
+        #[derive(NativeClass)]
+        #[inherit(EditorSpinSlider)]
+        struct Slider;
+
+        #[derive(NativeClass)]
+        #[inherit(Node)]
+        struct Registered;
+
+        #[derive(NativeClass)]
+        #[inherit(Node)]
+        struct Runtime;
+
+        fn init(handle: InitHandle) {
+            handle.add_tool_class::<Registered>();
+            handle.add_class::<Runtime>();
+        }
+        "#,
+    );
+    let is_tool = |name: &str| documentation.classes[name].is_tool;
+    assert!(is_tool("Plugin"));
+    assert!(is_tool("DerivedPlugin"));
+    assert!(!is_tool("Slider"));
+    assert!(is_tool("Registered"));
+    assert!(!is_tool("Runtime"));
+}
+
+#[test]
+fn module_documentation() {
+    let documentation = visit(
+        r#"
+        /// The graph.
+        mod graph {
+            //! Nodes and edges.
+
+            mod nodes {
+                //! The nodes.
+            }
+
+            mod undocumented {}
+        }
+        "#,
+    );
+    let modules: Vec<_> = documentation
+        .modules
+        .iter()
+        .map(|module| (module.path.as_str(), module.documentation.as_str()))
+        .collect();
+    assert_eq!(
+        modules,
+        [
+            ("graph", " The graph.\n Nodes and edges."),
+            ("graph::nodes", " The nodes."),
+        ]
+    );
+    assert_eq!(documentation.modules[1].file_stem(), "module-graph-nodes");
+}
+
+#[test]
+fn sort_members() {
+    let source = r#"
+        #[derive(NativeClass)]
+        #[inherit(Node)]
+        struct MyClass {
+            #[property]
+            speed: f32,
+            #[property]
+            acceleration: f32,
+        }
+
+        #[methods]
+        impl MyClass {
+            #[method]
+            pub fn run(&self) {}
+            #[method]
+            pub fn create() -> Instance<MyClass> { todo!() }
+            #[method]
+            pub fn jump(&self) {}
+            pub fn new(_: &Node) -> Self { todo!() }
+        }
+        "#;
+    let names = |sort: MemberSort| {
+        let mut documentation = visit(source);
+        documentation.sort_members(sort);
+        let class = &documentation.classes["MyClass"];
+        let methods: Vec<_> = class.methods.iter().map(|m| m.name.clone()).collect();
+        let properties: Vec<_> = class.properties.iter().map(|p| p.name.clone()).collect();
+        (methods, properties)
+    };
+    assert_eq!(
+        names(MemberSort::Source),
+        (
+            vec!["run".into(), "create".into(), "jump".into(), "new".into()],
+            vec!["speed".into(), "acceleration".into()]
+        )
+    );
+    assert_eq!(
+        names(MemberSort::Alphabetical),
+        (
+            vec!["create".into(), "jump".into(), "new".into(), "run".into()],
+            vec!["acceleration".into(), "speed".into()]
+        )
+    );
+    assert_eq!(
+        names(MemberSort::ByKind).0,
+        ["new", "create", "jump", "run"]
+    );
+}
+
+#[test]
+fn undocumented_lints() {
+    use crate::diagnostics::{self, Lint, LintLevel};
+
+    let source = r#"
+        /// Documented class.
+        #[derive(NativeClass)]
+        #[inherit(Node)]
+        struct MyClass {
+            #[property]
+            speed: f32,
+        }
+
+        #[methods]
+        impl MyClass {
+            /// Documented method.
+            #[method]
+            pub fn run(&self) {}
+            #[method]
+            pub fn jump(&self) {}
+        }
+        "#;
+    assert_eq!(Lint::find("gdoc002"), Some(Lint::UndocumentedMethod));
+    assert_eq!(
+        Lint::find("undocumented_property"),
+        Some(Lint::UndocumentedProperty)
+    );
+    assert_eq!(Lint::find("GDOC999"), None);
+
+    let documentation = visit(source);
+    diagnostics::configure(&HashMap::from([
+        (String::from("GDOC002"), LintLevel::Deny),
+        (String::from("undocumented_property"), LintLevel::Deny),
+        (String::from("undocumented_class"), LintLevel::Deny),
+    ]));
+    diagnostics::check_documentation(&documentation);
+    // 'jump' and 'speed'
+    assert_eq!(diagnostics::denied_count(), 2);
+    // the same diagnostics are only counted once
+    diagnostics::check_documentation(&documentation);
+    assert_eq!(diagnostics::denied_count(), 2);
+
+    // the name of a lint takes precedence over its code
+    for _ in 0..8 {
+        diagnostics::configure(&HashMap::from([
+            (String::from("GDOC002"), LintLevel::Allow),
+            (String::from("undocumented_method"), LintLevel::Deny),
+            (String::from("gdoc002"), LintLevel::Warn),
+        ]));
+        diagnostics::check_documentation(&documentation);
+        // 'jump'
+        assert_eq!(diagnostics::denied_count(), 1);
+    }
+}
+
+#[test]
+fn rpc_modes() {
+    let documentation = visit(
+        r#"
+        #[derive(NativeClass)]
+        #[inherit(Node)]
+        struct Player;
+
+        #[methods]
+        impl Player {
+            #[method(rpc = "remote")]
+            pub fn shoot(&self) {}
+            #[method(rpc = "puppet_sync")]
+            pub fn set_position(&self, position: Vector2) {}
+            #[method(rpc = "disabled")]
+            pub fn reload(&self) {}
+            #[export(rpc = "master")]
+            pub fn hit(&self, _owner: &Node) {}
+            #[method]
+            pub fn jump(&self) {}
+        }
+        "#,
+    );
+    let modes: Vec<_> = documentation.classes["Player"]
+        .methods
+        .iter()
+        .map(|method| (method.name.as_str(), method.rpc_mode))
+        .collect();
+    assert_eq!(
+        modes,
+        vec![
+            ("shoot", Some(RpcMode::Remote)),
+            ("set_position", Some(RpcMode::PuppetSync)),
+            ("reload", None),
+            ("hit", Some(RpcMode::Master)),
+            ("jump", None),
+        ]
+    );
+}
+
+#[test]
+fn constructors() {
+    let mut documentation = visit(
+        r#"
+        #[derive(NativeClass)]
+        #[inherit(Node)]
+        struct MyClass;
+
+        #[methods]
+        impl MyClass {
+            pub fn new(_: &Node) -> Self { todo!() }
+            #[method]
+            pub fn from_speed(speed: f32) -> Instance<Self, Unique> { todo!() }
+            #[method]
+            pub fn create() -> Instance<Self, Unique> {
+                Instance::emplace(Self {}).into_shared()
+            }
+            #[method]
+            pub fn spawn(&self) -> Instance<Self, Unique> {
+                Self {}.emplace()
+            }
+            #[method]
+            pub fn with_speed(speed: f32) -> Instance<Self, Unique> { todo!() }
+            #[method]
+            pub fn from_node(&self, node: Ref<Node>) {}
+        }
+        "#,
+    );
+    let constructors = |documentation: &Documentation| {
+        documentation.classes["MyClass"]
+            .methods
+            .iter()
+            .filter(|method| method.is_constructor)
+            .map(|method| method.name.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(constructors(&documentation), vec!["new", "create"]);
+    documentation.mark_constructors(&[String::from("from_*"), String::from("with_speed")]);
+    assert_eq!(
+        constructors(&documentation),
+        vec!["new", "from_speed", "create", "with_speed"]
+    );
+}
+
+#[test]
+fn class_name_collisions() {
+    let documentation = visit(
+        r#"
+        #[derive(NativeClass)]
+        #[inherit(Node)]
+        struct Unique;
+
+        mod graph {
+            /// Graph interface.
+            #[derive(NativeClass)]
+            #[inherit(Node)]
+            pub struct Interface;
+
+            #[methods]
+            impl Interface {
+                #[method]
+                pub fn connect(&self) {}
+            }
+        }
+
+        mod network {
+            /// Network interface.
+            #[derive(NativeClass)]
+            #[inherit(Reference)]
+            pub struct Interface;
+        }
+
+        mod methods {
+            #[methods]
+            impl super::Unique {
+                #[method]
+                pub fn run(&self) {}
+            }
+        }
+        "#,
+    );
+    let mut keys: Vec<_> = documentation.classes.keys().map(String::as_str).collect();
+    keys.sort_unstable();
+    assert_eq!(
+        keys,
+        vec!["Unique", "graph::Interface", "network::Interface"]
+    );
+    let graph = &documentation.classes["graph::Interface"];
+    assert_eq!(graph.name, "Interface");
+    assert_eq!(graph.documentation.trim(), "Graph interface.");
+    assert_eq!(graph.methods[0].name, "connect");
+    assert_eq!(
+        documentation.classes["network::Interface"].inherit,
+        "Reference"
+    );
+    // impl blocks in another module are merged into their class
+    assert_eq!(documentation.classes["Unique"].methods[0].name, "run");
+    assert_eq!(documentation.classes["Unique"].module, "");
+    assert_eq!(graph.module, "graph");
+}
+
+#[test]
+fn relocate_images() {
+    let directory = tempfile::tempdir().unwrap();
+    let directory = directory.path();
+    std::fs::create_dir_all(directory.join("images")).unwrap();
+    std::fs::write(directory.join("images/grid.png"), "").unwrap();
+    std::fs::write(directory.join("grid.png"), "").unwrap();
+
+    let mut documentation = documentation(vec![("MyClass", Vec::new())]);
+    documentation.root_file = directory.join("lib.rs");
+    documentation.root_documentation = String::from(
+        " ![grid](images/grid.png) ![other grid][other]
+ ![remote](https://example.com/grid.png) ![missing](missing.png)
+
+ [other]: grid.png",
+    );
+    let class = documentation.classes.get_mut("MyClass").unwrap();
+    class.file = directory.join("class.rs");
+    class.documentation = String::from(" ![grid](images/grid.png \"title\")");
+
+    let images = documentation.relocate_images();
+
+    assert_eq!(
+        documentation.root_documentation,
+        " ![grid](images/grid.png) ![other grid][other]
+ ![remote](https://example.com/grid.png) ![missing](missing.png)
+
+ [other]: images/grid-1.png"
+    );
+    assert_eq!(
+        documentation.classes["MyClass"].documentation,
+        " ![grid](images/grid.png \"title\")"
+    );
+    let mut images: Vec<_> = images.iter().collect();
+    images.sort_unstable();
+    assert_eq!(
+        images,
+        [
+            (directory.join("grid.png").as_path(), "images/grid-1.png"),
+            (
+                directory.join("images/grid.png").as_path(),
+                "images/grid.png"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn filter_classes() {
+    let patterns = |patterns: &[&str]| -> Vec<String> {
+        patterns.iter().map(|pattern| pattern.to_string()).collect()
+    };
+    let mut documentation = documentation(vec![
+        ("Graph", Vec::new()),
+        ("GraphHelper", Vec::new()),
+        ("Cache", Vec::new()),
+        ("Point", Vec::new()),
+    ]);
+    documentation.classes.get_mut("Cache").unwrap().module = String::from("internal");
+
+    documentation.filter_classes(&[], &patterns(&["*Helper", "internal::*"]));
+    let mut names: Vec<_> = documentation.classes.keys().cloned().collect();
+    names.sort();
+    assert_eq!(names, ["Graph", "Point"]);
+
+    documentation.filter_classes(&patterns(&["G?aph", "Poi"]), &[]);
+    assert_eq!(documentation.classes.keys().collect::<Vec<_>>(), ["Graph"]);
+}
+
+#[test]
+fn filter_members() {
+    let mut documentation = documentation(vec![
+        (
+            "Graph",
+            vec![
+                method("_process", &[("delta", "f64")], Type::Unit),
+                method("debug_dump", &[], Type::Unit),
+                method("get_speed", &[], Type::Named(String::from("f64"))),
+                method("connect", &[], Type::Unit),
+            ],
+        ),
+        ("Other", vec![method("connect", &[], Type::Unit)]),
+    ]);
+    let graph = documentation.classes.get_mut("Graph").unwrap();
+    graph.methods[2].accessor_of = Some(String::from("speed"));
+    graph.properties.push(Property {
+        name: String::from("speed"),
+        typ: Type::Named(String::from("f64")),
+        default: None,
+        getter: Some(String::from("get_speed")),
+        setter: None,
+        documentation: String::new(),
+        summary: String::new(),
+        tags: Vec::new(),
+        deprecated: None,
+        line: 0,
+    });
+
+    documentation.filter_members(
+        &[
+            String::from("_*"),
+            String::from("debug_*"),
+            String::from("Other::connect"),
+        ],
+        &[String::from("speed")],
+    );
+    let graph = &documentation.classes["Graph"];
+    let names: Vec<_> = graph.methods.iter().map(|method| &method.name).collect();
+    assert_eq!(names, ["get_speed", "connect"]);
+    assert!(graph.properties.is_empty());
+    assert_eq!(graph.methods[0].accessor_of, None);
+    assert!(documentation.classes["Other"].methods.is_empty());
+}
+
+#[test]
+fn module_files() {
+    let directory = tempfile::tempdir().unwrap();
+    let directory = directory.path();
+    std::fs::create_dir_all(directory.join("graph")).unwrap();
+    let class = |name: &str| {
+        format!("/// {name}.\n#[derive(NativeClass)]\n#[inherit(Reference)]\npub struct {name};\n")
+    };
+    std::fs::write(
+        directory.join("lib.rs"),
+        "/// Graphs.\nmod graph;\n/// Inline.\nmod inline {}\nmod map;",
+    )
+    .unwrap();
+    std::fs::write(
+        directory.join("graph/mod.rs"),
+        format!("//! Of nodes.\nmod edge;\n{}", class("Graph")),
+    )
+    .unwrap();
+    std::fs::write(directory.join("graph/edge.rs"), "//! Edges.").unwrap();
+    std::fs::write(
+        directory.join("map.rs"),
+        format!("//! Maps.\n{}", class("Map")),
+    )
+    .unwrap();
+
+    let (documentation, parsed) = Documentation::from_root_file(
+        String::from("modules"),
+        directory.join("lib.rs"),
+        &TagRegistry::default(),
+        &ParsedFiles::default(),
+    )
+    .unwrap();
+
+    let modules: Vec<_> = documentation
+        .modules
+        .iter()
+        .map(|module| (module.path.as_str(), module.documentation.as_str()))
+        .collect();
+    assert_eq!(
+        modules,
+        [
+            ("graph", " Graphs.\n Of nodes."),
+            ("graph::edge", " Edges."),
+            ("inline", " Inline."),
+            ("map", " Maps."),
+        ]
+    );
+    assert_eq!(documentation.classes["Graph"].module, "graph");
+    assert_eq!(documentation.classes["Map"].file, directory.join("map.rs"));
+    let mut files: Vec<_> = parsed.files.into_keys().collect();
+    files.sort();
+    assert_eq!(
+        files,
+        [
+            directory.join("graph/edge.rs"),
+            directory.join("graph/mod.rs"),
+            directory.join("lib.rs"),
+            directory.join("map.rs"),
+        ]
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serialization() {
+    let mut connect_points = method(
+        "connect_points",
+        &[("from", "int")],
+        Type::Named(String::from("bool")),
+    );
+    connect_points.parameters.push((
+        String::from("to"),
+        Type::Option(String::from("int")),
+        ParameterAttribute::None,
+    ));
+    connect_points.documentation = String::from(" Connect two points.");
+    let mut clear = method("clear", &[], Type::Unit);
+    clear.deprecated = Some(String::from("Use `reset`."));
+    let mut documentation = documentation(vec![("Graph", vec![connect_points, clear])]);
+    documentation.root_documentation = String::from(" Graphs for godot.");
+    let graph = documentation.classes.get_mut("Graph").unwrap();
+    graph.documentation = String::from(" A graph.");
+    graph.deprecated = Some(String::new());
+
+    let json = serde_json::to_string(&documentation).unwrap();
+    let deserialized: Documentation = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, documentation);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn schema_version() {
+    let mut json = serde_json::to_value(documentation(Vec::new())).unwrap();
+    assert_eq!(json["schema_version"], SCHEMA_VERSION);
+    json["schema_version"] = serde_json::Value::from(SCHEMA_VERSION + 1);
+    let error = serde_json::from_value::<Documentation>(json).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "unsupported schema version {} (expected {})",
+            SCHEMA_VERSION + 1,
+            SCHEMA_VERSION
+        )
+    );
+}
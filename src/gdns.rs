@@ -0,0 +1,77 @@
+//! Reading the `.gdns` files of a godot project.
+
+#[cfg(test)]
+mod tests;
+
+use crate::Error;
+use std::{collections::HashMap, fs, path::Path};
+
+/// Find all `.gdns` files in `project_dir` and its subdirectories, and return the
+/// mapping from the Rust name of their class to their `script_class_name`.
+///
+/// Hidden directories (like `.import`) are skipped.
+pub(crate) fn script_class_names(project_dir: &Path) -> Result<HashMap<String, String>, Error> {
+    let mut names = HashMap::new();
+    let mut directories = vec![project_dir.to_path_buf()];
+    while let Some(directory) = directories.pop() {
+        let entries = fs::read_dir(&directory).map_err(|err| Error::Io(directory.clone(), err))?;
+        for entry in entries {
+            let path = entry
+                .map_err(|err| Error::Io(directory.clone(), err))?
+                .path();
+            if path.is_dir() {
+                if !is_hidden(&path) {
+                    directories.push(path)
+                }
+            } else if path
+                .extension()
+                .is_some_and(|extension| extension == "gdns")
+            {
+                let content =
+                    fs::read_to_string(&path).map_err(|err| Error::Io(path.clone(), err))?;
+                if let Some((class_name, script_class_name)) = parse_gdns(&content) {
+                    log::debug!(
+                        "{}: renaming '{}' to '{}'",
+                        path.display(),
+                        class_name,
+                        script_class_name
+                    );
+                    names.insert(class_name, script_class_name);
+                }
+            }
+        }
+    }
+    Ok(names)
+}
+
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+}
+
+/// Get the `class_name` and `script_class_name` in the `[resource]` section of a
+/// `.gdns` file, if both are present.
+fn parse_gdns(content: &str) -> Option<(String, String)> {
+    let mut in_resource = false;
+    let mut class_name = None;
+    let mut script_class_name = None;
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_resource = line == "[resource]";
+            continue;
+        }
+        if !in_resource {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            let value = value.trim().trim_matches('"').to_string();
+            match key.trim() {
+                "class_name" => class_name = Some(value),
+                "script_class_name" => script_class_name = Some(value),
+                _ => {}
+            }
+        }
+    }
+    Some((class_name?, script_class_name?))
+}
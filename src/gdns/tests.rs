@@ -0,0 +1,60 @@
+use super::*;
+
+#[test]
+fn gdns_files() {
+    let directory = tempfile::tempdir().unwrap();
+    let directory = directory.path();
+    fs::create_dir_all(directory.join("scripts/graph")).unwrap();
+    fs::write(
+        directory.join("scripts/graph/graph.gdns"),
+        r#"[gd_resource type="NativeScript" load_steps=2 format=2]
+
+[ext_resource path="res://bin/graph.gdnlib" type="GDNativeLibrary" id=1]
+
+[resource]
+resource_name = "Graph"
+class_name = "Graph"
+library = ExtResource( 1 )
+script_class_name = "DijkstraGraph"
+"#,
+    )
+    .unwrap();
+    // no `script_class_name`
+    fs::write(
+        directory.join("point.gdns"),
+        "[resource]\nclass_name = \"Point\"\n",
+    )
+    .unwrap();
+    // keys outside of the `[resource]` section
+    fs::write(
+        directory.join("edge.gdns"),
+        "class_name = \"Edge\"\nscript_class_name = \"GraphEdge\"\n\n[resource]\n",
+    )
+    .unwrap();
+    // hidden directories are skipped
+    fs::create_dir_all(directory.join(".import")).unwrap();
+    fs::write(
+        directory.join(".import/node.gdns"),
+        "[resource]\nclass_name = \"Node\"\nscript_class_name = \"GraphNode\"\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        script_class_names(directory).unwrap(),
+        HashMap::from([(String::from("Graph"), String::from("DijkstraGraph"))])
+    );
+
+    fs::write(directory.join("invalid.gdns"), [0xff, 0xfe]).unwrap();
+    let error = script_class_names(directory).unwrap_err();
+    assert!(
+        matches!(&error, Error::Io(path, _) if path.ends_with("invalid.gdns")),
+        "{:?}",
+        error
+    );
+    let error = script_class_names(&directory.join("missing")).unwrap_err();
+    assert!(
+        matches!(&error, Error::Io(path, _) if path.ends_with("missing")),
+        "{:?}",
+        error
+    );
+}
@@ -0,0 +1,118 @@
+//! Reading external lists of godot classes.
+//!
+//! See [`ConfigFile::godot_classes_file`](crate::ConfigFile::godot_classes_file).
+
+use crate::Error;
+use std::{collections::HashMap, fs, path::Path};
+
+/// Godot classes read by [`read_godot_classes`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct GodotClasses {
+    /// Names of the classes.
+    pub(crate) names: Vec<String>,
+    /// Kind of the members of the classes, by `Class.member`: `"method"`,
+    /// `"property"`, `"signal"` or `"constant"`.
+    ///
+    /// Only class references list them.
+    pub(crate) members: HashMap<String, String>,
+}
+
+/// Read the godot classes listed at `path`.
+///
+/// `path` can be:
+/// - A directory of class references, like `doc/classes` in the godot repository
+///   or the output of `godot --doctool`: each `.xml` file is a class.
+/// - An XML dump of the class references (like `classes.xml`): each
+///   `<class name="...">` element is a class.
+/// - A JSON array of class names, like the lists bundled with this crate.
+/// - A text file with one class name per line.
+pub(crate) fn read_godot_classes(path: &Path) -> Result<GodotClasses, Error> {
+    let mut classes = GodotClasses::default();
+    if path.is_dir() {
+        let entries = fs::read_dir(path).map_err(|err| Error::Io(path.to_path_buf(), err))?;
+        for entry in entries {
+            let file = entry
+                .map_err(|err| Error::Io(path.to_path_buf(), err))?
+                .path();
+            if file.extension().is_some_and(|extension| extension == "xml") {
+                if let Some(stem) = file.file_stem().and_then(|stem| stem.to_str()) {
+                    let content =
+                        fs::read_to_string(&file).map_err(|err| Error::Io(file.clone(), err))?;
+                    classes.members.extend(xml_members(stem, &content));
+                    classes.names.push(stem.to_string());
+                }
+            }
+        }
+    } else {
+        let content = fs::read_to_string(path).map_err(|err| Error::Io(path.to_path_buf(), err))?;
+        if path.extension().is_some_and(|extension| extension == "xml") {
+            for (name, element) in xml_classes(&content) {
+                classes.members.extend(xml_members(&name, element));
+                classes.names.push(name);
+            }
+        } else {
+            classes.names = listed_classes(&content);
+        }
+    }
+    classes.names.sort_unstable();
+    classes.names.dedup();
+    log::debug!(
+        "read {} godot classes and {} members from {}",
+        classes.names.len(),
+        classes.members.len(),
+        path.display()
+    );
+    Ok(classes)
+}
+
+/// Value of the `name="..."` attribute at the start of `element`.
+fn name_attribute(element: &str) -> Option<&str> {
+    let element = &element[..element.find('>')?];
+    let name = &element[element.find("name=\"")? + "name=\"".len()..];
+    Some(&name[..name.find('"')?])
+}
+
+/// Names of the `<class name="...">` elements of `content`, with their content.
+fn xml_classes(content: &str) -> Vec<(String, &str)> {
+    content
+        .split("<class ")
+        .skip(1)
+        .filter_map(|element| Some((name_attribute(element)?.to_string(), element)))
+        .collect()
+}
+
+/// Members of the class `class`, described by the XML `content` of its class
+/// reference.
+fn xml_members(class: &str, content: &str) -> Vec<(String, String)> {
+    const ELEMENTS: &[(&str, &str)] = &[
+        ("<method ", "method"),
+        ("<member ", "property"),
+        ("<signal ", "signal"),
+        ("<constant ", "constant"),
+    ];
+    let mut members = Vec::new();
+    for (element, kind) in ELEMENTS {
+        for member in content.split(element).skip(1).filter_map(name_attribute) {
+            members.push((format!("{}.{}", class, member), kind.to_string()));
+        }
+    }
+    members
+}
+
+/// Class names in a JSON array, or one per line.
+///
+/// Lines starting with `//` or `#` are comments.
+fn listed_classes(content: &str) -> Vec<String> {
+    let mut classes = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with("//") || line.starts_with('#') {
+            continue;
+        }
+        let names = line
+            .split(',')
+            .map(|name| name.trim_matches(|c: char| c.is_whitespace() || "[]\"".contains(c)));
+        classes.extend(names.filter(|name| !name.is_empty()).map(String::from));
+    }
+    classes
+}
@@ -0,0 +1,129 @@
+//! Choosing the godot version to document.
+//!
+//! See [`ConfigFile::godot_version`](crate::ConfigFile::godot_version).
+
+#[cfg(test)]
+mod tests;
+
+use crate::{ConfigFile, Error, GodotVersion};
+use std::{fs, path::Path, process::Command};
+
+/// Select the godot version described by `config`.
+///
+/// Returns the version whose bundled classes and type mapping are used, and the
+/// version of the godot documentation to link to (like `"4.2"` or `"stable"`).
+pub(crate) fn select(config: &ConfigFile) -> Result<(GodotVersion, String), Error> {
+    let name = config.godot_version.as_deref().unwrap_or("3.5");
+    match name {
+        "stable" | "latest" => {
+            log::info!(
+                "godot version '{}': using the classes of godot {}",
+                name,
+                GodotVersion::NEWEST.as_str()
+            );
+            Ok((GodotVersion::NEWEST, name.to_string()))
+        }
+        "auto" => Ok(auto(config.godot_project.as_deref())),
+        _ => {
+            let version = match GodotVersion::try_from(name) {
+                Ok(version) => version,
+                Err(err) if config.godot_classes_file.is_some() => {
+                    GodotVersion::closest(name).ok_or(err)?
+                }
+                Err(err) => return Err(err),
+            };
+            Ok((version, name.to_string()))
+        }
+    }
+}
+
+/// Detect the godot version from the project at `godot_project`, or from the
+/// installed `godot` executable.
+///
+/// Falls back to `3.5` if no version could be detected.
+fn auto(godot_project: Option<&Path>) -> (GodotVersion, String) {
+    let detected = godot_project
+        .and_then(|project| {
+            let path = project.join("project.godot");
+            let content = fs::read_to_string(&path).ok()?;
+            Some((project_version(&content)?, path.display().to_string()))
+        })
+        .or_else(|| {
+            let output = Command::new("godot").arg("--version").output().ok()?;
+            let output = String::from_utf8_lossy(&output.stdout);
+            Some((
+                executable_version(&output)?,
+                String::from("godot --version"),
+            ))
+        });
+    let (detected, source) = match detected {
+        Some(detected) => detected,
+        None => {
+            log::warn!("could not detect the godot version: defaulting to 3.5");
+            return (GodotVersion::Version35, String::from("3.5"));
+        }
+    };
+    let (version, documentation) = match GodotVersion::try_from(detected.as_str()) {
+        Ok(version) => (version, detected.clone()),
+        Err(_) => match GodotVersion::closest(&detected) {
+            // Only the major version is known.
+            Some(version) if !detected.contains('.') => (version, version.as_str().to_string()),
+            Some(version) => (version, detected.clone()),
+            None => {
+                log::warn!(
+                    "unsupported godot version {} (from {}): defaulting to 3.5",
+                    detected,
+                    source
+                );
+                return (GodotVersion::Version35, String::from("3.5"));
+            }
+        },
+    };
+    log::info!(
+        "detected godot {} from {}: using the classes of godot {}",
+        detected,
+        source,
+        version.as_str()
+    );
+    (version, documentation)
+}
+
+/// Version targeted by a `project.godot` file.
+///
+/// This is the version in `config/features` (godot 4), or else the major version
+/// given by `config_version`.
+fn project_version(content: &str) -> Option<String> {
+    let mut config_version = None;
+    for line in content.lines() {
+        if let Some(features) = line.trim().strip_prefix("config/features=") {
+            let version = features
+                .split('"')
+                .skip(1)
+                .step_by(2)
+                .find(|feature| feature.starts_with(|c: char| c.is_ascii_digit()));
+            if let Some(version) = version {
+                return Some(version.to_string());
+            }
+        } else if let Some(version) = line.trim().strip_prefix("config_version=") {
+            config_version = match version.trim() {
+                "4" => Some("3"),
+                "5" => Some("4"),
+                _ => None,
+            };
+        }
+    }
+    config_version.map(String::from)
+}
+
+/// `major.minor` version in the output of `godot --version`, like
+/// `4.2.1.stable.official.b09f793f5`.
+fn executable_version(output: &str) -> Option<String> {
+    let mut numbers = output.trim().split('.');
+    let major = numbers.next()?;
+    let minor = numbers.next()?;
+    if major.parse::<u32>().is_ok() && minor.parse::<u32>().is_ok() {
+        Some(format!("{}.{}", major, minor))
+    } else {
+        None
+    }
+}
@@ -0,0 +1,47 @@
+use super::*;
+
+#[test]
+fn project_versions() {
+    for (content, expected) in [
+        (
+            "config_version=5\n\n[application]\n\nconfig/name=\"Graph\"\nconfig/features=PackedStringArray(\"4.2\", \"Forward Plus\")\n",
+            Some("4.2"),
+        ),
+        (
+            "[application]\nconfig/features=PackedStringArray(\"Mobile\", \"4.1\")\nconfig_version=5\n",
+            Some("4.1"),
+        ),
+        // godot 3 projects only give the major version
+        ("config_version=4\n\n[application]\n\nconfig/name=\"Graph\"\n", Some("3")),
+        (
+            "config_version=5\n\n[application]\nconfig/features=PackedStringArray(\"GL Compatibility\")\n",
+            Some("4"),
+        ),
+        ("config_version=3\n", None),
+        ("config/features=PackedStringArray(\n", None),
+        ("", None),
+    ] {
+        assert_eq!(project_version(content).as_deref(), expected, "{:?}", content);
+    }
+}
+
+#[test]
+fn executable_versions() {
+    for (output, expected) in [
+        ("4.2.1.stable.official.b09f793f5\n", Some("4.2")),
+        ("4.3.stable.official.77dcf97d8", Some("4.3")),
+        ("3.5.3.stable.official.6c814135b\n", Some("3.5")),
+        ("3.2.3.stable.mono.official", Some("3.2")),
+        ("godot: command not found", None),
+        ("4", None),
+        ("4.x.stable", None),
+        ("", None),
+    ] {
+        assert_eq!(
+            executable_version(output).as_deref(),
+            expected,
+            "{:?}",
+            output
+        );
+    }
+}
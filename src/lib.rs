@@ -26,11 +26,21 @@
 
 pub mod backend;
 mod builder;
+#[cfg(feature = "serde")]
+mod cache;
+mod checkout;
 mod config;
+pub mod diagnostics;
 pub mod documentation;
+mod gdns;
+mod godot_classes;
+mod godot_version;
 
-pub use builder::{Builder, Package};
-pub use config::ConfigFile;
+pub use builder::{BackendReport, BuildReport, Builder, FileDiff, FileDiffKind, Package};
+pub use config::{
+    AnchorStyle, CleanMode, ConfigFile, ConfigFormat, FrontMatter, FrontMatterFormat, GutConfig,
+    GutSuite, HtmlConfig, HtmlTheme, MarkdownConfig, MemberSort,
+};
 #[cfg(feature = "simplelog")]
 pub use simplelog::LevelFilter;
 
@@ -44,6 +54,55 @@ pub enum GodotVersion {
     Version34,
     /// Version `3.5`
     Version35,
+    /// Version `4.0`
+    Version40,
+    /// Version `4.1`
+    Version41,
+    /// Version `4.2`
+    Version42,
+    /// Version `4.3`
+    Version43,
+}
+
+impl GodotVersion {
+    /// Newest version with a bundled class list.
+    pub(crate) const NEWEST: Self = Self::Version43;
+
+    /// All the versions with a bundled class list.
+    pub(crate) const ALL: [Self; 8] = [
+        Self::Version32,
+        Self::Version33,
+        Self::Version34,
+        Self::Version35,
+        Self::Version40,
+        Self::Version41,
+        Self::Version42,
+        Self::Version43,
+    ];
+
+    /// The version as a string, like `"3.5"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Version32 => "3.2",
+            Self::Version33 => "3.3",
+            Self::Version34 => "3.4",
+            Self::Version35 => "3.5",
+            Self::Version40 => "4.0",
+            Self::Version41 => "4.1",
+            Self::Version42 => "4.2",
+            Self::Version43 => "4.3",
+        }
+    }
+
+    /// Latest supported version with the same major version as `version` (like
+    /// `"4.5"`), used to document versions without a bundled class list.
+    pub(crate) fn closest(version: &str) -> Option<Self> {
+        match version.split('.').next()? {
+            "3" => Some(Self::Version35),
+            "4" => Some(Self::NEWEST),
+            _ => None,
+        }
+    }
 }
 
 impl TryFrom<&str> for GodotVersion {
@@ -55,6 +114,10 @@ impl TryFrom<&str> for GodotVersion {
             "3.3" => Ok(Self::Version33),
             "3.4" => Ok(Self::Version34),
             "3.5" => Ok(Self::Version35),
+            "4.0" => Ok(Self::Version40),
+            "4.1" => Ok(Self::Version41),
+            "4.2" => Ok(Self::Version42),
+            "4.3" => Ok(Self::Version43),
             _ => Err(Error::InvalidGodotVersion(String::from(value))),
         }
     }
@@ -90,8 +153,28 @@ Please select the one you want via either:
     /// When trying to determine a root file, no suitable candidate was found.
     #[error("No crate was found with a 'cdylib' target")]
     NoCandidateCrate,
+    /// The configuration file contains an unknown key or an invalid value.
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
     #[error("Invalid or unsupported godot version: {0}")]
     InvalidGodotVersion(String),
+    /// When merging two [`Documentation`](documentation::Documentation), different
+    /// classes had the same name.
+    #[error("Cannot merge documentations: conflicting classes {0:?}")]
+    MergeConflict(Vec<String>),
+    /// Error while checking out the sources at the revision given to
+    /// [`Builder::at_revision`].
+    #[error("Git error: {0}")]
+    Git(String),
+    /// Files generated by [`Builder::check`] differ from the files on disk.
+    #[error("{} generated file(s) are out of date", .0.len())]
+    OutdatedFiles(Vec<FileDiff>),
+    /// Diagnostics denied in [`ConfigFile::lints`] were emitted.
+    #[error("{0} denied diagnostic(s) emitted")]
+    DeniedDiagnostics(usize),
+    /// A template given in [`HtmlConfig::templates`] could not be parsed.
+    #[error("Invalid template {0}: {1}")]
+    Template(std::path::PathBuf, String),
     #[cfg(feature = "simplelog")]
     /// Error while initializing logging via [`init_logger`].
     #[error("Logger initialization failed: {0}")]